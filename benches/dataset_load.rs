@@ -2,6 +2,7 @@ extern crate rust_mnist;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use rust_mnist::Mnist;
+use std::path::PathBuf;
 use std::time::Duration;
 
 fn load_dataset(c: &mut Criterion) {
@@ -10,11 +11,10 @@ fn load_dataset(c: &mut Criterion) {
         .sample_size(10)
         .measurement_time(Duration::from_secs(10));
 
-    // TODO: Make windows compatible.
     custom.bench_function("Load Default Mnist", |b| {
         b.iter(|| {
             // Load the dataset.
-            let mnist = Mnist::new("examples/MNIST_data/");
+            let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
             black_box(mnist);
         })
     });
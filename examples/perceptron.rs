@@ -2,7 +2,9 @@ extern crate rand; // For initializing weights.
 extern crate rust_mnist;
 
 use rand::distributions::{Distribution, Uniform};
-use rust_mnist::{print_image, Mnist};
+use rust_mnist::metrics::Metrics;
+use rust_mnist::{print_image, print_prediction, Mnist};
+use std::convert::TryFrom;
 use std::io::{self, Write};
 use std::path::PathBuf;
 
@@ -64,6 +66,30 @@ fn main() {
         }
     }
     println!("\nFinal Accuracy: {:.2}", accuracy);
+
+    // Evaluate the trained model on the testing data.
+    let predictions: Vec<u8> = mnist
+        .test_data
+        .iter()
+        .map(|image| {
+            let outputs = dot_product(&normalize(image), weights);
+            u8::try_from(largest(&outputs)).unwrap()
+        })
+        .collect();
+    print!(
+        "{}",
+        Metrics::from_predictions(&predictions, &mnist.test_labels)
+    );
+
+    // Show the trained model's guess for one testing image.
+    let outputs = softmax(&dot_product(&normalize(&mnist.test_data[0]), weights));
+    let predicted = u8::try_from(largest(&outputs)).unwrap();
+    print_prediction(
+        &mnist.test_data[0],
+        mnist.test_labels[0],
+        predicted,
+        &outputs,
+    );
 }
 
 fn update(weights: &mut [[f64; 785]; 10], error: &[f64; 10], image: &[f64]) {
@@ -0,0 +1,85 @@
+#ifndef RUST_MNIST_H
+#define RUST_MNIST_H
+
+/* Generated by cbindgen from src/ffi.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Mnist Mnist;
+
+/**
+ * Selects the training images and labels.
+ */
+#define MNIST_TRAIN 0
+
+/**
+ * Selects the testing images and labels.
+ */
+#define MNIST_TEST 1
+
+/**
+ * Size of one image in bytes, one byte per pixel.
+ */
+#define MNIST_IMAGE_SIZE (28 * 28)
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Load the dataset from the directory `path`. Returns null if the files cannot be read.
+ *
+ * The returned dataset must be released with `mnist_free`.
+ *
+ * # Safety
+ *
+ * `path` must be a valid, nul-terminated string.
+ */
+Mnist *mnist_load(const char *path);
+
+/**
+ * Number of samples in the split, or zero if `split` is neither `MNIST_TRAIN` nor `MNIST_TEST`.
+ *
+ * # Safety
+ *
+ * `mnist` must have been returned by `mnist_load` and not yet freed.
+ */
+size_t mnist_len(const Mnist *mnist, int split);
+
+/**
+ * Pointer to the `MNIST_IMAGE_SIZE` row-major pixels of an image, or null if `index` is out of
+ * range. The pixels stay valid until the dataset is freed.
+ *
+ * # Safety
+ *
+ * `mnist` must have been returned by `mnist_load` and not yet freed.
+ */
+const uint8_t *mnist_image_ptr(const Mnist *mnist, int split, size_t index);
+
+/**
+ * Label of an image, or -1 if `index` is out of range.
+ *
+ * # Safety
+ *
+ * `mnist` must have been returned by `mnist_load` and not yet freed.
+ */
+int mnist_label(const Mnist *mnist, int split, size_t index);
+
+/**
+ * Release a dataset returned by `mnist_load`. Does nothing if `mnist` is null.
+ *
+ * # Safety
+ *
+ * `mnist` must have been returned by `mnist_load` and not already freed.
+ */
+void mnist_free(Mnist *mnist);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RUST_MNIST_H */
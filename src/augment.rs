@@ -0,0 +1,79 @@
+//! Transforms of normalized images that can be recorded and replayed, unlike closures.
+
+use crate::{IMAGE_COLUMNS, IMAGE_ROWS};
+use std::convert::TryFrom;
+
+/// A transform of a normalized 28x28 image, for use with [`LoadOptions::transform`] or
+/// [`DataLoader::transform`], that a [`Manifest`] can record and [replay](Manifest::apply).
+///
+/// Each is deterministic, so applying the same ones to the same data makes the same dataset.
+///
+/// [`LoadOptions::transform`]: crate::LoadOptions::transform
+/// [`DataLoader::transform`]: crate::DataLoader::transform
+/// [`Manifest`]: crate::Manifest
+/// [`Manifest::apply`]: crate::Manifest::apply
+///
+/// # Examples
+/// ```
+/// use rust_mnist::Augmentation;
+///
+/// let mut image = [0.0; 784];
+/// image[5 * 28 + 5] = 1.0;
+/// Augmentation::Shift { dx: 2, dy: 1 }.apply(&mut image);
+/// assert_eq!(image[6 * 28 + 7], 1.0);
+/// assert_eq!(image.iter().sum::<f32>(), 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Augmentation {
+    /// Move the image `dx` pixels right and `dy` pixels down, or left and up if negative,
+    /// repeating the edge pixels into the space left behind.
+    Shift { dx: i32, dy: i32 },
+    /// Mirror the image left to right.
+    FlipHorizontal,
+    /// Limit every value to `[min, max]`.
+    Clamp { min: f32, max: f32 },
+}
+
+impl Augmentation {
+    /// Transform `image`, a normalized 28x28 image in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `image` does not hold 784 values.
+    pub fn apply(self, image: &mut [f32]) {
+        assert_eq!(
+            image.len(),
+            IMAGE_ROWS * IMAGE_COLUMNS,
+            "Augmentations transform 28x28 images."
+        );
+        match self {
+            Augmentation::Shift { dx, dy } => {
+                let original = image.to_vec();
+                // Where a pixel moved by `offset` along an axis of `len` pixels comes from.
+                let from = |index: usize, offset: i32, len: usize| {
+                    let distance = usize::try_from(offset.unsigned_abs()).unwrap_or(usize::MAX);
+                    if offset >= 0 {
+                        index.saturating_sub(distance)
+                    } else {
+                        index.saturating_add(distance).min(len - 1)
+                    }
+                };
+                for (row, pixels) in image.chunks_exact_mut(IMAGE_COLUMNS).enumerate() {
+                    let source = from(row, dy, IMAGE_ROWS) * IMAGE_COLUMNS;
+                    for (column, pixel) in pixels.iter_mut().enumerate() {
+                        *pixel = original[source + from(column, dx, IMAGE_COLUMNS)];
+                    }
+                }
+            }
+            Augmentation::FlipHorizontal => image
+                .chunks_exact_mut(IMAGE_COLUMNS)
+                .for_each(<[f32]>::reverse),
+            Augmentation::Clamp { min, max } => {
+                for pixel in image {
+                    *pixel = pixel.max(min).min(max);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use crate::evaluate::predict_all;
+use crate::pca::Pca;
+use crate::{IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES};
+use std::sync::OnceLock;
+use std::thread;
+
+/// A k-nearest-neighbour classifier, predicting the most common label among the `k` training
+/// images closest to an image in Euclidean distance.
+///
+/// With `k` of 3 it reaches about 97% accuracy on the MNIST testing data, at the cost of
+/// comparing every image against the whole training set; project onto principal components with
+/// [`Knn::pca`] and spread the work over threads with [`Knn::workers`] to speed it up.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::baselines::Knn;
+/// use rust_mnist::Mnist;
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+/// let knn = Knn::new(&mnist.train_data, &mnist.train_labels, 3).pca(50);
+/// let metrics = mnist.evaluate(8, |image| knn.predict(image));
+/// println!("kNN accuracy: {:.4}", metrics.accuracy);
+/// ```
+pub struct Knn<'a> {
+    images: &'a [[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &'a [u8],
+    k: usize,
+    /// Number of principal components to compare images along, if reducing.
+    n_components: Option<usize>,
+    /// Principal components and the training images projected onto them, fitted on first use.
+    reduced: OnceLock<(Pca, Vec<Vec<f32>>)>,
+    workers: usize,
+}
+
+impl<'a> Knn<'a> {
+    /// Create a classifier comparing images against `images`, labelled with `labels`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero or larger than the number of images, or if there is not exactly one
+    /// label per image.
+    #[must_use]
+    pub fn new(
+        images: &'a [[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+        labels: &'a [u8],
+        k: usize,
+    ) -> Knn<'a> {
+        assert!(
+            (1..=images.len()).contains(&k),
+            "k must be between one and the number of training images."
+        );
+        assert_eq!(
+            images.len(),
+            labels.len(),
+            "Number of images does not match number of labels."
+        );
+
+        Knn {
+            images,
+            labels,
+            k,
+            n_components: None,
+            reduced: OnceLock::new(),
+            workers: 0,
+        }
+    }
+
+    /// Compare images by their coordinates along the first `n_components` principal components
+    /// of the training images, rather than pixel by pixel. Around 50 components keep accuracy
+    /// while making predictions much faster.
+    ///
+    /// The components are fitted on the first prediction, using the threads set with
+    /// [`Knn::workers`] whichever order the two are called in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_components` is zero or more than 784.
+    #[must_use]
+    pub fn pca(mut self, n_components: usize) -> Self {
+        assert!(
+            (1..=IMAGE_ROWS * IMAGE_COLUMNS).contains(&n_components),
+            "Number of components must be between 1 and 784."
+        );
+        self.n_components = Some(n_components);
+        self.reduced = OnceLock::new();
+        self
+    }
+
+    /// Compute distances on `workers` threads. With zero workers (the default) everything runs
+    /// on the calling thread.
+    #[must_use]
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = workers;
+        self
+    }
+
+    /// Predict the label of an image.
+    ///
+    /// Ties between labels go to the label of the nearest tied neighbour.
+    #[must_use]
+    pub fn predict(&self, image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> u8 {
+        self.predict_on(image, self.workers)
+    }
+
+    /// Predict the label of every image, splitting the images between the worker threads.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_mnist::baselines::Knn;
+    ///
+    /// // Digit `d` is a bar across rows `2d + 4` and `2d + 5`, drawn at brightness `shade`.
+    /// let bar = |digit: u8, shade: u8| {
+    ///     let mut image = [0; 784];
+    ///     let row = 2 * usize::from(digit) + 4;
+    ///     image[row * 28..(row + 2) * 28].fill(shade);
+    ///     image
+    /// };
+    /// let (images, labels): (Vec<_>, Vec<_>) =
+    ///     (0..10).flat_map(|digit| [(bar(digit, 255), digit), (bar(digit, 200), digit)]).unzip();
+    /// let queries: Vec<_> = (0..10).map(|digit| bar(digit, 150)).collect();
+    ///
+    /// let knn = Knn::new(&images, &labels, 3).workers(2);
+    /// assert_eq!(knn.predict_batch(&queries), (0..10).collect::<Vec<u8>>());
+    ///
+    /// // Comparing along a few principal components finds the same neighbours.
+    /// let knn = Knn::new(&images, &labels, 3).pca(12);
+    /// assert_eq!(knn.predict_batch(&queries), (0..10).collect::<Vec<u8>>());
+    /// ```
+    #[must_use]
+    pub fn predict_batch(&self, images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]]) -> Vec<u8> {
+        // Each thread handles whole images, so there is no need to split up distances too.
+        predict_all(images, self.workers, &|image| self.predict_on(image, 0))
+    }
+
+    /// Predict the label of an image, computing distances on `workers` threads.
+    fn predict_on(&self, image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], workers: usize) -> u8 {
+        let neighbours = match self.reduced() {
+            Some((pca, features)) => {
+                let query = pca.project(image);
+                self.nearest(workers, |i| {
+                    let distance: f32 = features[i]
+                        .iter()
+                        .zip(&query)
+                        .map(|(a, b)| (a - b) * (a - b))
+                        .sum();
+                    f64::from(distance)
+                })
+            }
+            None => self.nearest(workers, |i| {
+                let distance: u32 = self.images[i]
+                    .iter()
+                    .zip(image.iter())
+                    .map(|(&a, &b)| u32::from(a.abs_diff(b)).pow(2))
+                    .sum();
+                f64::from(distance)
+            }),
+        };
+        vote(&neighbours)
+    }
+
+    /// Principal components and projected training images, fitting them if not done yet, or
+    /// `None` if comparing pixels.
+    fn reduced(&self) -> Option<&(Pca, Vec<Vec<f32>>)> {
+        let n_components = self.n_components?;
+        Some(self.reduced.get_or_init(|| {
+            let pca = Pca::fit(self.images, n_components, self.workers);
+            let features = predict_all(self.images, self.workers, &|image| pca.project(image));
+            (pca, features)
+        }))
+    }
+
+    /// The `k` nearest training samples as `(distance, label)` pairs, closest first, given the
+    /// distance to each training sample by index and computing distances on `workers` threads.
+    fn nearest(&self, workers: usize, distance: impl Fn(usize) -> f64 + Sync) -> Vec<(f64, u8)> {
+        let scan = |range: std::ops::Range<usize>| {
+            let mut nearest: Vec<(f64, u8)> = Vec::with_capacity(self.k + 1);
+            for i in range {
+                let candidate = (distance(i), self.labels[i]);
+                if nearest.len() < self.k || candidate.0 < nearest[self.k - 1].0 {
+                    let at = nearest.partition_point(|&(d, _)| d <= candidate.0);
+                    nearest.insert(at, candidate);
+                    nearest.truncate(self.k);
+                }
+            }
+            nearest
+        };
+
+        if workers == 0 {
+            return scan(0..self.images.len());
+        }
+        let per_worker = self.images.len().div_ceil(workers);
+        let mut nearest: Vec<(f64, u8)> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..self.images.len())
+                .step_by(per_worker)
+                .map(|start| {
+                    let end = (start + per_worker).min(self.images.len());
+                    scope.spawn(move || scan(start..end))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("kNN worker panicked."))
+                .collect()
+        });
+        nearest.sort_by(|a, b| a.0.total_cmp(&b.0));
+        nearest.truncate(self.k);
+        nearest
+    }
+}
+
+/// Most common label among neighbours sorted closest first, preferring the closest on ties.
+fn vote(neighbours: &[(f64, u8)]) -> u8 {
+    let mut votes = [0; NUM_CLASSES];
+    for &(_, label) in neighbours {
+        votes[usize::from(label)] += 1;
+    }
+    let most = votes.iter().copied().max().unwrap_or(0);
+    neighbours
+        .iter()
+        .map(|&(_, label)| label)
+        .find(|&label| votes[usize::from(label)] == most)
+        .unwrap_or(0)
+}
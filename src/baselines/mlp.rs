@@ -0,0 +1,405 @@
+use super::softmax::softmax;
+use super::weights;
+use crate::evaluate::argmax;
+use crate::{DataLoader, IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::io;
+use std::path::Path;
+
+/// How an [`Mlp`] turns gradients into parameter updates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Optimizer {
+    /// Plain stochastic gradient descent: step against the gradient, scaled by the learning rate.
+    Sgd,
+    /// Adam, with the usual decay rates of 0.9 and 0.999. Works best with a much smaller learning
+    /// rate than SGD, such as 0.001.
+    Adam,
+}
+
+/// A fully connected neural network with rectified linear (`ReLU`) hidden layers and a softmax
+/// output, trained with minibatch gradient descent.
+///
+/// A single hidden layer of 128 units reaches about 97% accuracy on the MNIST testing data in a
+/// few epochs. Like [`SoftmaxRegression`](super::SoftmaxRegression), training is fully
+/// determined by the seed.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::baselines::{Mlp, Optimizer};
+/// use rust_mnist::Mnist;
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+/// let mut model = Mlp::new(&[128], 7)
+///     .optimizer(Optimizer::Adam)
+///     .learning_rate(0.001)
+///     .epochs(5);
+/// model.fit(&mnist.train_data, &mnist.train_labels);
+///
+/// let metrics = mnist.evaluate(8, |image| model.predict(image));
+/// println!("MLP accuracy: {:.4}", metrics.accuracy);
+/// ```
+pub struct Mlp {
+    layers: Vec<Layer>,
+    optimizer: Optimizer,
+    learning_rate: f32,
+    batch_size: usize,
+    epochs: usize,
+    seed: u64,
+    /// Adam's running first and second moments of each layer's gradients.
+    moments: Vec<(Vec<f32>, Vec<f32>)>,
+    /// Number of Adam updates made so far.
+    steps: i32,
+}
+
+/// One fully connected layer. Its parameters are stored flat: the weights, one row of `inputs`
+/// per output, followed by one bias per output.
+pub(super) struct Layer {
+    pub(super) inputs: usize,
+    pub(super) outputs: usize,
+    pub(super) parameters: Vec<f32>,
+}
+
+impl Layer {
+    /// Output of the layer before its activation.
+    fn forward(&self, input: &[f32]) -> Vec<f32> {
+        let (weights, biases) = self.parameters.split_at(self.inputs * self.outputs);
+        weights
+            .chunks(self.inputs)
+            .zip(biases)
+            .map(|(row, bias)| bias + row.iter().zip(input).map(|(w, x)| w * x).sum::<f32>())
+            .collect()
+    }
+}
+
+impl Mlp {
+    /// Create an untrained network with hidden layers of the given sizes, in order from the
+    /// input, and random weights drawn from `seed`. The same seed also decides the order samples
+    /// are visited in during training. With no hidden layers the network is a softmax
+    /// regression.
+    ///
+    /// Trains for 3 epochs of batches of 64 using SGD with a learning rate of 0.1 unless told
+    /// otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a hidden layer has no units.
+    #[must_use]
+    pub fn new(hidden: &[usize], seed: u64) -> Mlp {
+        assert!(
+            hidden.iter().all(|&units| units > 0),
+            "Hidden layers must have at least one unit."
+        );
+
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut sizes = vec![IMAGE_ROWS * IMAGE_COLUMNS];
+        sizes.extend_from_slice(hidden);
+        sizes.push(NUM_CLASSES);
+        let layers = sizes
+            .windows(2)
+            .map(|pair| {
+                let (inputs, outputs) = (pair[0], pair[1]);
+                // He initialisation, suited to ReLU activations; biases start at zero.
+                #[allow(clippy::cast_precision_loss)]
+                let limit = (6.0 / inputs as f32).sqrt();
+                let mut parameters: Vec<f32> = (0..inputs * outputs)
+                    .map(|_| rng.gen_range(-limit..limit))
+                    .collect();
+                parameters.resize(parameters.len() + outputs, 0.0);
+                Layer {
+                    inputs,
+                    outputs,
+                    parameters,
+                }
+            })
+            .collect();
+
+        Mlp::from_layers(layers, seed)
+    }
+
+    /// Read a network saved with [`Mlp::save`]. It trains with the defaults of [`Mlp::new`] and a
+    /// seed of zero unless told otherwise.
+    ///
+    /// A model saved with [`SoftmaxRegression::save`](super::SoftmaxRegression::save) loads as a
+    /// network without hidden layers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not hold a network over MNIST images.
+    pub fn load(path: &Path) -> io::Result<Mlp> {
+        Ok(Mlp::from_layers(weights::load(path)?, 0))
+    }
+
+    /// Write the network's weights to `path`: as JSON if it has a `.json` extension, and in a
+    /// compact binary format otherwise. Training settings and optimizer state are not saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::baselines::Mlp;
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let mut model = Mlp::new(&[128], 7);
+    /// model.fit(&mnist.train_data, &mnist.train_labels);
+    /// model.save(Path::new("mlp.bin"))?;
+    ///
+    /// let model = Mlp::load(Path::new("mlp.bin"))?;
+    /// println!("Predicted {}", model.predict(&mnist.test_data[0]));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        weights::save(path, &self.layers)
+    }
+
+    /// An untrained network with the given layers and default training settings.
+    fn from_layers(layers: Vec<Layer>, seed: u64) -> Mlp {
+        Mlp {
+            layers,
+            optimizer: Optimizer::Sgd,
+            learning_rate: 0.1,
+            batch_size: 64,
+            epochs: 3,
+            seed,
+            moments: Vec::new(),
+            steps: 0,
+        }
+    }
+
+    /// How gradients are turned into updates; [`Optimizer::Sgd`] by default.
+    #[must_use]
+    pub fn optimizer(mut self, optimizer: Optimizer) -> Self {
+        self.optimizer = optimizer;
+        self
+    }
+
+    /// Step size of each update.
+    #[must_use]
+    pub fn learning_rate(mut self, learning_rate: f32) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    /// Number of samples averaged over for each update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    #[must_use]
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "Batch size must be at least one.");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Number of passes over the training data made by [`Mlp::fit`].
+    #[must_use]
+    pub fn epochs(mut self, epochs: usize) -> Self {
+        self.epochs = epochs;
+        self
+    }
+
+    /// Train on `images` labelled with `labels`, continuing from the current weights.
+    ///
+    /// Returns the mean cross-entropy loss of each epoch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not exactly one label per image.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_mnist::baselines::{Mlp, Optimizer};
+    ///
+    /// // Digit `d` is a bar across rows `2d + 4` and `2d + 5`, drawn at brightness `shade`.
+    /// let bar = |digit: u8, shade: u8| {
+    ///     let mut image = [0; 784];
+    ///     let row = 2 * usize::from(digit) + 4;
+    ///     image[row * 28..(row + 2) * 28].fill(shade);
+    ///     image
+    /// };
+    /// let (images, labels): (Vec<_>, Vec<_>) =
+    ///     (0..10).flat_map(|digit| [(bar(digit, 255), digit), (bar(digit, 200), digit)]).unzip();
+    ///
+    /// let mut model = Mlp::new(&[32], 7)
+    ///     .optimizer(Optimizer::Adam)
+    ///     .learning_rate(0.01)
+    ///     .batch_size(4)
+    ///     .epochs(20);
+    /// let losses = model.fit(&images, &labels);
+    /// assert!(losses[losses.len() - 1] < losses[0] / 4.0);
+    /// assert!((0..10).all(|digit| model.predict(&bar(digit, 150)) == digit));
+    /// ```
+    pub fn fit(&mut self, images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]], labels: &[u8]) -> Vec<f32> {
+        let mut loader = DataLoader::new(images, labels, self.batch_size)
+            .shuffle(true)
+            .seed(self.seed);
+
+        let mut losses = Vec::with_capacity(self.epochs);
+        for _ in 0..self.epochs {
+            let mut total_loss = 0.0;
+            for batch in loader.epoch() {
+                total_loss += self.step(&batch.images, &batch.labels);
+            }
+            #[allow(clippy::cast_precision_loss)]
+            losses.push(total_loss / images.len().max(1) as f32);
+        }
+        losses
+    }
+
+    /// Probability of each label for an image.
+    #[must_use]
+    pub fn predict_proba(&self, image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> [f32; NUM_CLASSES] {
+        let pixels: Vec<f32> = image
+            .iter()
+            .map(|&pixel| f32::from(pixel) / 255.0)
+            .collect();
+        let activations = self.forward(pixels);
+        probabilities(&activations[activations.len() - 1])
+    }
+
+    /// Predict the label of an image.
+    #[must_use]
+    pub fn predict(&self, image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> u8 {
+        argmax(&self.predict_proba(image))
+    }
+
+    /// Input of every layer followed by the output scores of the last, with pixels already
+    /// scaled to `[0, 1]`.
+    fn forward(&self, pixels: Vec<f32>) -> Vec<Vec<f32>> {
+        let mut activations = vec![pixels];
+        for (index, layer) in self.layers.iter().enumerate() {
+            let mut output = layer.forward(&activations[index]);
+            if index + 1 < self.layers.len() {
+                for value in &mut output {
+                    *value = value.max(0.0);
+                }
+            }
+            activations.push(output);
+        }
+        activations
+    }
+
+    /// Take one optimizer step on a batch of scaled images, returning the summed cross-entropy
+    /// loss of the batch before the step.
+    fn step(&mut self, images: &[f32], labels: &[u8]) -> f32 {
+        let mut gradients: Vec<Vec<f32>> = self
+            .layers
+            .iter()
+            .map(|layer| vec![0.0; layer.parameters.len()])
+            .collect();
+        let mut loss = 0.0;
+
+        for (pixels, &label) in images.chunks(IMAGE_ROWS * IMAGE_COLUMNS).zip(labels) {
+            let activations = self.forward(pixels.to_vec());
+            let mut deltas = probabilities(&activations[activations.len() - 1]).to_vec();
+            loss -= deltas[usize::from(label)].max(f32::MIN_POSITIVE).ln();
+            deltas[usize::from(label)] -= 1.0;
+
+            for (index, layer) in self.layers.iter().enumerate().rev() {
+                let input = &activations[index];
+                let (weight_gradients, bias_gradients) =
+                    gradients[index].split_at_mut(layer.inputs * layer.outputs);
+                for ((row, bias), delta) in weight_gradients
+                    .chunks_mut(layer.inputs)
+                    .zip(bias_gradients)
+                    .zip(&deltas)
+                {
+                    for (gradient, x) in row.iter_mut().zip(input) {
+                        *gradient += delta * x;
+                    }
+                    *bias += delta;
+                }
+
+                if index > 0 {
+                    // Backpropagate through the weights, then through the ReLU of the input.
+                    let weights = &layer.parameters[..layer.inputs * layer.outputs];
+                    let mut previous = vec![0.0; layer.inputs];
+                    for (row, delta) in weights.chunks(layer.inputs).zip(&deltas) {
+                        for (sum, w) in previous.iter_mut().zip(row) {
+                            *sum += w * delta;
+                        }
+                    }
+                    for (sum, &x) in previous.iter_mut().zip(input) {
+                        if x <= 0.0 {
+                            *sum = 0.0;
+                        }
+                    }
+                    deltas = previous;
+                }
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let scale = 1.0 / labels.len() as f32;
+        for gradient in gradients.iter_mut().flatten() {
+            *gradient *= scale;
+        }
+        self.update(&gradients);
+        loss
+    }
+
+    /// Apply averaged gradients, one vector per layer, with the configured optimizer.
+    fn update(&mut self, gradients: &[Vec<f32>]) {
+        const BETA1: f32 = 0.9;
+        const BETA2: f32 = 0.999;
+        const EPSILON: f32 = 1e-8;
+
+        match self.optimizer {
+            Optimizer::Sgd => {
+                for (layer, gradients) in self.layers.iter_mut().zip(gradients) {
+                    for (parameter, gradient) in layer.parameters.iter_mut().zip(gradients) {
+                        *parameter -= self.learning_rate * gradient;
+                    }
+                }
+            }
+            Optimizer::Adam => {
+                if self.moments.is_empty() {
+                    self.moments = self
+                        .layers
+                        .iter()
+                        .map(|layer| {
+                            let len = layer.parameters.len();
+                            (vec![0.0; len], vec![0.0; len])
+                        })
+                        .collect();
+                }
+                self.steps = self.steps.saturating_add(1);
+                let first_correction = 1.0 - BETA1.powi(self.steps);
+                let second_correction = 1.0 - BETA2.powi(self.steps);
+
+                for ((layer, gradients), (first, second)) in
+                    self.layers.iter_mut().zip(gradients).zip(&mut self.moments)
+                {
+                    for (((parameter, &gradient), m), v) in layer
+                        .parameters
+                        .iter_mut()
+                        .zip(gradients)
+                        .zip(first.iter_mut())
+                        .zip(second.iter_mut())
+                    {
+                        *m = BETA1 * *m + (1.0 - BETA1) * gradient;
+                        *v = BETA2 * *v + (1.0 - BETA2) * gradient * gradient;
+                        let m_hat = *m / first_correction;
+                        let v_hat = *v / second_correction;
+                        *parameter -= self.learning_rate * m_hat / (v_hat.sqrt() + EPSILON);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Softmax of the output scores of the last layer.
+fn probabilities(scores: &[f32]) -> [f32; NUM_CLASSES] {
+    let mut probabilities = [0.0; NUM_CLASSES];
+    probabilities.copy_from_slice(scores);
+    softmax(&mut probabilities);
+    probabilities
+}
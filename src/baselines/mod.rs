@@ -0,0 +1,11 @@
+//! Simple reference classifiers, to check a data pipeline end to end and to have an accuracy to
+//! beat.
+
+mod knn;
+mod mlp;
+mod softmax;
+mod weights;
+
+pub use knn::Knn;
+pub use mlp::{Mlp, Optimizer};
+pub use softmax::SoftmaxRegression;
@@ -0,0 +1,281 @@
+use super::mlp::Layer;
+use super::weights;
+use crate::evaluate::argmax;
+use crate::{DataLoader, IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::io;
+use std::path::Path;
+
+/// A multinomial logistic-regression ("softmax regression") classifier over raw pixels, trained
+/// with minibatch stochastic gradient descent.
+///
+/// A few epochs are enough to reach about 92% accuracy on the MNIST testing data. Training is
+/// fully determined by the seed, so the same data and settings always produce the same model.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::baselines::SoftmaxRegression;
+/// use rust_mnist::Mnist;
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+/// let mut model = SoftmaxRegression::new(7).learning_rate(0.5).epochs(5);
+/// let losses = model.fit(&mnist.train_data, &mnist.train_labels);
+/// println!("Final training loss: {:.4}", losses[losses.len() - 1]);
+///
+/// let metrics = mnist.evaluate(8, |image| model.predict(image));
+/// println!("Softmax regression accuracy: {:.4}", metrics.accuracy);
+/// ```
+pub struct SoftmaxRegression {
+    /// One row of pixel weights per class.
+    weights: Vec<[f32; IMAGE_ROWS * IMAGE_COLUMNS]>,
+    biases: [f32; NUM_CLASSES],
+    learning_rate: f32,
+    batch_size: usize,
+    epochs: usize,
+    seed: u64,
+}
+
+impl SoftmaxRegression {
+    /// Create an untrained classifier with small random weights drawn from `seed`. The same seed
+    /// also decides the order samples are visited in during training.
+    ///
+    /// Trains for 3 epochs of batches of 64 with a learning rate of 0.1 unless told otherwise.
+    #[must_use]
+    pub fn new(seed: u64) -> SoftmaxRegression {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let weights = (0..NUM_CLASSES)
+            .map(|_| {
+                let mut row = [0.0; IMAGE_ROWS * IMAGE_COLUMNS];
+                for weight in &mut row {
+                    *weight = rng.gen_range(-0.01..0.01);
+                }
+                row
+            })
+            .collect();
+
+        SoftmaxRegression {
+            weights,
+            biases: [0.0; NUM_CLASSES],
+            learning_rate: 0.1,
+            batch_size: 64,
+            epochs: 3,
+            seed,
+        }
+    }
+
+    /// Read a model saved with [`SoftmaxRegression::save`]. It trains with the defaults of
+    /// [`SoftmaxRegression::new`] and a seed of zero unless told otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not hold a single-layer model over
+    /// MNIST images.
+    pub fn load(path: &Path) -> io::Result<SoftmaxRegression> {
+        let mut layers = weights::load(path)?;
+        if layers.len() != 1 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Softmax regression has exactly one layer.",
+            ));
+        }
+        let Layer { parameters, .. } = layers.remove(0);
+        let (weights, biases) = parameters.split_at(NUM_CLASSES * IMAGE_ROWS * IMAGE_COLUMNS);
+
+        let mut model = SoftmaxRegression::new(0);
+        for (row, weights) in model
+            .weights
+            .iter_mut()
+            .zip(weights.chunks(IMAGE_ROWS * IMAGE_COLUMNS))
+        {
+            row.copy_from_slice(weights);
+        }
+        model.biases.copy_from_slice(biases);
+        Ok(model)
+    }
+
+    /// Write the model's weights to `path`: as JSON if it has a `.json` extension, and in a
+    /// compact binary format otherwise. Training settings are not saved.
+    ///
+    /// The file can also be read with [`Mlp::load`](super::Mlp::load).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::baselines::SoftmaxRegression;
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let mut model = SoftmaxRegression::new(7);
+    /// model.fit(&mnist.train_data, &mnist.train_labels);
+    /// model.save(Path::new("softmax.json"))?;
+    ///
+    /// let model = SoftmaxRegression::load(Path::new("softmax.json"))?;
+    /// println!("Predicted {}", model.predict(&mnist.test_data[0]));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut parameters: Vec<f32> = self.weights.iter().flatten().copied().collect();
+        parameters.extend_from_slice(&self.biases);
+        let layer = Layer {
+            inputs: IMAGE_ROWS * IMAGE_COLUMNS,
+            outputs: NUM_CLASSES,
+            parameters,
+        };
+        weights::save(path, &[layer])
+    }
+
+    /// Step size of each gradient descent update.
+    #[must_use]
+    pub fn learning_rate(mut self, learning_rate: f32) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    /// Number of samples averaged over for each update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    #[must_use]
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "Batch size must be at least one.");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Number of passes over the training data made by [`SoftmaxRegression::fit`].
+    #[must_use]
+    pub fn epochs(mut self, epochs: usize) -> Self {
+        self.epochs = epochs;
+        self
+    }
+
+    /// Train on `images` labelled with `labels`, continuing from the current weights.
+    ///
+    /// Returns the mean cross-entropy loss of each epoch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not exactly one label per image.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_mnist::baselines::SoftmaxRegression;
+    ///
+    /// // Digit `d` is a bar across rows `2d + 4` and `2d + 5`, drawn at brightness `shade`.
+    /// let bar = |digit: u8, shade: u8| {
+    ///     let mut image = [0; 784];
+    ///     let row = 2 * usize::from(digit) + 4;
+    ///     image[row * 28..(row + 2) * 28].fill(shade);
+    ///     image
+    /// };
+    /// let (images, labels): (Vec<_>, Vec<_>) =
+    ///     (0..10).flat_map(|digit| [(bar(digit, 255), digit), (bar(digit, 200), digit)]).unzip();
+    ///
+    /// let mut model = SoftmaxRegression::new(7).batch_size(4).epochs(20);
+    /// let losses = model.fit(&images, &labels);
+    /// assert!(losses[losses.len() - 1] < losses[0] / 4.0);
+    /// assert!((0..10).all(|digit| model.predict(&bar(digit, 150)) == digit));
+    /// ```
+    pub fn fit(&mut self, images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]], labels: &[u8]) -> Vec<f32> {
+        let mut loader = DataLoader::new(images, labels, self.batch_size)
+            .shuffle(true)
+            .seed(self.seed);
+
+        let mut losses = Vec::with_capacity(self.epochs);
+        for _ in 0..self.epochs {
+            let mut total_loss = 0.0;
+            for batch in loader.epoch() {
+                total_loss += self.step(&batch.images, &batch.labels);
+            }
+            #[allow(clippy::cast_precision_loss)]
+            losses.push(total_loss / images.len().max(1) as f32);
+        }
+        losses
+    }
+
+    /// Probability of each label for an image.
+    #[must_use]
+    pub fn predict_proba(&self, image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> [f32; NUM_CLASSES] {
+        let pixels: Vec<f32> = image
+            .iter()
+            .map(|&pixel| f32::from(pixel) / 255.0)
+            .collect();
+        self.probabilities(&pixels)
+    }
+
+    /// Predict the label of an image.
+    #[must_use]
+    pub fn predict(&self, image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> u8 {
+        argmax(&self.predict_proba(image))
+    }
+
+    /// Softmax of the class scores of one image, with pixels already scaled to `[0, 1]`.
+    fn probabilities(&self, pixels: &[f32]) -> [f32; NUM_CLASSES] {
+        let mut scores = self.biases;
+        for (score, weights) in scores.iter_mut().zip(&self.weights) {
+            *score += weights.iter().zip(pixels).map(|(w, x)| w * x).sum::<f32>();
+        }
+        softmax(&mut scores);
+        scores
+    }
+
+    /// Take one gradient descent step on a batch of scaled images, returning the summed
+    /// cross-entropy loss of the batch before the step.
+    fn step(&mut self, images: &[f32], labels: &[u8]) -> f32 {
+        let mut weight_gradients = vec![[0.0; IMAGE_ROWS * IMAGE_COLUMNS]; NUM_CLASSES];
+        let mut bias_gradients = [0.0; NUM_CLASSES];
+        let mut loss = 0.0;
+
+        for (pixels, &label) in images.chunks(IMAGE_ROWS * IMAGE_COLUMNS).zip(labels) {
+            let mut errors = self.probabilities(pixels);
+            loss -= errors[usize::from(label)].max(f32::MIN_POSITIVE).ln();
+            errors[usize::from(label)] -= 1.0;
+
+            for ((gradients, bias), error) in weight_gradients
+                .iter_mut()
+                .zip(bias_gradients.iter_mut())
+                .zip(errors)
+            {
+                for (gradient, pixel) in gradients.iter_mut().zip(pixels) {
+                    *gradient += error * pixel;
+                }
+                *bias += error;
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let step = self.learning_rate / labels.len() as f32;
+        for ((weights, bias), (gradients, bias_gradient)) in self
+            .weights
+            .iter_mut()
+            .zip(self.biases.iter_mut())
+            .zip(weight_gradients.iter().zip(bias_gradients))
+        {
+            for (weight, gradient) in weights.iter_mut().zip(gradients.iter()) {
+                *weight -= step * gradient;
+            }
+            *bias -= step * bias_gradient;
+        }
+        loss
+    }
+}
+
+/// Turn scores into probabilities in place, subtracting the largest score first for stability.
+pub(super) fn softmax(scores: &mut [f32; NUM_CLASSES]) {
+    let max = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let mut sum = 0.0;
+    for score in scores.iter_mut() {
+        *score = (*score - max).exp();
+        sum += *score;
+    }
+    for score in scores.iter_mut() {
+        *score /= sum;
+    }
+}
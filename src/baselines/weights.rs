@@ -0,0 +1,211 @@
+//! Saving and loading the parameters of baseline models.
+//!
+//! Every model is stored as its stack of fully connected layers, input first. Each layer has
+//! `inputs` and `outputs` sizes and `(inputs + 1) * outputs` parameters: the weights, one row of
+//! `inputs` per output, followed by one bias per output.
+//!
+//! In the binary format, all numbers are little-endian: the magic bytes `MNWT`, a `u32` format
+//! version (1), a `u32` layer count, then for each layer `u32` `inputs` and `outputs` followed
+//! by its parameters as `f32`. The JSON format holds the same data as
+//! `{"layers": [{"inputs": 784, "outputs": 10, "parameters": [...]}, ...]}`.
+
+use super::mlp::Layer;
+use crate::json::{invalid_data, JsonReader};
+use crate::{IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES};
+use std::convert::TryFrom;
+use std::fs;
+use std::io;
+use std::io::{Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"MNWT";
+const VERSION: u32 = 1;
+
+/// Write layers to `path`, as JSON if it has a `.json` extension and in binary otherwise.
+pub(super) fn save(path: &Path, layers: &[Layer]) -> io::Result<()> {
+    let mut writer = io::BufWriter::new(fs::File::create(path)?);
+    if is_json(path) {
+        write_json(&mut writer, layers)?;
+    } else {
+        write_binary(&mut writer, layers)?;
+    }
+    writer.flush()
+}
+
+/// Read layers written by [`save`], checking that they map an image to class scores.
+pub(super) fn load(path: &Path) -> io::Result<Vec<Layer>> {
+    let layers = if is_json(path) {
+        read_json(&fs::read_to_string(path)?)?
+    } else {
+        read_binary(&mut io::BufReader::new(fs::File::open(path)?))?
+    };
+
+    let mut inputs = IMAGE_ROWS * IMAGE_COLUMNS;
+    for layer in &layers {
+        if layer.inputs != inputs || layer.outputs == 0 {
+            return Err(invalid_data(format!(
+                "Layer of shape {}x{} does not follow a layer of {} outputs.",
+                layer.inputs, layer.outputs, inputs
+            )));
+        }
+        if layer.parameters.len() != (layer.inputs + 1) * layer.outputs {
+            return Err(invalid_data(format!(
+                "Layer of shape {}x{} has {} parameters.",
+                layer.inputs,
+                layer.outputs,
+                layer.parameters.len()
+            )));
+        }
+        inputs = layer.outputs;
+    }
+    if inputs != NUM_CLASSES || layers.is_empty() {
+        return Err(invalid_data(format!(
+            "Model has {inputs} outputs rather than one per class."
+        )));
+    }
+    Ok(layers)
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+}
+
+fn write_binary(writer: &mut impl Write, layers: &[Layer]) -> io::Result<()> {
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&size_to_u32(layers.len())?.to_le_bytes())?;
+    for layer in layers {
+        writer.write_all(&size_to_u32(layer.inputs)?.to_le_bytes())?;
+        writer.write_all(&size_to_u32(layer.outputs)?.to_le_bytes())?;
+        for parameter in &layer.parameters {
+            writer.write_all(&parameter.to_le_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+fn read_binary(reader: &mut impl Read) -> io::Result<Vec<Layer>> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid_data("Not a model weights file.".to_string()));
+    }
+    let version = read_u32(reader)?;
+    if version != VERSION {
+        return Err(invalid_data(format!(
+            "Unsupported model weights version {version}."
+        )));
+    }
+
+    let count = read_u32(reader)?;
+    let mut layers = Vec::new();
+    for _ in 0..count {
+        let inputs = usize::try_from(read_u32(reader)?).map_err(invalid_data)?;
+        let outputs = usize::try_from(read_u32(reader)?).map_err(invalid_data)?;
+        let len = inputs
+            .checked_add(1)
+            .and_then(|inputs| inputs.checked_mul(outputs))
+            .and_then(|parameters| parameters.checked_mul(4))
+            .ok_or_else(|| invalid_data("Layer is too large.".to_string()))?;
+
+        // Read through `take` so that a corrupt size runs into the end of the file rather than
+        // allocating the whole size up front.
+        let mut bytes = Vec::new();
+        reader
+            .by_ref()
+            .take(u64::try_from(len).map_err(invalid_data)?)
+            .read_to_end(&mut bytes)?;
+        if bytes.len() != len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let parameters = bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        layers.push(Layer {
+            inputs,
+            outputs,
+            parameters,
+        });
+    }
+    Ok(layers)
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn size_to_u32(size: usize) -> io::Result<u32> {
+    u32::try_from(size).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Model is too large for the weights format.",
+        )
+    })
+}
+
+fn write_json(writer: &mut impl Write, layers: &[Layer]) -> io::Result<()> {
+    writeln!(writer, r#"{{"layers": ["#)?;
+    for (i, layer) in layers.iter().enumerate() {
+        write!(
+            writer,
+            r#"  {{"inputs": {}, "outputs": {}, "parameters": ["#,
+            layer.inputs, layer.outputs
+        )?;
+        for (j, parameter) in layer.parameters.iter().enumerate() {
+            if !parameter.is_finite() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "JSON cannot hold non-finite parameters.",
+                ));
+            }
+            let separator = if j == 0 { "" } else { ", " };
+            // `f32`'s `Display` prints the shortest text that reads back as the same value.
+            write!(writer, "{separator}{parameter}")?;
+        }
+        let separator = if i + 1 < layers.len() { "," } else { "" };
+        writeln!(writer, "]}}{separator}")?;
+    }
+    writeln!(writer, "]}}")
+}
+
+fn read_json(text: &str) -> io::Result<Vec<Layer>> {
+    let mut json = JsonReader::new(text);
+    let mut layers = None;
+    json.object(|json, key| {
+        if key == "layers" {
+            layers = Some(json.array(read_layer)?);
+            Ok(())
+        } else {
+            Err(invalid_data(format!("Unexpected key \"{key}\".")))
+        }
+    })?;
+    json.end()?;
+    layers.ok_or_else(|| invalid_data("Missing key \"layers\".".to_string()))
+}
+
+fn read_layer(json: &mut JsonReader<'_>) -> io::Result<Layer> {
+    let (mut inputs, mut outputs, mut parameters) = (None, None, None);
+    json.object(|json, key| {
+        match key {
+            "inputs" => inputs = Some(json.number()?),
+            "outputs" => outputs = Some(json.number()?),
+            "parameters" => parameters = Some(json.array(JsonReader::number)?),
+            _ => return Err(invalid_data(format!("Unexpected key \"{key}\"."))),
+        }
+        Ok(())
+    })?;
+    match (inputs, outputs, parameters) {
+        (Some(inputs), Some(outputs), Some(parameters)) => Ok(Layer {
+            inputs,
+            outputs,
+            parameters,
+        }),
+        _ => Err(invalid_data(
+            "Layer needs \"inputs\", \"outputs\" and \"parameters\".".to_string(),
+        )),
+    }
+}
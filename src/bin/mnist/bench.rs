@@ -0,0 +1,200 @@
+//! `mnist bench`: time loading a dataset through each loading backend and file format.
+
+use crate::{load, CliResult};
+use clap::ValueEnum;
+use rust_mnist::{CachedSource, LoadOptions, LocalDir, MmapDir, Mnist};
+use std::convert::TryFrom;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::time::{Duration, Instant};
+
+/// Way of loading a dataset from disk.
+#[derive(Clone, Copy, ValueEnum)]
+enum Backend {
+    /// The four IDX files, read through buffered readers as by every other subcommand.
+    Buffered,
+    /// The four IDX files, memory-mapped.
+    Mmap,
+    /// The four IDX files, read once and then kept in memory for every later load.
+    Cached,
+    /// A Keras-style .npz archive.
+    Npz,
+    /// The label-first CSV files; slow, and best left out unless comparing against them.
+    Csv,
+}
+
+impl Backend {
+    fn name(self) -> &'static str {
+        match self {
+            Backend::Buffered => "buffered",
+            Backend::Mmap => "mmap",
+            Backend::Cached => "cached",
+            Backend::Npz => "npz",
+            Backend::Csv => "csv",
+        }
+    }
+}
+
+/// Time loading the dataset through each backend, cold and warm, with throughput and footprint.
+///
+/// Each backend loads a fresh copy of the dataset written to a temporary directory, so all of
+/// them start from the same data. The first load is left out of the warm times, as it pays for
+/// faulting in freshly allocated memory and, for the cached backend, for filling its cache. It
+/// is only reported as a cold load with `--drop-caches`, which drops the OS page cache before
+/// it so that it reads from disk.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Directory holding the dataset's IDX files.
+    #[arg(default_value = "data")]
+    dir: PathBuf,
+    /// Number of times to load the dataset with each backend, the first one cold.
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+    iterations: u32,
+    /// Backends to time, separated by commas.
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_values = ["buffered", "mmap", "cached"]
+    )]
+    backends: Vec<Backend>,
+    /// Drop the OS page cache before each backend's first load, to time it reading from disk.
+    /// This drops the cache of the whole machine, slowing everything running on it for a while,
+    /// and needs root on Linux.
+    #[arg(long)]
+    drop_caches: bool,
+}
+
+pub fn run(args: &Args) -> CliResult {
+    let mnist = load(&args.dir)?;
+    let scratch = env::temp_dir().join(format!("mnist-bench-{}", process::id()));
+    let result = bench(args, &mnist, &scratch);
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+fn bench(args: &Args, mnist: &Mnist, scratch: &Path) -> CliResult {
+    let summary = mnist.summary();
+    let samples = summary.train.len + summary.test.len;
+    #[allow(clippy::cast_precision_loss)]
+    let mebibytes = |bytes: u64| bytes as f64 / f64::from(1 << 20);
+    println!(
+        "{samples} samples, {:.1} MiB in memory once loaded",
+        mebibytes(summary.memory_bytes as u64)
+    );
+    println!();
+    println!(
+        "{:<8}  {:>11}  {:>11}  {:>11}  {:>9}  {:>9}  {:>9}",
+        "Backend", "On disk", "In memory", "Cold", "Warm mean", "Warm best", "Images/s"
+    );
+
+    let mut dropped_all = true;
+    for &backend in &args.backends {
+        let dir = scratch.join(backend.name());
+        fs::create_dir_all(&dir)?;
+        let idx_options = |dir: &Path| {
+            mnist.to_idx(dir)?;
+            CliResult::Ok(LoadOptions::new().strict(false).verbose(false))
+        };
+        let load_copy: Box<dyn Fn() -> CliResult<Mnist>> = match backend {
+            Backend::Buffered => {
+                let options = idx_options(&dir)?.source(LocalDir::new(&dir));
+                Box::new(move || Ok(Mnist::load(&options)?))
+            }
+            Backend::Mmap => {
+                let options = idx_options(&dir)?.source(MmapDir::new(&dir));
+                Box::new(move || Ok(Mnist::load(&options)?))
+            }
+            Backend::Cached => {
+                let options = idx_options(&dir)?.source(CachedSource::new(LocalDir::new(&dir)));
+                Box::new(move || Ok(Mnist::load(&options)?))
+            }
+            Backend::Npz => {
+                mnist.to_npz(&dir.join("mnist.npz"))?;
+                let source = LocalDir::new(&dir);
+                Box::new(move || Ok(Mnist::from_npz(&source, "mnist.npz")?))
+            }
+            Backend::Csv => {
+                mnist.to_csv(&dir)?;
+                let (train, test) = (dir.join("mnist_train.csv"), dir.join("mnist_test.csv"));
+                Box::new(move || Ok(Mnist::from_csv(&train, &test)?))
+            }
+        };
+
+        let dropped = args.drop_caches && drop_page_cache(&dir)?;
+        dropped_all &= dropped;
+        let mut times = Vec::new();
+        for _ in 0..args.iterations {
+            let start = Instant::now();
+            let loaded = load_copy()?;
+            times.push(start.elapsed());
+            drop(loaded);
+        }
+
+        let warm = if times.len() > 1 {
+            &times[1..]
+        } else {
+            &times[..]
+        };
+        let warm_mean = warm.iter().sum::<Duration>() / u32::try_from(warm.len())?;
+        let warm_best = warm.iter().min().copied().unwrap_or(times[0]);
+        #[allow(clippy::cast_precision_loss)]
+        let throughput = samples as f64 / warm_mean.as_secs_f64();
+        let on_disk = disk_usage(&dir)?;
+        // The cached backend holds the files' bytes on top of the loaded dataset.
+        let in_memory = summary.memory_bytes as u64
+            + match backend {
+                Backend::Cached => on_disk,
+                _ => 0,
+            };
+        let cold = if dropped {
+            format!("{:.3} s", times[0].as_secs_f64())
+        } else {
+            "unavailable".to_string()
+        };
+        println!(
+            "{:<8}  {:>7.1} MiB  {:>7.1} MiB  {cold:>11}  {:>7.3} s  {:>7.3} s  {throughput:>9.0}",
+            backend.name(),
+            mebibytes(on_disk),
+            mebibytes(in_memory),
+            warm_mean.as_secs_f64(),
+            warm_best.as_secs_f64(),
+        );
+    }
+
+    if !args.drop_caches {
+        println!();
+        println!(
+            "Cold loads need the page cache dropped; pass --drop-caches, as root on Linux, to \
+             time them."
+        );
+    } else if !dropped_all {
+        println!();
+        println!(
+            "The page cache could not be dropped, so cold loads are unavailable; run as root on \
+             Linux to time loads from disk."
+        );
+    }
+    Ok(())
+}
+
+/// Flush the files in `dir` to disk and ask the OS to drop its page cache, so the next load
+/// reads them from disk, returning whether it could.
+fn drop_page_cache(dir: &Path) -> CliResult<bool> {
+    for entry in fs::read_dir(dir)? {
+        fs::File::open(entry?.path())?.sync_all()?;
+    }
+    Ok(fs::write("/proc/sys/vm/drop_caches", "1").is_ok())
+}
+
+/// Total size of the files in `dir`.
+fn disk_usage(dir: &Path) -> CliResult<u64> {
+    let mut bytes = 0;
+    for entry in fs::read_dir(dir)? {
+        bytes += entry?.metadata()?.len();
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,99 @@
+//! `mnist convert`: export a dataset to other file formats.
+
+use crate::{load, select, CliResult, SplitArg};
+use clap::ValueEnum;
+use rust_mnist::{ImageSet, Mnist};
+use std::fs;
+use std::path::PathBuf;
+
+/// File format to export to.
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// A CSV file per split, one sample per line with the label first.
+    Csv,
+    /// A PNG file per sample, in a directory per split and label.
+    Png,
+    /// A single .npz archive, laid out like the one Keras downloads.
+    Npz,
+    /// A Parquet file per split, with a label and an image column.
+    Parquet,
+}
+
+/// Export a dataset to CSV, PNG, NPZ or Parquet files.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Directory holding the dataset's IDX files.
+    #[arg(default_value = "data")]
+    dir: PathBuf,
+    /// Format to export to.
+    #[arg(long, value_enum)]
+    to: Format,
+    /// Directory to write the exported files to.
+    #[arg(long, default_value = "export")]
+    out: PathBuf,
+    /// Export only this split rather than both.
+    #[arg(long, value_enum)]
+    split: Option<SplitArg>,
+    /// Export at most this many samples of each split.
+    #[arg(long)]
+    limit: Option<usize>,
+    /// With --limit, pick the samples at random with this seed rather than taking the first ones.
+    #[arg(long, requires = "limit")]
+    seed: Option<u64>,
+}
+
+pub fn run(args: &Args) -> CliResult {
+    let mnist = load(&args.dir)?;
+    let splits = match args.split {
+        Some(split) => vec![split],
+        None => vec![SplitArg::Train, SplitArg::Test],
+    };
+    let sets: Vec<(SplitArg, ImageSet)> = splits
+        .into_iter()
+        .map(|split| {
+            (
+                split,
+                select(&mnist, split.into(), &[], args.limit, args.seed),
+            )
+        })
+        .collect();
+
+    let count: usize = sets.iter().map(|(_, set)| set.len()).sum();
+
+    fs::create_dir_all(&args.out)?;
+    match args.to {
+        Format::Csv => {
+            for (split, set) in &sets {
+                set.to_csv(&args.out.join(format!("mnist_{split}.csv")))?;
+            }
+        }
+        Format::Png => {
+            for (split, set) in &sets {
+                set.export_png(&args.out.join(split.to_string()))?;
+            }
+        }
+        Format::Parquet => {
+            for (split, set) in &sets {
+                set.to_parquet(&args.out.join(format!("mnist_{split}.parquet")))?;
+            }
+        }
+        Format::Npz => {
+            // The archive always holds both splits; one left out is stored as empty arrays.
+            let empty = || ImageSet {
+                images: Vec::new(),
+                labels: Vec::new(),
+            };
+            let (mut train, mut test) = (empty(), empty());
+            for (split, set) in sets {
+                match split {
+                    SplitArg::Train => train = set,
+                    SplitArg::Test => test = set,
+                }
+            }
+            Mnist::from_splits(train, test).to_npz(&args.out.join("mnist.npz"))?;
+        }
+    }
+
+    println!("Exported {count} samples to {}", args.out.display());
+    Ok(())
+}
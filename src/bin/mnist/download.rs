@@ -0,0 +1,91 @@
+//! `mnist download`: fetch a dataset from its public mirror.
+
+use crate::CliResult;
+use rust_mnist::{Dataset, HttpSource, LocalDir};
+use std::convert::TryFrom;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Width of the progress bar, in characters.
+const BAR_WIDTH: u64 = 30;
+
+/// Download a dataset and extract it as IDX files.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Dataset to download: mnist, fashion or kmnist.
+    #[arg(long, default_value = "mnist")]
+    dataset: Dataset,
+    /// Directory to put the files in; created if needed.
+    #[arg(long, default_value = "data")]
+    dir: PathBuf,
+    /// Fetch the .gz files from this URL, or directory, instead of the dataset's usual mirror;
+    /// they must still match the dataset's published checksums.
+    #[arg(long)]
+    mirror: Option<String>,
+}
+
+pub fn run(args: &Args) -> CliResult {
+    let mut current = String::new();
+    let mut last_line = String::new();
+    let progress = |filename: &str, received: u64, total: Option<u64>| {
+        if current != filename {
+            if !current.is_empty() {
+                eprintln!();
+            }
+            current = filename.to_string();
+        }
+        // Only redraw when what is shown changes, not on every read.
+        let line = progress_line(filename, received, total);
+        if line != last_line {
+            let mut stderr = io::stderr().lock();
+            let _ = write!(stderr, "\r{line}");
+            let _ = stderr.flush();
+            last_line = line;
+        }
+    };
+
+    let mirror = args
+        .mirror
+        .as_deref()
+        .unwrap_or_else(|| args.dataset.base_url());
+    let result = if Path::new(mirror).is_dir() {
+        let mirror = LocalDir::new(Path::new(mirror));
+        rust_mnist::download_from(&mirror, &args.dir, Some(args.dataset), progress)
+    } else {
+        let mirror = HttpSource::new(mirror);
+        rust_mnist::download_from(&mirror, &args.dir, Some(args.dataset), progress)
+    };
+    if !current.is_empty() {
+        eprintln!();
+    }
+    result?;
+
+    println!("{} is ready in {}", args.dataset, args.dir.display());
+    Ok(())
+}
+
+/// `bytes` in mebibytes, to one decimal place.
+fn mebibytes(bytes: u64) -> String {
+    const MIB: u64 = 1 << 20;
+    format!("{}.{}", bytes / MIB, bytes % MIB * 10 / MIB)
+}
+
+/// Progress line of the file being downloaded.
+fn progress_line(filename: &str, received: u64, total: Option<u64>) -> String {
+    match total {
+        Some(total) if total > 0 => {
+            let received = received.min(total);
+            let filled = usize::try_from(received * BAR_WIDTH / total).unwrap_or(0);
+            let empty = usize::try_from(BAR_WIDTH).unwrap_or(0) - filled;
+            format!(
+                "{filename:<24} [{}{}] {:>3}% {}/{} MiB",
+                "#".repeat(filled),
+                ".".repeat(empty),
+                received * 100 / total,
+                mebibytes(received),
+                mebibytes(total)
+            )
+        }
+        _ => format!("{filename:<24} {} MiB", mebibytes(received)),
+    }
+}
@@ -0,0 +1,136 @@
+#![warn(clippy::pedantic)]
+//! `mnist`: command-line tools for the MNIST dataset, built on the `rust-mnist` library.
+
+mod bench;
+mod convert;
+mod download;
+mod montage;
+mod serve;
+mod show;
+mod stats;
+mod subset;
+mod verify;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rust_mnist::{ImageSet, LoadOptions, Mnist, Split};
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use std::process::ExitCode;
+
+/// Error returned by a subcommand, printed before exiting with a failure status.
+type CliResult<T = ()> = Result<T, Box<dyn Error>>;
+
+/// Command-line tools for the MNIST dataset.
+#[derive(Parser)]
+#[command(name = "mnist", version, arg_required_else_help = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    Bench(bench::Args),
+    Convert(convert::Args),
+    Download(download::Args),
+    Montage(montage::Args),
+    Show(show::Args),
+    Serve(serve::Args),
+    Stats(stats::Args),
+    Subset(subset::Args),
+    Verify(verify::Args),
+}
+
+/// One of the two halves of the dataset, as chosen on the command line.
+#[derive(Clone, Copy, ValueEnum)]
+enum SplitArg {
+    Train,
+    Test,
+}
+
+impl From<SplitArg> for Split {
+    fn from(split: SplitArg) -> Split {
+        match split {
+            SplitArg::Train => Split::Train,
+            SplitArg::Test => Split::Test,
+        }
+    }
+}
+
+impl fmt::Display for SplitArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SplitArg::Train => "train",
+            SplitArg::Test => "test",
+        })
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let Some(command) = cli.command else {
+        return ExitCode::SUCCESS;
+    };
+    match run(&command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: &Command) -> CliResult {
+    match command {
+        Command::Bench(args) => bench::run(args),
+        Command::Convert(args) => convert::run(args),
+        Command::Download(args) => download::run(args),
+        Command::Montage(args) => montage::run(args),
+        Command::Show(args) => show::run(args),
+        Command::Serve(args) => serve::run(args),
+        Command::Stats(args) => stats::run(args),
+        Command::Subset(args) => subset::run(args),
+        Command::Verify(args) => verify::run(args),
+    }
+}
+
+/// Load the dataset from `dir`, accepting splits of any size so that the output of
+/// `mnist subset` can be read back.
+fn load(dir: &Path) -> CliResult<Mnist> {
+    let options = LoadOptions::new().cache_dir(dir).strict(false);
+    Mnist::load(&options)
+        .map_err(|err| format!("Cannot load the dataset from \"{}\": {err}", dir.display()).into())
+}
+
+/// The samples of `split` whose label is one of `labels`, or all of them if `labels` is empty:
+/// every such sample, the first `limit`, or `limit` picked at random with `seed`, kept in their
+/// original order.
+fn select(
+    mnist: &Mnist,
+    split: Split,
+    labels: &[u8],
+    limit: Option<usize>,
+    seed: Option<u64>,
+) -> ImageSet {
+    let split_labels = mnist.labels(split);
+    let mut indices: Vec<usize> = (0..split_labels.len())
+        .filter(|&i| labels.is_empty() || labels.contains(&split_labels[i]))
+        .collect();
+    let limit = limit.unwrap_or(indices.len()).min(indices.len());
+    match seed {
+        Some(seed) => {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let mut picked: Vec<usize> = rand::seq::index::sample(&mut rng, indices.len(), limit)
+                .into_iter()
+                .map(|position| indices[position])
+                .collect();
+            picked.sort_unstable();
+            indices = picked;
+        }
+        None => indices.truncate(limit),
+    }
+    mnist.subset(split, &indices)
+}
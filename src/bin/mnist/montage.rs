@@ -0,0 +1,59 @@
+//! `mnist montage`: save a contact sheet of random samples.
+
+use crate::{load, CliResult, SplitArg};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use rust_mnist::NUM_CLASSES;
+use std::path::PathBuf;
+
+/// Save a labelled grid of random samples, one row per digit.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Directory holding the dataset's IDX files.
+    #[arg(default_value = "data")]
+    dir: PathBuf,
+    /// Number of samples of each digit.
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u16).range(1..))]
+    per_class: u16,
+    /// Image file to write; the format follows its extension.
+    #[arg(long, default_value = "montage.png")]
+    out: PathBuf,
+    /// Split to take the samples from.
+    #[arg(long, value_enum, default_value = "train")]
+    split: SplitArg,
+    /// Seed for picking the samples, for a repeatable sheet; random if not given.
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+pub fn run(args: &Args) -> CliResult {
+    let mnist = load(&args.dir)?;
+    let mut rng = match args.seed {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+        None => ChaCha8Rng::from_entropy(),
+    };
+
+    let per_class = usize::from(args.per_class);
+    let mut indices = Vec::with_capacity(NUM_CLASSES * per_class);
+    for (digit, class) in mnist.class_indices(args.split.into()).iter().enumerate() {
+        if class.len() < per_class {
+            return Err(format!(
+                "The {} split has only {} samples of {digit}.",
+                args.split,
+                class.len()
+            )
+            .into());
+        }
+        indices.extend(class.choose_multiple(&mut rng, per_class));
+    }
+
+    mnist.export_montage(&args.out, args.split.into(), &indices, per_class)?;
+    println!(
+        "Saved {} samples of the {} split to {}",
+        indices.len(),
+        args.split,
+        args.out.display()
+    );
+    Ok(())
+}
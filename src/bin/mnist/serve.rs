@@ -0,0 +1,193 @@
+//! `mnist serve`: browse samples over HTTP.
+
+use crate::{load, CliResult};
+use ::image::ImageFormat;
+use rust_mnist::{Mnist, Split};
+use std::io::{self, BufRead, Cursor, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Longest a client may take to send its request, or to accept each part of the response.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most bytes read of a request line and headers together.
+const MAX_REQUEST_BYTES: u64 = 8 * 1024;
+
+/// What the server answers on `/`.
+const INDEX: &str = "GET /train/{i}.png  training image i\n\
+                     GET /test/{i}.png   testing image i\n\
+                     GET /labels.json    every label, as {\"train\": [...], \"test\": [...]}\n";
+
+/// Serve samples as PNG images and labels as JSON over HTTP.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Directory holding the dataset's IDX files.
+    #[arg(default_value = "data")]
+    dir: PathBuf,
+    /// Port to listen on.
+    #[arg(long, default_value_t = 8080)]
+    port: u16,
+    /// Address to listen on; use 0.0.0.0 to accept connections from other machines.
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+    /// Most connections served at once; any more are turned away.
+    #[arg(long, default_value_t = 64)]
+    max_connections: usize,
+}
+
+/// An HTTP response: status line, content type and body.
+type Response = (&'static str, &'static str, Vec<u8>);
+
+pub fn run(args: &Args) -> CliResult {
+    let mnist = load(&args.dir)?;
+    let labels = labels_json(&mnist);
+    let listener = TcpListener::bind((args.host.as_str(), args.port))?;
+    println!(
+        "Serving {} on http://{}/",
+        args.dir.display(),
+        listener.local_addr()?
+    );
+
+    let open = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for stream in listener.incoming().flatten() {
+            // A client hanging up early is its own business, so failed connections are dropped.
+            let _ = stream
+                .set_read_timeout(Some(TIMEOUT))
+                .and_then(|()| stream.set_write_timeout(Some(TIMEOUT)));
+            if open.fetch_add(1, Ordering::SeqCst) >= args.max_connections {
+                open.fetch_sub(1, Ordering::SeqCst);
+                let busy = (
+                    "503 Service Unavailable",
+                    "text/plain",
+                    b"Too many connections; try again later.\n".to_vec(),
+                );
+                let _ = respond(&stream, "GET", busy);
+                continue;
+            }
+            let (mnist, labels, open) = (&mnist, labels.as_bytes(), &open);
+            scope.spawn(move || {
+                let _ = handle(&stream, mnist, labels);
+                open.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    });
+    Ok(())
+}
+
+fn handle(stream: &TcpStream, mnist: &Mnist, labels: &[u8]) -> io::Result<()> {
+    let mut reader = io::BufReader::new(stream.take(MAX_REQUEST_BYTES));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Headers are not needed, but are read so that the client sees its request consumed.
+    let mut complete = request_line.ends_with('\n');
+    let mut header = String::new();
+    while complete {
+        header.clear();
+        reader.read_line(&mut header)?;
+        if header == "\r\n" || header == "\n" {
+            break;
+        }
+        complete = header.ends_with('\n');
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+    let response = if !complete && reader.get_ref().limit() == 0 {
+        (
+            "431 Request Header Fields Too Large",
+            "text/plain",
+            format!("Requests must fit in {MAX_REQUEST_BYTES} bytes.\n").into_bytes(),
+        )
+    } else if !complete {
+        (
+            "400 Bad Request",
+            "text/plain",
+            b"The request ended before its headers did.\n".to_vec(),
+        )
+    } else if let "GET" | "HEAD" = method {
+        route(path, mnist, labels)
+    } else {
+        (
+            "405 Method Not Allowed",
+            "text/plain",
+            b"Only GET and HEAD are supported.\n".to_vec(),
+        )
+    };
+    respond(stream, method, response)
+}
+
+/// Send `response`, leaving out the body if answering a `HEAD` request.
+fn respond(stream: &TcpStream, method: &str, response: Response) -> io::Result<()> {
+    let (status, content_type, body) = response;
+    let mut writer = io::BufWriter::new(stream);
+    write!(
+        writer,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    if method != "HEAD" {
+        writer.write_all(&body)?;
+    }
+    writer.flush()
+}
+
+fn route(path: &str, mnist: &Mnist, labels: &[u8]) -> Response {
+    if path == "/" {
+        return ("200 OK", "text/plain", INDEX.as_bytes().to_vec());
+    }
+    if path == "/labels.json" {
+        return ("200 OK", "application/json", labels.to_vec());
+    }
+
+    let sample = path
+        .strip_prefix("/train/")
+        .map(|rest| (Split::Train, rest));
+    let sample = sample.or_else(|| path.strip_prefix("/test/").map(|rest| (Split::Test, rest)));
+    let sample = sample.and_then(|(split, rest)| {
+        let index = rest.strip_suffix(".png")?.parse().ok()?;
+        (index < mnist.labels(split).len()).then_some((split, index))
+    });
+    let Some((split, index)) = sample else {
+        return (
+            "404 Not Found",
+            "text/plain",
+            format!("No such sample.\n\n{INDEX}").into_bytes(),
+        );
+    };
+
+    let mut png = Vec::new();
+    match mnist
+        .to_gray_image(split, index)
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+    {
+        Ok(()) => ("200 OK", "image/png", png),
+        Err(err) => (
+            "500 Internal Server Error",
+            "text/plain",
+            format!("{err}\n").into_bytes(),
+        ),
+    }
+}
+
+fn labels_json(mnist: &Mnist) -> String {
+    let list = |split| {
+        let labels: Vec<String> = mnist
+            .labels(split)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        labels.join(",")
+    };
+    format!(
+        "{{\"train\": [{}], \"test\": [{}]}}",
+        list(Split::Train),
+        list(Split::Test)
+    )
+}
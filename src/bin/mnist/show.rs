@@ -0,0 +1,85 @@
+//! `mnist show`: draw a sample in the terminal.
+
+use crate::{load, CliResult, SplitArg};
+use clap::ValueEnum;
+use rust_mnist::{
+    image_to_ansi, image_to_braille, image_to_graphics, image_to_string, AsciiRamp, ColorSupport,
+    GraphicsProtocol,
+};
+use std::path::PathBuf;
+
+/// How to draw the image.
+#[derive(Clone, Copy, ValueEnum)]
+enum Style {
+    /// The best the terminal supports: graphics, then color, then ASCII.
+    Auto,
+    /// "##" for ink and "__" for background.
+    Blocks,
+    /// Characters of increasing density for brighter pixels.
+    Ascii,
+    /// True grayscale with ANSI colors, if the terminal supports them.
+    Ansi,
+    /// One braille dot per pixel, in a quarter of the space.
+    Braille,
+    /// An inline image, in terminals speaking the sixel or kitty graphics protocols.
+    Graphics,
+}
+
+/// Draw a sample in the terminal, with its label.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Directory holding the dataset's IDX files.
+    #[arg(default_value = "data")]
+    dir: PathBuf,
+    /// Split to take the sample from.
+    #[arg(long, value_enum, default_value = "train")]
+    split: SplitArg,
+    /// Index of the sample within the split.
+    #[arg(long, default_value_t = 0)]
+    index: usize,
+    /// How to draw the image.
+    #[arg(long, value_enum, default_value = "auto")]
+    style: Style,
+    /// Brightness above which a pixel is drawn as a braille dot.
+    #[arg(long, default_value_t = 127)]
+    threshold: u8,
+    /// Size of each pixel, in screen pixels, for the graphics style.
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+    scale: u16,
+}
+
+pub fn run(args: &Args) -> CliResult {
+    let mnist = load(&args.dir)?;
+    let split = args.split.into();
+    let images = mnist.images(split);
+    let image = images.get(args.index).ok_or_else(|| {
+        format!(
+            "Index {} is out of range; the {} split has {} samples.",
+            args.index,
+            args.split,
+            images.len()
+        )
+    })?;
+
+    let text = match args.style {
+        Style::Auto => match GraphicsProtocol::detect() {
+            Some(protocol) => image_to_graphics(image, Some(protocol), usize::from(args.scale)),
+            None => image_to_ansi(image, ColorSupport::detect()),
+        },
+        Style::Blocks => image_to_string(image),
+        Style::Ascii => AsciiRamp::standard().render(image),
+        Style::Ansi => image_to_ansi(image, ColorSupport::detect()),
+        Style::Braille => image_to_braille(image, args.threshold),
+        Style::Graphics => {
+            image_to_graphics(image, GraphicsProtocol::detect(), usize::from(args.scale))
+        }
+    };
+    println!(
+        "Label: {}  ({} #{})",
+        mnist.labels(split)[args.index],
+        args.split,
+        args.index
+    );
+    print!("{text}");
+    Ok(())
+}
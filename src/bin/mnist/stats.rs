@@ -0,0 +1,98 @@
+//! `mnist stats`: summarise a dataset for people or scripts.
+
+use crate::{load, CliResult};
+use rust_mnist::{Mnist, Split, SplitSummary};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Print sample counts, class balance, pixel statistics and checksums.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Directory holding the dataset's IDX files.
+    #[arg(default_value = "data")]
+    dir: PathBuf,
+    /// Print a JSON object instead of a report.
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn run(args: &Args) -> CliResult {
+    let mnist = load(&args.dir)?;
+    if args.json {
+        println!("{}", to_json(&mnist));
+    } else {
+        print!("{}", mnist.summary());
+        println!();
+        println!("  Split  Pixel mean  Pixel std");
+        for (name, split) in [("train", Split::Train), ("test", Split::Test)] {
+            let (mean, std) = mnist.pixel_stats(split);
+            println!("  {name:<5}  {mean:>10.4}  {std:>9.4}");
+        }
+    }
+    Ok(())
+}
+
+/// Everything in the report as a JSON object. Checksums are hexadecimal strings, since they do
+/// not fit in a JSON number without losing precision.
+fn to_json(mnist: &Mnist) -> String {
+    let summary = mnist.summary();
+    let (rows, columns) = summary.image_shape;
+
+    // Writing to a `String` cannot fail.
+    let mut json = String::new();
+    let _ = write!(
+        json,
+        r#"{{"image_shape": [{rows}, {columns}], "memory_bytes": {}"#,
+        summary.memory_bytes
+    );
+    for (name, split, split_summary) in [
+        ("train", Split::Train, &summary.train),
+        ("test", Split::Test, &summary.test),
+    ] {
+        let _ = write!(
+            json,
+            r#", "{name}": {}"#,
+            split_json(split_summary, mnist.pixel_stats(split))
+        );
+    }
+    json.push('}');
+    json
+}
+
+fn split_json(summary: &SplitSummary, (mean, std): (f32, f32)) -> String {
+    let list = |values: Vec<String>| format!("[{}]", values.join(", "));
+    let counts = summary
+        .label_counts
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    #[allow(clippy::cast_precision_loss)]
+    let balance = summary
+        .label_counts
+        .iter()
+        .map(|&count| number(count as f32 / summary.len as f32))
+        .collect();
+    let range = match summary.pixel_range {
+        Some((min, max)) => format!("[{min}, {max}]"),
+        None => String::from("null"),
+    };
+
+    format!(
+        r#"{{"samples": {}, "label_counts": {}, "class_balance": {}, "pixel_range": {range}, "pixel_mean": {}, "pixel_std": {}, "checksum": "{:016x}"}}"#,
+        summary.len,
+        list(counts),
+        list(balance),
+        number(mean),
+        number(std),
+        summary.checksum
+    )
+}
+
+/// `value` as a JSON number, or `null` if it is NaN or infinite, as it is for an empty split.
+fn number(value: f32) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        String::from("null")
+    }
+}
@@ -0,0 +1,42 @@
+//! `mnist subset`: write a smaller dataset of chosen digits.
+
+use crate::{load, select, CliResult};
+use rust_mnist::{Mnist, Split};
+use std::path::PathBuf;
+
+/// Write a smaller dataset, filtered by label, as IDX files.
+///
+/// The files keep the MNIST layout but not its sample counts. The other mnist subcommands load
+/// them like any dataset, as does the library with strict loading turned off.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Directory holding the dataset's IDX files.
+    #[arg(default_value = "data")]
+    dir: PathBuf,
+    /// Keep only samples of these digits, separated by commas; all digits if not given.
+    #[arg(long, value_delimiter = ',', value_parser = clap::value_parser!(u8).range(0..10))]
+    labels: Vec<u8>,
+    /// Keep at most this many samples of each split.
+    #[arg(long)]
+    n: Option<usize>,
+    /// With --n, pick the samples at random with this seed rather than taking the first ones.
+    #[arg(long, requires = "n")]
+    seed: Option<u64>,
+    /// Directory to write the IDX files to; created if needed.
+    #[arg(long, default_value = "subset")]
+    out: PathBuf,
+}
+
+pub fn run(args: &Args) -> CliResult {
+    let mnist = load(&args.dir)?;
+    let pick = |split| select(&mnist, split, &args.labels, args.n, args.seed);
+    let subset = Mnist::from_splits(pick(Split::Train), pick(Split::Test));
+    subset.to_idx(&args.out)?;
+    println!(
+        "Wrote {} training and {} testing samples to {}",
+        subset.train_labels.len(),
+        subset.test_labels.len(),
+        args.out.display()
+    );
+    Ok(())
+}
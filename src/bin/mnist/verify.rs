@@ -0,0 +1,75 @@
+//! `mnist verify`: check a dataset before relying on it.
+
+use crate::{load, CliResult};
+use rust_mnist::{Dataset, Mnist};
+use std::path::PathBuf;
+
+/// Check a dataset's headers, sample counts and labels, and compare its checksums.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Directory holding the dataset's IDX files.
+    #[arg(default_value = "data")]
+    dir: PathBuf,
+    /// Require the sample counts of MNIST, rather than those in the files' headers.
+    #[arg(long)]
+    strict: bool,
+    /// Dataset the files should be, whose published checksums the .gz files kept by mnist
+    /// download must match: mnist, fashion or kmnist. Implies --strict.
+    #[arg(long)]
+    dataset: Option<Dataset>,
+    /// Checksum the training split must have, as printed by mnist stats.
+    #[arg(long, value_parser = parse_checksum)]
+    train_checksum: Option<u64>,
+    /// Checksum the testing split must have, as printed by mnist stats.
+    #[arg(long, value_parser = parse_checksum)]
+    test_checksum: Option<u64>,
+}
+
+pub fn run(args: &Args) -> CliResult {
+    let mut problems = Mnist::verify(&args.dir, args.strict || args.dataset.is_some());
+    if let Some(dataset) = args.dataset {
+        problems.extend(dataset.verify_checksums(&args.dir));
+    }
+    if !problems.is_empty() {
+        for problem in &problems {
+            eprintln!("{problem}");
+        }
+        eprintln!(
+            "Delete any damaged files and run `mnist download --dir {}` to fetch them again.",
+            args.dir.display()
+        );
+        return Err("the dataset failed verification".into());
+    }
+    println!("Headers, sample counts and labels are valid.");
+    if let Some(dataset) = args.dataset {
+        println!("The files match the published checksums of {dataset}.");
+    }
+
+    let summary = load(&args.dir)?.summary();
+    let mut mismatches = 0;
+    for (name, actual, expected) in [
+        ("train", summary.train.checksum, args.train_checksum),
+        ("test", summary.test.checksum, args.test_checksum),
+    ] {
+        match expected {
+            Some(expected) if expected != actual => {
+                eprintln!(
+                    "The {name} split has checksum {actual:016x} where {expected:016x} was \
+                     expected; the files hold different data."
+                );
+                mismatches += 1;
+            }
+            Some(_) => println!("The {name} split has the expected checksum {actual:016x}."),
+            None => println!("The {name} split has checksum {actual:016x}."),
+        }
+    }
+    if mismatches > 0 {
+        return Err(format!("{mismatches} of 2 checksums did not match").into());
+    }
+    Ok(())
+}
+
+fn parse_checksum(text: &str) -> Result<u64, String> {
+    u64::from_str_radix(text.trim_start_matches("0x"), 16)
+        .map_err(|_| format!("\"{text}\" is not a 64-bit hexadecimal checksum"))
+}
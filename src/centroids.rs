@@ -0,0 +1,98 @@
+//! Analyses based on the mean image ("centroid") of each class.
+
+use crate::stats::class_stats;
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES};
+use std::fmt;
+
+/// Distances between the mean images of every pair of digits, returned by
+/// [`Mnist::class_confusability`]. Digits whose means are close, such as 4 and 9, are the ones
+/// classifiers tend to mix up.
+///
+/// Its [`Display`](fmt::Display) implementation prints the matrix as a table.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Confusability {
+    /// Euclidean distance between the mean images of digits `i` and `j`, with pixels scaled to
+    /// `[0, 1]`. The matrix is symmetric with a zero diagonal.
+    pub distances: [[f32; NUM_CLASSES]; NUM_CLASSES],
+}
+
+impl Confusability {
+    /// Every pair of distinct digits with the distance between their means, closest first.
+    #[must_use]
+    pub fn closest_pairs(&self) -> Vec<(u8, u8, f32)> {
+        let mut pairs: Vec<(u8, u8, f32)> = (0..=9_u8)
+            .flat_map(|a| (a + 1..=9).map(move |b| (a, b)))
+            .map(|(a, b)| (a, b, self.distances[usize::from(a)][usize::from(b)]))
+            .collect();
+        pairs.sort_by(|a, b| a.2.total_cmp(&b.2));
+        pairs
+    }
+}
+
+impl fmt::Display for Confusability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "     ")?;
+        for label in 0..NUM_CLASSES {
+            write!(f, "{label:>6}")?;
+        }
+        writeln!(f)?;
+        for (label, row) in self.distances.iter().enumerate() {
+            write!(f, "{label:>5}")?;
+            for distance in row {
+                write!(f, "{distance:>6.2}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Mnist {
+    /// Distances between the mean images of every pair of digits in the split.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let confusability = mnist.class_confusability(Split::Train);
+    /// print!("{}", confusability);
+    /// let (a, b, _) = confusability.closest_pairs()[0];
+    /// println!("Most alike: {} and {}", a, b);
+    /// ```
+    #[must_use]
+    pub fn class_confusability(&self, split: Split) -> Confusability {
+        confusability(self.images(split), self.labels(split))
+    }
+}
+
+impl ImageSet {
+    /// Distances between the mean images of every pair of digits in the set, as computed by
+    /// [`Mnist::class_confusability`].
+    #[must_use]
+    pub fn class_confusability(&self) -> Confusability {
+        confusability(&self.images, &self.labels)
+    }
+}
+
+fn confusability(images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]], labels: &[u8]) -> Confusability {
+    let means = class_stats(images, labels).map(|stats| stats.mean);
+    let mut distances = [[0.0; NUM_CLASSES]; NUM_CLASSES];
+    for (i, row) in distances.iter_mut().enumerate() {
+        for (j, distance) in row.iter_mut().enumerate() {
+            *distance = euclidean(&means[i], &means[j]);
+        }
+    }
+    Confusability { distances }
+}
+
+/// Euclidean distance between two images of equal length.
+pub(crate) fn euclidean(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum::<f32>()
+        .sqrt()
+}
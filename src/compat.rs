@@ -0,0 +1,311 @@
+//! A drop-in replacement for the API of the [`mnist`](https://crates.io/crates/mnist) crate.
+//!
+//! Projects written against `mnist` can switch to this crate by changing their import from
+//! `mnist::{Mnist, MnistBuilder}` to `rust_mnist::compat::{Mnist, MnistBuilder}`. The builder
+//! takes the same options and [`MnistBuilder::finalize`] returns the same flattened vectors:
+//! every image's 784 bytes one after another, and labels either as digits or one-hot.
+//!
+//! ```no_run
+//! use rust_mnist::compat::{Mnist, MnistBuilder};
+//!
+//! let Mnist { trn_img, trn_lbl, val_img, val_lbl, tst_img, tst_lbl } = MnistBuilder::new()
+//!     .label_format_digit()
+//!     .training_set_length(50_000)
+//!     .validation_set_length(10_000)
+//!     .test_set_length(10_000)
+//!     .finalize();
+//! assert_eq!(trn_img.len(), 50_000 * 28 * 28);
+//! ```
+
+use crate::{
+    read_split, DataSource, LocalDir, IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES, TEST_DATA_FILENAME,
+    TEST_LABEL_FILENAME, TRAIN_DATA_FILENAME, TRAIN_LABEL_FILENAME,
+};
+use std::path::Path;
+
+/// The dataset as flattened vectors, split into training, validation and testing sets.
+///
+/// Images are stored one after another, 784 row-major bytes each. Labels are one byte per
+/// sample, or ten bytes per sample if the builder asked for one-hot labels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mnist {
+    /// Training images.
+    pub trn_img: Vec<u8>,
+    /// Training labels.
+    pub trn_lbl: Vec<u8>,
+    /// Validation images, taken from the training files after the training images.
+    pub val_img: Vec<u8>,
+    /// Validation labels.
+    pub val_lbl: Vec<u8>,
+    /// Testing images.
+    pub tst_img: Vec<u8>,
+    /// Testing labels.
+    pub tst_lbl: Vec<u8>,
+}
+
+/// The dataset with pixels scaled to `[0, 1]`, created by [`Mnist::normalize`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct NormalizedMnist {
+    /// Training images.
+    pub trn_img: Vec<f32>,
+    /// Training labels.
+    pub trn_lbl: Vec<u8>,
+    /// Validation images.
+    pub val_img: Vec<f32>,
+    /// Validation labels.
+    pub val_lbl: Vec<u8>,
+    /// Testing images.
+    pub tst_img: Vec<f32>,
+    /// Testing labels.
+    pub tst_lbl: Vec<u8>,
+}
+
+impl Mnist {
+    /// Scale every pixel to `[0, 1]`.
+    #[must_use]
+    pub fn normalize(self) -> NormalizedMnist {
+        NormalizedMnist {
+            trn_img: normalize_vector(&self.trn_img),
+            trn_lbl: self.trn_lbl,
+            val_img: normalize_vector(&self.val_img),
+            val_lbl: self.val_lbl,
+            tst_img: normalize_vector(&self.tst_img),
+            tst_lbl: self.tst_lbl,
+        }
+    }
+}
+
+/// Scale pixels to `[0, 1]`.
+#[must_use]
+pub fn normalize_vector(pixels: &[u8]) -> Vec<f32> {
+    pixels
+        .iter()
+        .map(|&pixel| f32::from(pixel) / 255.0)
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LabelFormat {
+    Digit,
+    OneHot,
+}
+
+/// Options for reading the dataset into a compatibility [`Mnist`].
+///
+/// By default it reads 60,000 training and 10,000 testing samples, with no validation set and
+/// digit labels, from the standard filenames in `data/`.
+#[derive(Clone, Debug)]
+pub struct MnistBuilder<'a> {
+    lbl_format: LabelFormat,
+    trn_len: u32,
+    val_len: u32,
+    tst_len: u32,
+    base_path: &'a str,
+    trn_img_filename: &'a str,
+    trn_lbl_filename: &'a str,
+    tst_img_filename: &'a str,
+    tst_lbl_filename: &'a str,
+    #[cfg(feature = "download")]
+    download_and_extract: bool,
+    #[cfg(feature = "download")]
+    use_fashion_data: bool,
+}
+
+impl Default for MnistBuilder<'_> {
+    fn default() -> Self {
+        MnistBuilder::new()
+    }
+}
+
+impl<'a> MnistBuilder<'a> {
+    /// Builder with the default options.
+    #[must_use]
+    pub fn new() -> MnistBuilder<'a> {
+        MnistBuilder {
+            lbl_format: LabelFormat::Digit,
+            trn_len: 60_000,
+            val_len: 0,
+            tst_len: 10_000,
+            base_path: "data/",
+            trn_img_filename: TRAIN_DATA_FILENAME,
+            trn_lbl_filename: TRAIN_LABEL_FILENAME,
+            tst_img_filename: TEST_DATA_FILENAME,
+            tst_lbl_filename: TEST_LABEL_FILENAME,
+            #[cfg(feature = "download")]
+            download_and_extract: false,
+            #[cfg(feature = "download")]
+            use_fashion_data: false,
+        }
+    }
+
+    /// Store each label as its digit, in one byte.
+    pub fn label_format_digit(&mut self) -> &mut MnistBuilder<'a> {
+        self.lbl_format = LabelFormat::Digit;
+        self
+    }
+
+    /// Store each label one-hot, as ten bytes of which only the label's is 1.
+    pub fn label_format_one_hot(&mut self) -> &mut MnistBuilder<'a> {
+        self.lbl_format = LabelFormat::OneHot;
+        self
+    }
+
+    /// Number of training samples, read from the start of the training files.
+    pub fn training_set_length(&mut self, length: u32) -> &mut MnistBuilder<'a> {
+        self.trn_len = length;
+        self
+    }
+
+    /// Number of validation samples, read from the training files after the training samples.
+    pub fn validation_set_length(&mut self, length: u32) -> &mut MnistBuilder<'a> {
+        self.val_len = length;
+        self
+    }
+
+    /// Number of testing samples.
+    pub fn test_set_length(&mut self, length: u32) -> &mut MnistBuilder<'a> {
+        self.tst_len = length;
+        self
+    }
+
+    /// Directory holding the dataset files.
+    pub fn base_path(&mut self, base_path: &'a str) -> &mut MnistBuilder<'a> {
+        self.base_path = base_path;
+        self
+    }
+
+    /// Filename of the training images within the base path.
+    pub fn training_images_filename(&mut self, filename: &'a str) -> &mut MnistBuilder<'a> {
+        self.trn_img_filename = filename;
+        self
+    }
+
+    /// Filename of the training labels within the base path.
+    pub fn training_labels_filename(&mut self, filename: &'a str) -> &mut MnistBuilder<'a> {
+        self.trn_lbl_filename = filename;
+        self
+    }
+
+    /// Filename of the testing images within the base path.
+    pub fn test_images_filename(&mut self, filename: &'a str) -> &mut MnistBuilder<'a> {
+        self.tst_img_filename = filename;
+        self
+    }
+
+    /// Filename of the testing labels within the base path.
+    pub fn test_labels_filename(&mut self, filename: &'a str) -> &mut MnistBuilder<'a> {
+        self.tst_lbl_filename = filename;
+        self
+    }
+
+    /// Download any missing files into the base path before reading them, with
+    /// [`download`](crate::download).
+    #[cfg(feature = "download")]
+    pub fn download_and_extract(&mut self) -> &mut MnistBuilder<'a> {
+        self.download_and_extract = true;
+        self
+    }
+
+    /// Download Fashion-MNIST rather than MNIST.
+    #[cfg(feature = "download")]
+    pub fn use_fashion_data(&mut self) -> &mut MnistBuilder<'a> {
+        self.use_fashion_data = true;
+        self
+    }
+
+    /// Read the dataset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a file cannot be read or is malformed, or holds fewer samples than asked for.
+    #[must_use]
+    pub fn finalize(&self) -> Mnist {
+        let dir = Path::new(self.base_path);
+        #[cfg(feature = "download")]
+        if self.download_and_extract {
+            let dataset = if self.use_fashion_data {
+                crate::Dataset::Fashion
+            } else {
+                crate::Dataset::Mnist
+            };
+            crate::download(dataset, dir, |_, _, _| {})
+                .unwrap_or_else(|err| panic!("Cannot download {}: {}", dataset, err));
+        }
+
+        let source = LocalDir::new(dir);
+        let trn_len = self.trn_len as usize;
+        let val_len = self.val_len as usize;
+        let (trn_img, trn_lbl) = self.read(
+            &source,
+            "training",
+            (self.trn_img_filename, self.trn_lbl_filename),
+            trn_len + val_len,
+        );
+        let (tst_img, tst_lbl) = self.read(
+            &source,
+            "testing",
+            (self.tst_img_filename, self.tst_lbl_filename),
+            self.tst_len as usize,
+        );
+
+        let image_split = trn_len * IMAGE_ROWS * IMAGE_COLUMNS;
+        let label_split = trn_len * self.label_bytes();
+        Mnist {
+            val_img: trn_img[image_split..].to_vec(),
+            val_lbl: trn_lbl[label_split..].to_vec(),
+            trn_img: trn_img[..image_split].to_vec(),
+            trn_lbl: trn_lbl[..label_split].to_vec(),
+            tst_img,
+            tst_lbl,
+        }
+    }
+
+    /// Bytes each label takes in the chosen format.
+    fn label_bytes(&self) -> usize {
+        match self.lbl_format {
+            LabelFormat::Digit => 1,
+            LabelFormat::OneHot => NUM_CLASSES,
+        }
+    }
+
+    /// Read the first `len` samples of a pair of files holding the `split` split, flattened.
+    fn read(
+        &self,
+        source: &LocalDir,
+        split: &str,
+        (images, labels): (&str, &str),
+        len: usize,
+    ) -> (Vec<u8>, Vec<u8>) {
+        let path = |name: &str| Path::new(self.base_path).join(name).display().to_string();
+        let read = |name: &str| {
+            source
+                .read(name)
+                .unwrap_or_else(|err| panic!("Cannot read \"{}\": {err}", path(name)))
+        };
+        let (image_file, label_file) = read_split(&read(images), &read(labels), split)
+            .unwrap_or_else(|err| {
+                panic!(
+                    "Cannot read the {split} files in \"{}\": {err}",
+                    self.base_path
+                )
+            });
+        assert!(
+            label_file.len() >= len,
+            "Asked for {} samples, but \"{}\" holds fewer.",
+            len,
+            path(images)
+        );
+
+        let image_bytes = image_file[..len].as_flattened().to_vec();
+        let label_bytes = match self.lbl_format {
+            LabelFormat::Digit => label_file[..len].to_vec(),
+            LabelFormat::OneHot => label_file[..len]
+                .iter()
+                .flat_map(|&label| {
+                    (0..NUM_CLASSES).map(move |class| u8::from(class == usize::from(label)))
+                })
+                .collect(),
+        };
+        (image_bytes, label_bytes)
+    }
+}
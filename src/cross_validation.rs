@@ -0,0 +1,196 @@
+//! K-fold cross-validation over the training data.
+
+use crate::metrics::Metrics;
+use crate::subset::gather;
+use crate::{ImageSet, Mnist, IMAGE_COLUMNS, IMAGE_ROWS};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+use std::thread;
+
+/// Metrics of every fold of a cross-validation run, created by [`Mnist::cross_validate`].
+///
+/// Its [`Display`](fmt::Display) implementation prints the accuracy and macro F1 of each fold,
+/// followed by their mean and standard deviation.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrossValidation {
+    /// Metrics on the held-out part of each fold, in fold order.
+    pub folds: Vec<Metrics>,
+}
+
+impl CrossValidation {
+    /// Mean and standard deviation of `metric` over the folds.
+    ///
+    /// The standard deviation is that of the fold values themselves (dividing by the number of
+    /// folds), as reported by most cross-validation tools.
+    #[must_use]
+    pub fn mean_std(&self, metric: impl Fn(&Metrics) -> f64) -> (f64, f64) {
+        let values: Vec<f64> = self.folds.iter().map(metric).collect();
+        #[allow(clippy::cast_precision_loss)]
+        let len = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / len;
+        let variance = values
+            .iter()
+            .map(|value| (value - mean) * (value - mean))
+            .sum::<f64>()
+            / len;
+        (mean, variance.sqrt())
+    }
+
+    /// Mean and standard deviation of the accuracy over the folds.
+    #[must_use]
+    pub fn accuracy(&self) -> (f64, f64) {
+        self.mean_std(|metrics| metrics.accuracy)
+    }
+
+    /// Mean and standard deviation of the macro F1 score over the folds.
+    #[must_use]
+    pub fn macro_f1(&self) -> (f64, f64) {
+        self.mean_std(Metrics::macro_f1)
+    }
+}
+
+impl fmt::Display for CrossValidation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, " Fold  Accuracy  Macro F1")?;
+        for (fold, metrics) in self.folds.iter().enumerate() {
+            writeln!(
+                f,
+                "{fold:>5}  {:>8.4}  {:>8.4}",
+                metrics.accuracy,
+                metrics.macro_f1()
+            )?;
+        }
+        let (accuracy, accuracy_std) = self.accuracy();
+        let (macro_f1, macro_f1_std) = self.macro_f1();
+        writeln!(
+            f,
+            "Accuracy: {accuracy:.4} ± {accuracy_std:.4}  Macro F1: {macro_f1:.4} ± {macro_f1_std:.4}"
+        )
+    }
+}
+
+impl Mnist {
+    /// Run `k`-fold cross-validation over the training data.
+    ///
+    /// The training samples are shuffled with `seed` and dealt into `k` folds of nearly equal
+    /// size. For each fold, `run` is handed the other folds as a training set and the fold itself
+    /// as a validation set, and returns the metrics of a model trained on the first and scored
+    /// on the second.
+    ///
+    /// With `workers` greater than zero, the folds are split between that many threads;
+    /// otherwise they run one after another on the calling thread. Either way the results are in
+    /// fold order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is less than two or more than the number of training samples, or if `run`
+    /// panics.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    ///
+    /// // Score a nearest-class-mean classifier on five folds, two at a time.
+    /// let results = mnist.cross_validate(5, 7, 2, |train, validation| {
+    ///     let means = train.class_mean_images();
+    ///     validation.evaluate(0, |image| {
+    ///         let distance = |mean: &[u8; 784]| -> u32 {
+    ///             mean.iter().zip(image.iter()).map(|(&a, &b)| u32::from(a.abs_diff(b))).sum()
+    ///         };
+    ///         (0..10u8).min_by_key(|&class| distance(&means[usize::from(class)])).unwrap()
+    ///     })
+    /// });
+    /// print!("{}", results);
+    /// ```
+    #[must_use]
+    pub fn cross_validate(
+        &self,
+        k: usize,
+        seed: u64,
+        workers: usize,
+        run: impl Fn(&ImageSet, &ImageSet) -> Metrics + Sync,
+    ) -> CrossValidation {
+        cross_validate(&self.train_data, &self.train_labels, k, seed, workers, &run)
+    }
+}
+
+impl ImageSet {
+    /// Run `k`-fold cross-validation over the set, as [`Mnist::cross_validate`] does over the
+    /// training data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is less than two or more than the number of samples, or if `run` panics.
+    #[must_use]
+    pub fn cross_validate(
+        &self,
+        k: usize,
+        seed: u64,
+        workers: usize,
+        run: impl Fn(&ImageSet, &ImageSet) -> Metrics + Sync,
+    ) -> CrossValidation {
+        cross_validate(&self.images, &self.labels, k, seed, workers, &run)
+    }
+}
+
+fn cross_validate(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+    k: usize,
+    seed: u64,
+    workers: usize,
+    run: &(impl Fn(&ImageSet, &ImageSet) -> Metrics + Sync),
+) -> CrossValidation {
+    assert!(
+        (2..=images.len()).contains(&k),
+        "Number of folds must be between two and the number of samples."
+    );
+
+    let mut order: Vec<usize> = (0..images.len()).collect();
+    order.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+
+    // Fold `i` holds every `k`th sample of the shuffled order, starting from the `i`th.
+    let run_fold = |fold: usize| {
+        let mut train = Vec::with_capacity(images.len());
+        let mut validation = Vec::with_capacity(images.len() / k + 1);
+        for (position, &index) in order.iter().enumerate() {
+            if position % k == fold {
+                validation.push(index);
+            } else {
+                train.push(index);
+            }
+        }
+        run(
+            &gather(images, labels, &train),
+            &gather(images, labels, &validation),
+        )
+    };
+
+    let folds: Vec<usize> = (0..k).collect();
+    let folds = if workers == 0 {
+        folds.into_iter().map(run_fold).collect()
+    } else {
+        let per_worker = k.div_ceil(workers);
+        let run_fold = &run_fold;
+        thread::scope(|scope| {
+            let handles: Vec<_> = folds
+                .chunks(per_worker)
+                .map(|chunk| {
+                    scope
+                        .spawn(move || chunk.iter().map(|&fold| run_fold(fold)).collect::<Vec<_>>())
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("Cross-validation worker panicked."))
+                .collect()
+        })
+    };
+    CrossValidation { folds }
+}
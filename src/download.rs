@@ -0,0 +1,341 @@
+//! Downloading datasets in the MNIST file format from their public mirrors.
+
+use crate::md5::Md5;
+use crate::verify::check_idx;
+use crate::{DataSource, HttpSource, IDX_FILENAMES};
+use ::flate2::read::GzDecoder;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A dataset published as the same four gzipped IDX files as MNIST, and so readable with
+/// [`Mnist::new`](crate::Mnist::new) once downloaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dataset {
+    /// The original handwritten digits.
+    Mnist,
+    /// Zalando's Fashion-MNIST: ten classes of clothing photographed in grayscale.
+    Fashion,
+    /// Kuzushiji-MNIST: ten classes of cursive Japanese characters.
+    Kmnist,
+}
+
+impl Dataset {
+    /// Every dataset that can be downloaded.
+    pub const ALL: [Dataset; 3] = [Dataset::Mnist, Dataset::Fashion, Dataset::Kmnist];
+
+    /// Short lowercase name of the dataset, as accepted by [`Dataset::from_str`].
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Dataset::Mnist => "mnist",
+            Dataset::Fashion => "fashion",
+            Dataset::Kmnist => "kmnist",
+        }
+    }
+
+    /// URL of the directory holding the dataset's `.gz` files.
+    #[must_use]
+    pub fn base_url(self) -> &'static str {
+        match self {
+            Dataset::Mnist => "https://ossci-datasets.s3.amazonaws.com/mnist",
+            Dataset::Fashion => "http://fashion-mnist.s3-website.eu-central-1.amazonaws.com",
+            Dataset::Kmnist => "http://codh.rois.ac.jp/kmnist/dataset/kmnist",
+        }
+    }
+
+    /// MD5 digest, as published with the dataset, of the gzipped copy of `filename`, one of the
+    /// four IDX files such as `"train-images-idx3-ubyte"`; `None` for any other name.
+    ///
+    /// [`download`] checks every file it fetches against these, and `mnist verify --dataset`
+    /// checks the copies kept next to the IDX files.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_mnist::Dataset;
+    ///
+    /// assert_eq!(
+    ///     Dataset::Mnist.md5("t10k-labels-idx1-ubyte"),
+    ///     Some("ec29112dd5afa0611ce80d1b7f02629c")
+    /// );
+    /// assert_eq!(Dataset::Mnist.md5("t10k-labels-idx1-ubyte.gz"), None);
+    /// ```
+    #[must_use]
+    pub fn md5(self, filename: &str) -> Option<&'static str> {
+        // In the order of `IDX_FILENAMES`.
+        let digests = match self {
+            Dataset::Mnist => [
+                "f68b3c2dcbeaaa9fbdd348bbdeb94873",
+                "9fb629c4189551a2d022fa330f9573f3",
+                "d53e105ee54ea40749a09fcbcd1e9432",
+                "ec29112dd5afa0611ce80d1b7f02629c",
+            ],
+            Dataset::Fashion => [
+                "8d4fb7e6c68d591d4c3dfef9ec88bf0d",
+                "bef4ecab320f06d8554ea6380940ec79",
+                "25c81989df183df01b3e8a0aad5dffbe",
+                "bb300cfdad3c16e7a12a480ee83cd310",
+            ],
+            Dataset::Kmnist => [
+                "bdb82020997e1d708af4cf47b453dcf7",
+                "5c965bf0a639b31b8f53240b1b52f4d7",
+                "e144d726b3acfaa3e44228e80efcd344",
+                "7320c461ea6c1c855c0b718fb2a4b134",
+            ],
+        };
+        let index = IDX_FILENAMES.iter().position(|&name| name == filename)?;
+        Some(digests[index])
+    }
+
+    /// Check the `.gz` files [`download`] keeps in `dir` against [`Dataset::md5`], and that each
+    /// decompresses to the IDX file next to it, so that the IDX files are known to be the
+    /// dataset's own.
+    ///
+    /// Returns a description of each problem found, naming the file, or nothing if every file
+    /// matches; see [`Mnist::verify`](crate::Mnist::verify) for checking the IDX files alone.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::Path;
+    /// use rust_mnist::{Dataset, Mnist};
+    ///
+    /// let dir = Path::new("data");
+    /// let mut problems = Mnist::verify(dir, true);
+    /// problems.extend(Dataset::Mnist.verify_checksums(dir));
+    /// assert!(problems.is_empty());
+    /// ```
+    #[must_use]
+    pub fn verify_checksums(self, dir: &Path) -> Vec<String> {
+        IDX_FILENAMES
+            .iter()
+            .filter_map(|&filename| self.check_checksum(dir, filename).err())
+            .collect()
+    }
+
+    /// Check the `.gz` copy of `filename` in `dir`, describing the first problem found.
+    fn check_checksum(self, dir: &Path, filename: &str) -> Result<(), String> {
+        let name = format!("{filename}.gz");
+        let open = |name: &str, hint: &str| {
+            fs::File::open(dir.join(name))
+                .map(io::BufReader::new)
+                .map_err(|err| match err.kind() {
+                    io::ErrorKind::NotFound => {
+                        format!("{name} is missing from {}{hint}.", dir.display())
+                    }
+                    _ => format!("{name} cannot be opened: {err}."),
+                })
+        };
+        let hint = "; download the dataset again to keep a copy to check against";
+        let unreadable = |name: &str, err: io::Error| format!("{name} cannot be read: {err}.");
+
+        let actual = Md5::of_reader(&mut open(&name, hint)?)
+            .map_err(|err| unreadable(&name, err))?
+            .hex();
+        let expected = self.md5(filename).unwrap_or_default();
+        if actual != expected {
+            return Err(format!(
+                "{name} has MD5 {actual} where {expected} was expected; it is not the file {self} publishes."
+            ));
+        }
+
+        let decompressed = Md5::of_reader(&mut GzDecoder::new(open(&name, hint)?))
+            .map_err(|err| unreadable(&name, err))?
+            .hex();
+        let idx = Md5::of_reader(&mut open(filename, "")?)
+            .map_err(|err| unreadable(filename, err))?
+            .hex();
+        if decompressed == idx {
+            Ok(())
+        } else {
+            Err(format!(
+                "{filename} differs from {name}; it has been changed since it was downloaded."
+            ))
+        }
+    }
+}
+
+impl fmt::Display for Dataset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Dataset {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Dataset, String> {
+        Dataset::ALL
+            .iter()
+            .copied()
+            .find(|dataset| dataset.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                let names: Vec<_> = Dataset::ALL.iter().map(|dataset| dataset.name()).collect();
+                format!(
+                    "Unknown dataset \"{name}\"; expected one of {}.",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Download `dataset` into `dir`, creating it if needed, as the four uncompressed IDX files read
+/// by [`Mnist::new`](crate::Mnist::new).
+///
+/// Files already in `dir` are kept rather than downloaded again. Each downloaded file is
+/// decompressed, checked to hold the expected header and number of samples, and its compressed
+/// bytes checked against [`Dataset::md5`] before it is put in place, so an interrupted, corrupt
+/// or substituted download never leaves a file that looks complete. The verified `.gz` files are
+/// kept next to the IDX files, for `mnist verify --dataset` to check them again later.
+///
+/// `progress` is called as each file downloads with its name, the number of compressed bytes
+/// received so far and, if the server reported it, the total.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be fetched or written, or does not hold what it should.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::Path;
+/// use rust_mnist::{download, Dataset, Mnist};
+///
+/// download(Dataset::Fashion, Path::new("data"), |_, _, _| {})?;
+/// let fashion = Mnist::new(Path::new("data"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn download(
+    dataset: Dataset,
+    dir: &Path,
+    progress: impl FnMut(&str, u64, Option<u64>),
+) -> io::Result<()> {
+    download_from(
+        &HttpSource::new(dataset.base_url()),
+        dir,
+        Some(dataset),
+        progress,
+    )
+}
+
+/// Download a dataset laid out like MNIST from `source`, such as a mirror served with
+/// [`HttpSource`] or a directory of `.gz` files read with [`LocalDir`](crate::LocalDir), as
+/// [`download`] does.
+///
+/// The source must hold the four gzipped IDX files, named as in the dataset's public mirrors:
+/// `train-images-idx3-ubyte.gz` and so on. If `dataset` names the dataset the source mirrors,
+/// the files must have its sample counts and match its [checksums](Dataset::md5); otherwise,
+/// for datasets this crate does not know, each file need only hold as many samples as its
+/// header says.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be fetched or written, or does not hold what it should.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::Path;
+/// use rust_mnist::{download_from, Dataset, HttpSource};
+///
+/// let mirror = HttpSource::new("https://example.com/mnist");
+/// download_from(&mirror, Path::new("data"), Some(Dataset::Mnist), |_, _, _| {})?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn download_from(
+    source: &(impl DataSource + ?Sized),
+    dir: &Path,
+    dataset: Option<Dataset>,
+    mut progress: impl FnMut(&str, u64, Option<u64>),
+) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for filename in IDX_FILENAMES {
+        let path = dir.join(filename);
+        if path.exists() {
+            continue;
+        }
+
+        // Fetch and decompress next to the destination, and only move the files into place once
+        // both are checked.
+        let compressed = format!("{filename}.gz");
+        let partial = dir.join(format!("{filename}.part"));
+        let compressed_partial = dir.join(format!("{compressed}.part"));
+        let result = fetch(
+            source,
+            filename,
+            &compressed_partial,
+            &partial,
+            dataset,
+            &mut progress,
+        )
+        .and_then(|()| fs::rename(&compressed_partial, dir.join(&compressed)))
+        .and_then(|()| fs::rename(&partial, &path));
+        if result.is_err() {
+            let _ = fs::remove_file(&compressed_partial);
+            let _ = fs::remove_file(&partial);
+        }
+        result?;
+    }
+    Ok(())
+}
+
+/// Fetch the gzipped `filename` from `source` into `compressed`, decompress it into
+/// `decompressed`, and check both.
+fn fetch(
+    source: &(impl DataSource + ?Sized),
+    filename: &str,
+    compressed: &Path,
+    decompressed: &Path,
+    dataset: Option<Dataset>,
+    progress: &mut impl FnMut(&str, u64, Option<u64>),
+) -> io::Result<()> {
+    let name = format!("{filename}.gz");
+    let total = source.size(&name);
+    let mut reader = ProgressReader {
+        inner: source.open(&name)?,
+        received: 0,
+        report: |received| progress(filename, received, total),
+    };
+    io::copy(&mut reader, &mut fs::File::create(compressed)?)?;
+
+    io::copy(
+        &mut GzDecoder::new(io::BufReader::new(fs::File::open(compressed)?)),
+        &mut fs::File::create(decompressed)?,
+    )?;
+    let mut reader = io::BufReader::new(fs::File::open(decompressed)?);
+    // Only a known dataset's sample counts are known.
+    check_idx(&mut reader, filename, dataset.is_some()).map_err(|problem| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("Downloaded {problem}"))
+    })?;
+
+    if let Some(expected) = dataset.and_then(|dataset| dataset.md5(filename)) {
+        let actual = Md5::of_reader(&mut io::BufReader::new(fs::File::open(compressed)?))?.hex();
+        if actual != expected {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Downloaded {name} has MD5 {actual} where {expected} was expected; the \
+                     source holds different data."
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reader reporting the running total of bytes read through it.
+struct ProgressReader<R, F> {
+    inner: R,
+    received: u64,
+    report: F,
+}
+
+impl<R: Read, F: FnMut(u64)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.received += read as u64;
+        (self.report)(self.received);
+        Ok(read)
+    }
+}
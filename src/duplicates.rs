@@ -0,0 +1,195 @@
+//! Perceptual hashing, for finding images that look the same.
+
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Average hash ("aHash") of an image: it is shrunk to 8x8, and each bit records whether a
+/// pixel of the thumbnail is brighter than the thumbnail's mean.
+///
+/// Images that look alike have hashes differing in few bits; compare them with
+/// `(a ^ b).count_ones()`.
+#[must_use]
+pub fn average_hash(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> u64 {
+    let thumbnail = shrink(image, 8, 8);
+    let total: u32 = thumbnail.iter().sum();
+    thumbnail
+        .iter()
+        .enumerate()
+        .filter(|&(_, &pixel)| pixel * 64 > total)
+        .fold(0, |hash, (bit, _)| hash | 1 << bit)
+}
+
+/// Difference hash ("dHash") of an image: it is shrunk to 9x8, and each bit records whether a
+/// pixel of the thumbnail is brighter than its right-hand neighbour.
+///
+/// Images that look alike have hashes differing in few bits; compare them with
+/// `(a ^ b).count_ones()`.
+#[must_use]
+pub fn difference_hash(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> u64 {
+    let thumbnail = shrink(image, 9, 8);
+    let mut hash = 0;
+    for (row, pixels) in thumbnail.chunks(9).enumerate() {
+        for (column, pair) in pixels.windows(2).enumerate() {
+            if pair[0] > pair[1] {
+                hash |= 1 << (row * 8 + column);
+            }
+        }
+    }
+    hash
+}
+
+impl Mnist {
+    /// Group samples from both splits whose [difference hashes](difference_hash) differ in at
+    /// most `threshold` bits, so that duplicates within a split and test images leaking from the
+    /// training set can be inspected. A threshold of zero finds visually identical images.
+    ///
+    /// Only groups of two or more samples are returned. Samples within a group, and groups by
+    /// their first sample, are sorted with training samples first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is 64 or more, which would put every sample in one group.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let leaks = mnist
+    ///     .find_near_duplicates(2)
+    ///     .into_iter()
+    ///     .filter(|group| group.iter().any(|&(split, _)| split == Split::Test))
+    ///     .count();
+    /// ```
+    #[must_use]
+    pub fn find_near_duplicates(&self, threshold: u32) -> Vec<Vec<(Split, usize)>> {
+        let samples: Vec<(Split, usize)> = (0..self.train_data.len())
+            .map(|index| (Split::Train, index))
+            .chain((0..self.test_data.len()).map(|index| (Split::Test, index)))
+            .collect();
+        let images = self.train_data.iter().chain(&self.test_data);
+
+        near_duplicate_groups(images, threshold)
+            .into_iter()
+            .map(|group| group.into_iter().map(|sample| samples[sample]).collect())
+            .collect()
+    }
+}
+
+impl ImageSet {
+    /// Group the indices of samples whose difference hashes differ in at most `threshold` bits,
+    /// as [`Mnist::find_near_duplicates`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is 64 or more.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_mnist::ImageSet;
+    ///
+    /// // A bar, a brighter copy of it, a different bar and a blank image.
+    /// let bar = |rows: std::ops::Range<usize>, brightness: u8| {
+    ///     let mut image = [0; 784];
+    ///     for row in rows {
+    ///         image[row * 28 + 4..row * 28 + 24].fill(brightness);
+    ///     }
+    ///     image
+    /// };
+    /// let set = ImageSet {
+    ///     images: vec![bar(4..8, 200), [0; 784], bar(4..8, 255), bar(18..24, 255)],
+    ///     labels: vec![1, 0, 1, 7],
+    /// };
+    ///
+    /// assert_eq!(set.find_near_duplicates(0), vec![vec![0, 2]]);
+    /// ```
+    #[must_use]
+    pub fn find_near_duplicates(&self, threshold: u32) -> Vec<Vec<usize>> {
+        near_duplicate_groups(self.images.iter(), threshold)
+    }
+}
+
+/// Indices of groups of images whose difference hashes are within `threshold` bits of each other,
+/// directly or through other members of the group.
+fn near_duplicate_groups<'a>(
+    images: impl Iterator<Item = &'a [u8; IMAGE_ROWS * IMAGE_COLUMNS]>,
+    threshold: u32,
+) -> Vec<Vec<usize>> {
+    assert!(threshold < 64, "Threshold must be less than 64 bits.");
+    let hashes: Vec<u64> = images.map(difference_hash).collect();
+    let mut parents: Vec<usize> = (0..hashes.len()).collect();
+
+    // Two hashes within `threshold` bits of each other must agree exactly on at least one of
+    // `threshold + 1` chunks, so only samples sharing a chunk need comparing.
+    let chunks = threshold + 1;
+    for chunk in 0..chunks {
+        let start = 64 * chunk / chunks;
+        let width = 64 * (chunk + 1) / chunks - start;
+        let mask = u64::MAX >> (64 - width);
+
+        let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (sample, hash) in hashes.iter().enumerate() {
+            buckets
+                .entry(hash >> start & mask)
+                .or_default()
+                .push(sample);
+        }
+        for bucket in buckets.values() {
+            for (position, &a) in bucket.iter().enumerate() {
+                for &b in &bucket[position + 1..] {
+                    if (hashes[a] ^ hashes[b]).count_ones() <= threshold {
+                        let (root_a, root_b) = (root(&mut parents, a), root(&mut parents, b));
+                        parents[root_a.max(root_b)] = root_a.min(root_b);
+                    }
+                }
+            }
+        }
+    }
+
+    // Samples are visited in order, so groups come out sorted by their first member.
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut group_of_root = HashMap::new();
+    for sample in 0..hashes.len() {
+        let root = root(&mut parents, sample);
+        let group = *group_of_root.entry(root).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[group].push(sample);
+    }
+    groups.retain(|group| group.len() > 1);
+    groups
+}
+
+/// Representative of the union-find set containing `sample`, halving the path on the way.
+fn root(parents: &mut [usize], mut sample: usize) -> usize {
+    while parents[sample] != sample {
+        parents[sample] = parents[parents[sample]];
+        sample = parents[sample];
+    }
+    sample
+}
+
+/// Shrink an image to `width` by `height` pixels, averaging the pixels each one covers.
+fn shrink(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], width: usize, height: usize) -> Vec<u32> {
+    let span =
+        |cell: usize, cells: usize, size: usize| cell * size / cells..(cell + 1) * size / cells;
+    let mut thumbnail = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let (rows, columns) = (span(y, height, IMAGE_ROWS), span(x, width, IMAGE_COLUMNS));
+            let area = u32::try_from(rows.len() * columns.len()).unwrap();
+            let total: u32 = rows
+                .flat_map(|row| {
+                    columns
+                        .clone()
+                        .map(move |column| u32::from(image[row * IMAGE_COLUMNS + column]))
+                })
+                .sum();
+            thumbnail.push(total / area);
+        }
+    }
+    thumbnail
+}
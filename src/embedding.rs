@@ -0,0 +1,160 @@
+//! Two-dimensional embeddings of the images, for scatter plots in external tools.
+
+use crate::pca::Pca;
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+/// How [`Mnist::embed_2d`] maps images onto a plane.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Projection {
+    /// The first two principal components of the training images.
+    Pca,
+    /// Two random directions with `±1` weights, generated from `seed`. Much faster than PCA, and
+    /// distances between images are roughly preserved.
+    Random { seed: u64 },
+}
+
+impl Mnist {
+    /// Project every image of the split onto a plane, returning an `(x, y, label)` point per
+    /// sample. Pixels are scaled to `[0, 1]` first, and [`Projection::Pca`] is fitted on the
+    /// training images so both splits share the same axes.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{Mnist, Projection, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let points = mnist.embed_2d(Split::Test, Projection::Random { seed: 0 });
+    /// ```
+    #[must_use]
+    pub fn embed_2d(&self, split: Split, projection: Projection) -> Vec<(f32, f32, u8)> {
+        embed(
+            &self.train_data,
+            self.images(split),
+            self.labels(split),
+            projection,
+        )
+    }
+
+    /// Project every image of the split onto a plane, as [`Mnist::embed_2d`] does, and write the
+    /// points to `path`. A `.json` file gets an array of `{"x": ..., "y": ..., "label": ...}`
+    /// objects; anything else gets CSV with an `x,y,label` header.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::{Mnist, Projection, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.export_embedding(Path::new("embedding.csv"), Split::Test, Projection::Pca)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn export_embedding(
+        &self,
+        path: &Path,
+        split: Split,
+        projection: Projection,
+    ) -> io::Result<()> {
+        write_points(path, &self.embed_2d(split, projection))
+    }
+}
+
+impl ImageSet {
+    /// Project every image of the set onto a plane, as [`Mnist::embed_2d`] does, fitting
+    /// [`Projection::Pca`] on the set itself.
+    #[must_use]
+    pub fn embed_2d(&self, projection: Projection) -> Vec<(f32, f32, u8)> {
+        embed(&self.images, &self.images, &self.labels, projection)
+    }
+
+    /// Project every image of the set onto a plane and write the points to `path`, as
+    /// [`Mnist::export_embedding`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn export_embedding(&self, path: &Path, projection: Projection) -> io::Result<()> {
+        write_points(path, &self.embed_2d(projection))
+    }
+}
+
+/// Project `images` onto a plane, fitting PCA on `fit_images` if needed.
+fn embed(
+    fit_images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+    projection: Projection,
+) -> Vec<(f32, f32, u8)> {
+    let axes: [Vec<f32>; 2] = match projection {
+        Projection::Pca => {
+            let pca = Pca::fit(fit_images, 2, 0);
+            return images
+                .iter()
+                .zip(labels)
+                .map(|(image, &label)| {
+                    let coordinates = pca.project(image);
+                    (coordinates[0], coordinates[1], label)
+                })
+                .collect();
+        }
+        Projection::Random { seed } => {
+            // Scale the axes to unit length so the coordinates stay in a readable range.
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let weight = 1.0 / 28.0;
+            [(); 2].map(|()| {
+                (0..IMAGE_ROWS * IMAGE_COLUMNS)
+                    .map(|_| if rng.gen() { weight } else { -weight })
+                    .collect()
+            })
+        }
+    };
+
+    let along = |axis: &[f32], image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]| {
+        axis.iter()
+            .zip(image.iter())
+            .map(|(weight, &pixel)| weight * f32::from(pixel) / 255.0)
+            .sum()
+    };
+    images
+        .iter()
+        .zip(labels)
+        .map(|(image, &label)| (along(&axes[0], image), along(&axes[1], image), label))
+        .collect()
+}
+
+/// Write points as JSON or CSV, depending on the extension of `path`.
+fn write_points(path: &Path, points: &[(f32, f32, u8)]) -> io::Result<()> {
+    let json = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+    let mut writer = io::BufWriter::new(fs::File::create(path)?);
+
+    if json {
+        writeln!(writer, "[")?;
+        for (i, (x, y, label)) in points.iter().enumerate() {
+            let separator = if i + 1 < points.len() { "," } else { "" };
+            writeln!(
+                writer,
+                r#"  {{"x": {x}, "y": {y}, "label": {label}}}{separator}"#
+            )?;
+        }
+        writeln!(writer, "]")?;
+    } else {
+        writeln!(writer, "x,y,label")?;
+        for (x, y, label) in points {
+            writeln!(writer, "{x},{y},{label}")?;
+        }
+    }
+    writer.flush()
+}
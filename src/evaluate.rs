@@ -0,0 +1,241 @@
+//! Running a model over the testing data and scoring its predictions.
+
+use crate::metrics::{top_k_accuracy, Metrics};
+use crate::{ImageSet, Mnist, IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES};
+use std::thread;
+
+impl Mnist {
+    /// Predict the label of every testing image with `predict` and score the predictions.
+    ///
+    /// With `workers` greater than zero, the images are split between that many threads;
+    /// otherwise they are predicted in order on the calling thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `predict` panics or returns a label of 10 or more.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    ///
+    /// // A "model" that guesses from how much ink an image has.
+    /// let metrics = mnist.evaluate(4, |image| {
+    ///     let ink: u32 = image.iter().map(|&pixel| u32::from(pixel)).sum();
+    ///     (ink / 20_000).min(9) as u8
+    /// });
+    /// print!("{}", metrics);
+    /// ```
+    #[must_use]
+    pub fn evaluate(
+        &self,
+        workers: usize,
+        predict: impl Fn(&[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> u8 + Sync,
+    ) -> Metrics {
+        evaluate(&self.test_data, &self.test_labels, workers, &predict)
+    }
+
+    /// Predict class probabilities for every testing image with `predict` and score the most
+    /// probable class, as [`Mnist::evaluate`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `predict` panics.
+    #[must_use]
+    pub fn evaluate_proba(
+        &self,
+        workers: usize,
+        predict: impl Fn(&[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> [f32; NUM_CLASSES] + Sync,
+    ) -> Metrics {
+        evaluate(&self.test_data, &self.test_labels, workers, &|image| {
+            argmax(&predict(image))
+        })
+    }
+
+    /// Predict class probabilities for every testing image with `predict` and return the
+    /// fraction whose label is among the `k` most probable, as computed by
+    /// [`top_k_accuracy`](crate::metrics::top_k_accuracy).
+    ///
+    /// Images are split between `workers` threads as by [`Mnist::evaluate`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero or if `predict` panics.
+    #[must_use]
+    pub fn top_k_accuracy(
+        &self,
+        workers: usize,
+        k: usize,
+        predict: impl Fn(&[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> [f32; NUM_CLASSES] + Sync,
+    ) -> f64 {
+        let probabilities = predict_all(&self.test_data, workers, &predict);
+        top_k_accuracy(&probabilities, &self.test_labels, k)
+    }
+
+    /// Predict the label of every testing image with `predict` and return the indices of the
+    /// images it got wrong, in order.
+    ///
+    /// Images are split between `workers` threads as by [`Mnist::evaluate`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `predict` panics.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{print_grid, Mnist};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let wrong = mnist.misclassified_indices(4, |image| {
+    ///     let ink: u32 = image.iter().map(|&pixel| u32::from(pixel)).sum();
+    ///     (ink / 20_000).min(9) as u8
+    /// });
+    ///
+    /// // Look at the first few mistakes.
+    /// let samples: Vec<_> = wrong
+    ///     .iter()
+    ///     .take(8)
+    ///     .map(|&index| (&mnist.test_data[index], mnist.test_labels[index]))
+    ///     .collect();
+    /// print_grid(&samples, 4);
+    /// ```
+    #[must_use]
+    pub fn misclassified_indices(
+        &self,
+        workers: usize,
+        predict: impl Fn(&[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> u8 + Sync,
+    ) -> Vec<usize> {
+        misclassified_indices(&self.test_data, &self.test_labels, workers, &predict)
+    }
+}
+
+impl ImageSet {
+    /// Predict the label of every image in the set with `predict` and score the predictions, as
+    /// [`Mnist::evaluate`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `predict` panics or returns a label of 10 or more.
+    #[must_use]
+    pub fn evaluate(
+        &self,
+        workers: usize,
+        predict: impl Fn(&[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> u8 + Sync,
+    ) -> Metrics {
+        evaluate(&self.images, &self.labels, workers, &predict)
+    }
+
+    /// Predict class probabilities for every image in the set with `predict` and score the most
+    /// probable class, as [`Mnist::evaluate_proba`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `predict` panics.
+    #[must_use]
+    pub fn evaluate_proba(
+        &self,
+        workers: usize,
+        predict: impl Fn(&[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> [f32; NUM_CLASSES] + Sync,
+    ) -> Metrics {
+        evaluate(&self.images, &self.labels, workers, &|image| {
+            argmax(&predict(image))
+        })
+    }
+
+    /// Predict class probabilities for every image in the set with `predict` and return the
+    /// fraction whose label is among the `k` most probable, as [`Mnist::top_k_accuracy`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero or if `predict` panics.
+    #[must_use]
+    pub fn top_k_accuracy(
+        &self,
+        workers: usize,
+        k: usize,
+        predict: impl Fn(&[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> [f32; NUM_CLASSES] + Sync,
+    ) -> f64 {
+        let probabilities = predict_all(&self.images, workers, &predict);
+        top_k_accuracy(&probabilities, &self.labels, k)
+    }
+
+    /// Predict the label of every image in the set with `predict` and return the indices of the
+    /// images it got wrong, as [`Mnist::misclassified_indices`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `predict` panics.
+    #[must_use]
+    pub fn misclassified_indices(
+        &self,
+        workers: usize,
+        predict: impl Fn(&[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> u8 + Sync,
+    ) -> Vec<usize> {
+        misclassified_indices(&self.images, &self.labels, workers, &predict)
+    }
+}
+
+fn evaluate(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+    workers: usize,
+    predict: &(impl Fn(&[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> u8 + Sync),
+) -> Metrics {
+    let predicted = predict_all(images, workers, predict);
+    Metrics::from_predictions(&predicted, labels)
+}
+
+fn misclassified_indices(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+    workers: usize,
+    predict: &(impl Fn(&[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> u8 + Sync),
+) -> Vec<usize> {
+    predict_all(images, workers, predict)
+        .into_iter()
+        .zip(labels)
+        .enumerate()
+        .filter(|(_, (predicted, &label))| *predicted != label)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Apply `predict` to every image, in order, on `workers` threads or the calling thread.
+pub(crate) fn predict_all<T: Send>(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    workers: usize,
+    predict: &(impl Fn(&[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> T + Sync),
+) -> Vec<T> {
+    if workers == 0 {
+        return images.iter().map(predict).collect();
+    }
+
+    let per_worker = images.len().div_ceil(workers).max(1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = images
+            .chunks(per_worker)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(predict).collect::<Vec<_>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Prediction worker panicked."))
+            .collect()
+    })
+}
+
+/// Index of the largest probability, preferring the first of equal ones.
+pub(crate) fn argmax(probabilities: &[f32; NUM_CLASSES]) -> u8 {
+    let mut best = 0;
+    for (class, probability) in (0..).zip(probabilities) {
+        if probability
+            .total_cmp(&probabilities[usize::from(best)])
+            .is_gt()
+        {
+            best = class;
+        }
+    }
+    best
+}
@@ -0,0 +1,115 @@
+//! C interface to the loader, declared in `include/rust_mnist.h`.
+//!
+//! Build the shared library with `cargo rustc --lib --release --features ffi --crate-type cdylib`
+//! (or `staticlib` for a static one).
+//!
+//! Regenerate the header after changing anything here with
+//! `cbindgen --config cbindgen.toml --output include/rust_mnist.h src/ffi.rs`.
+
+use crate::{LoadOptions, Mnist, Split};
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+use std::path::Path;
+use std::ptr;
+
+/// Selects the training images and labels.
+pub const MNIST_TRAIN: c_int = 0;
+/// Selects the testing images and labels.
+pub const MNIST_TEST: c_int = 1;
+
+/// Size of one image in bytes, one byte per pixel.
+pub const MNIST_IMAGE_SIZE: usize = 28 * 28;
+
+fn to_split(split: c_int) -> Option<Split> {
+    match split {
+        MNIST_TRAIN => Some(Split::Train),
+        MNIST_TEST => Some(Split::Test),
+        _ => None,
+    }
+}
+
+/// Load the dataset from the directory `path`. Returns null if the files cannot be read.
+///
+/// The returned dataset must be released with `mnist_free`.
+///
+/// # Safety
+///
+/// `path` must be a valid, nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn mnist_load(path: *const c_char) -> *mut Mnist {
+    if path.is_null() {
+        return ptr::null_mut();
+    }
+    let Ok(path) = CStr::from_ptr(path).to_str() else {
+        return ptr::null_mut();
+    };
+
+    // Load as `Mnist::new` does, but with errors returned rather than panicked on, so nothing
+    // unwinds into C or reaches the host's stderr.
+    match Mnist::load(&LoadOptions::new().cache_dir(Path::new(path))) {
+        Ok(mnist) => Box::into_raw(Box::new(mnist)),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Number of samples in the split, or zero if `split` is neither `MNIST_TRAIN` nor `MNIST_TEST`.
+///
+/// # Safety
+///
+/// `mnist` must have been returned by `mnist_load` and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn mnist_len(mnist: *const Mnist, split: c_int) -> usize {
+    match (mnist.as_ref(), to_split(split)) {
+        (Some(mnist), Some(split)) => mnist.labels(split).len(),
+        _ => 0,
+    }
+}
+
+/// Pointer to the `MNIST_IMAGE_SIZE` row-major pixels of an image, or null if `index` is out of
+/// range. The pixels stay valid until the dataset is freed.
+///
+/// # Safety
+///
+/// `mnist` must have been returned by `mnist_load` and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn mnist_image_ptr(
+    mnist: *const Mnist,
+    split: c_int,
+    index: usize,
+) -> *const u8 {
+    match (mnist.as_ref(), to_split(split)) {
+        (Some(mnist), Some(split)) => mnist
+            .images(split)
+            .get(index)
+            .map_or(ptr::null(), |image| image.as_ptr()),
+        _ => ptr::null(),
+    }
+}
+
+/// Label of an image, or -1 if `index` is out of range.
+///
+/// # Safety
+///
+/// `mnist` must have been returned by `mnist_load` and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn mnist_label(mnist: *const Mnist, split: c_int, index: usize) -> c_int {
+    match (mnist.as_ref(), to_split(split)) {
+        (Some(mnist), Some(split)) => mnist
+            .labels(split)
+            .get(index)
+            .map_or(-1, |&label| c_int::from(label)),
+        _ => -1,
+    }
+}
+
+/// Release a dataset returned by `mnist_load`. Does nothing if `mnist` is null.
+///
+/// # Safety
+///
+/// `mnist` must have been returned by `mnist_load` and not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn mnist_free(mnist: *mut Mnist) {
+    if !mnist.is_null() {
+        drop(Box::from_raw(mnist));
+    }
+}
@@ -0,0 +1,160 @@
+//! The label-first CSV layout of the Kaggle `mnist_train.csv` and `mnist_test.csv` files.
+
+use crate::{check_labels, ImageSet, Mnist, IMAGE_COLUMNS, IMAGE_ROWS};
+use std::fs;
+use std::io;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+const TRAIN_CSV_FILENAME: &str = "mnist_train.csv";
+const TEST_CSV_FILENAME: &str = "mnist_test.csv";
+
+impl Mnist {
+    /// Load the dataset from a pair of CSV files in the Kaggle layout: one sample per line, the
+    /// label followed by the 784 pixels in row-major order. A leading header line is skipped.
+    ///
+    /// Files are read a line at a time, so memory use stays close to the size of the parsed
+    /// dataset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either file cannot be read, if a line does not hold a label and 784
+    /// pixels, all between 0 and 255, or if a label is not a digit.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::Path;
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::from_csv(Path::new("mnist_train.csv"), Path::new("mnist_test.csv"))?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn from_csv(train: &Path, test: &Path) -> io::Result<Mnist> {
+        let (train_data, train_labels) = read_csv(train)?;
+        let (test_data, test_labels) = read_csv(test)?;
+        Ok(Mnist {
+            train_data,
+            test_data,
+            train_labels,
+            test_labels,
+        })
+    }
+
+    /// Write the dataset to `dir/mnist_train.csv` and `dir/mnist_test.csv` in the layout read by
+    /// [`Mnist::from_csv`], with a `label,1x1,1x2,...,28x28` header line. `dir` is created if it
+    /// does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or either file cannot be written.
+    pub fn to_csv(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        write_csv(
+            &dir.join(TRAIN_CSV_FILENAME),
+            &self.train_data,
+            &self.train_labels,
+        )?;
+        write_csv(
+            &dir.join(TEST_CSV_FILENAME),
+            &self.test_data,
+            &self.test_labels,
+        )
+    }
+}
+
+impl ImageSet {
+    /// Write the set to a CSV file at `path` in the layout written by [`Mnist::to_csv`], header
+    /// line included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn to_csv(&self, path: &Path) -> io::Result<()> {
+        write_csv(path, &self.images, &self.labels)
+    }
+}
+
+fn read_csv(path: &Path) -> io::Result<(Vec<[u8; IMAGE_ROWS * IMAGE_COLUMNS]>, Vec<u8>)> {
+    let mut reader = io::BufReader::new(fs::File::open(path)?);
+    let mut images = Vec::new();
+    let mut labels = Vec::new();
+
+    // Reuse one line buffer rather than allocating a string per sample.
+    let mut line = String::new();
+    let mut line_number = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_number += 1;
+        let line = line.trim_end();
+        if line.is_empty() || (line_number == 1 && line.starts_with("label")) {
+            continue;
+        }
+
+        let invalid = |reason: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}, line {line_number}: {reason}", path.display()),
+            )
+        };
+        let mut fields = line.split(',').map(|field| {
+            field
+                .trim()
+                .parse::<u8>()
+                .map_err(|err| invalid(&err.to_string()))
+        });
+        let label = fields.next().ok_or_else(|| invalid("missing label"))??;
+        let mut image = [0; IMAGE_ROWS * IMAGE_COLUMNS];
+        let mut count = 0;
+        for field in fields {
+            let pixel = field?;
+            if count < image.len() {
+                image[count] = pixel;
+            }
+            count += 1;
+        }
+        if count != image.len() {
+            return Err(invalid(&format!(
+                "expected {} pixels, found {count}",
+                image.len()
+            )));
+        }
+
+        images.push(image);
+        labels.push(label);
+    }
+    check_labels(&labels).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {err}", path.display()),
+        )
+    })?;
+    Ok((images, labels))
+}
+
+fn write_csv(
+    path: &Path,
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+) -> io::Result<()> {
+    let mut writer = io::BufWriter::new(fs::File::create(path)?);
+
+    write!(writer, "label")?;
+    for row in 1..=IMAGE_ROWS {
+        for column in 1..=IMAGE_COLUMNS {
+            write!(writer, ",{row}x{column}")?;
+        }
+    }
+    writeln!(writer)?;
+
+    for (image, label) in images.iter().zip(labels) {
+        write!(writer, "{label}")?;
+        for pixel in image {
+            write!(writer, ",{pixel}")?;
+        }
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
@@ -0,0 +1,168 @@
+//! Animated GIFs of samples, for eyeballing sequences of images and augmentation pipelines.
+
+use crate::interop::image::gray_image;
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::image::codecs::gif::{GifEncoder, Repeat};
+use ::image::{Delay, DynamicImage, Frame, ImageResult};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+impl Mnist {
+    /// Save the images at `indices` of the split to `path` as a looping GIF animation, one frame
+    /// per image, shown at `fps` frames per second.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is zero or an index is out of bounds.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let first_twenty: Vec<usize> = (0..20).collect();
+    /// mnist.export_gif(Path::new("digits.gif"), Split::Train, &first_twenty, 4)?;
+    /// # Ok::<(), image::ImageError>(())
+    /// ```
+    pub fn export_gif(
+        &self,
+        path: &Path,
+        split: Split,
+        indices: &[usize],
+        fps: u32,
+    ) -> ImageResult<()> {
+        let images = self.images(split);
+        write_gif(path, indices.iter().map(|&i| images[i]), fps)
+    }
+
+    /// Save `frames` successive augmentations of image `index` of the split to `path` as a
+    /// looping GIF animation, to check what an augmentation pipeline does to a digit.
+    ///
+    /// Every frame starts from the original image with its pixels scaled to `[0, 1]`, as a
+    /// [`DataLoader`](crate::DataLoader) transform receives them, so the same closure can be
+    /// passed to both. Out-of-range results are clamped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is zero or `index` is out of bounds.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rand::Rng;
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let noise = |pixels: &mut [f32]| {
+    ///     let mut rng = rand::thread_rng();
+    ///     pixels.iter_mut().for_each(|pixel| *pixel += rng.gen_range(-0.2..0.2));
+    /// };
+    /// mnist.export_augmentation_gif(Path::new("noise.gif"), Split::Train, 0, 16, 4, noise)?;
+    /// # Ok::<(), image::ImageError>(())
+    /// ```
+    pub fn export_augmentation_gif(
+        &self,
+        path: &Path,
+        split: Split,
+        index: usize,
+        frames: usize,
+        fps: u32,
+        augment: impl FnMut(&mut [f32]),
+    ) -> ImageResult<()> {
+        write_augmentations(path, &self.images(split)[index], frames, fps, augment)
+    }
+}
+
+impl ImageSet {
+    /// Save the images at `indices` of the set to `path` as a looping GIF animation, as
+    /// [`Mnist::export_gif`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is zero or an index is out of bounds.
+    pub fn export_gif(&self, path: &Path, indices: &[usize], fps: u32) -> ImageResult<()> {
+        write_gif(path, indices.iter().map(|&i| self.images[i]), fps)
+    }
+
+    /// Save `frames` successive augmentations of image `index` of the set to `path` as a looping
+    /// GIF animation, as [`Mnist::export_augmentation_gif`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is zero or `index` is out of bounds.
+    pub fn export_augmentation_gif(
+        &self,
+        path: &Path,
+        index: usize,
+        frames: usize,
+        fps: u32,
+        augment: impl FnMut(&mut [f32]),
+    ) -> ImageResult<()> {
+        write_augmentations(path, &self.images[index], frames, fps, augment)
+    }
+}
+
+fn write_augmentations(
+    path: &Path,
+    image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS],
+    frames: usize,
+    fps: u32,
+    mut augment: impl FnMut(&mut [f32]),
+) -> ImageResult<()> {
+    let augmented = (0..frames).map(|_| {
+        let mut pixels: Vec<f32> = image
+            .iter()
+            .map(|&pixel| f32::from(pixel) / 255.0)
+            .collect();
+        augment(&mut pixels);
+
+        let mut frame = [0; IMAGE_ROWS * IMAGE_COLUMNS];
+        for (out, pixel) in frame.iter_mut().zip(pixels) {
+            *out = unit_to_byte(pixel);
+        }
+        frame
+    });
+    write_gif(path, augmented, fps)
+}
+
+/// Write images to `path` as a GIF that loops forever.
+fn write_gif(
+    path: &Path,
+    images: impl Iterator<Item = [u8; IMAGE_ROWS * IMAGE_COLUMNS]>,
+    fps: u32,
+) -> ImageResult<()> {
+    assert!(fps > 0, "Frame rate must be at least one frame per second.");
+    let delay = Delay::from_numer_denom_ms(1000, fps);
+
+    let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+    encoder.set_repeat(Repeat::Infinite)?;
+    encoder.encode_frames(images.map(|image| {
+        let rgba = DynamicImage::ImageLuma8(gray_image(&image)).into_rgba8();
+        Frame::from_parts(rgba, 0, 0, delay)
+    }))
+}
+
+/// Convert a pixel in `[0, 1]` back to a byte, clamping values outside the range.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn unit_to_byte(pixel: f32) -> u8 {
+    // The clamp keeps the rounded value within `0..=255`, so the cast is exact.
+    (pixel.clamp(0.0, 1.0) * 255.0).round() as u8
+}
@@ -0,0 +1,57 @@
+//! HDF5 files with a group per split.
+
+use crate::{Mnist, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::hdf5::{File, Result};
+use std::path::Path;
+
+impl Mnist {
+    /// Write the dataset to an HDF5 file with `uint8` datasets `/train/images` (`N x 28 x 28`),
+    /// `/train/labels`, `/test/images` and `/test/labels`. Each image dataset also carries `rows`
+    /// and `columns` attributes.
+    ///
+    /// Needs the HDF5 library to be installed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.to_hdf5(Path::new("mnist.h5"))?;
+    /// # Ok::<(), hdf5::Error>(())
+    /// ```
+    pub fn to_hdf5(&self, path: &Path) -> Result<()> {
+        let file = File::create(path)?;
+        for (name, images, labels) in [
+            ("train", &self.train_data, &self.train_labels),
+            ("test", &self.test_data, &self.test_labels),
+        ] {
+            let group = file.create_group(name)?;
+
+            let dataset = group
+                .new_dataset::<u8>()
+                .shape([images.len(), IMAGE_ROWS, IMAGE_COLUMNS])
+                .create("images")?;
+            dataset.write_raw(images.as_flattened())?;
+            dataset
+                .new_attr::<usize>()
+                .create("rows")?
+                .write_scalar(&IMAGE_ROWS)?;
+            dataset
+                .new_attr::<usize>()
+                .create("columns")?
+                .write_scalar(&IMAGE_COLUMNS)?;
+
+            group
+                .new_dataset::<u8>()
+                .shape([labels.len()])
+                .create("labels")?
+                .write_raw(labels.as_slice())?;
+        }
+        file.close()
+    }
+}
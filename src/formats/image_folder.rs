@@ -0,0 +1,160 @@
+//! The folder-per-class image layout used by generic image-folder dataset loaders.
+
+use crate::interop::image::{from_gray_image, gray_image};
+use crate::source::join;
+use crate::{DataSource, ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES};
+use ::image::{ImageError, ImageResult};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// Extensions of the files picked up by `from_image_dir`; anything else is ignored.
+const IMAGE_EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+
+impl Mnist {
+    /// Build a dataset from `dir/train/<label>/*` and `dir/test/<label>/*` in `source`, the
+    /// layout written by [`Mnist::export_png`]. See [`ImageSet::from_image_dir`] for how each
+    /// tree is read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either tree cannot be read, as for [`ImageSet::from_image_dir`].
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::Path;
+    /// use rust_mnist::{LocalDir, Mnist};
+    ///
+    /// let digits = Mnist::from_image_dir(&LocalDir::new(Path::new("my_digits")), "")?;
+    /// # Ok::<(), image::ImageError>(())
+    /// ```
+    pub fn from_image_dir(source: &(impl DataSource + ?Sized), dir: &str) -> ImageResult<Mnist> {
+        let (train_data, train_labels) = read_image_dir(source, &join(dir, "train"))?;
+        let (test_data, test_labels) = read_image_dir(source, &join(dir, "test"))?;
+        Ok(Mnist {
+            train_data,
+            test_data,
+            train_labels,
+            test_labels,
+        })
+    }
+
+    /// Write every image of the split to `dir/<label>/<index>.png`, where `index` is the image's
+    /// position in the split. Directories are created as needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory or image cannot be written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.export_png(Path::new("mnist_png/train"), Split::Train)?;
+    /// mnist.export_png(Path::new("mnist_png/test"), Split::Test)?;
+    /// # Ok::<(), image::ImageError>(())
+    /// ```
+    pub fn export_png(&self, dir: &Path, split: Split) -> ImageResult<()> {
+        export_png(dir, self.images(split), self.labels(split))
+    }
+}
+
+impl ImageSet {
+    /// Build a set from a folder-per-class tree, `dir/<label>/*` in `source`, where every label
+    /// directory is named after its digit. `dir` is `""` for a tree at the top of the source.
+    /// The source must support [listing](DataSource::list).
+    ///
+    /// PNG and JPEG files are converted to grayscale and resized to 28x28; other files are
+    /// ignored. Samples are ordered by label, then by file name. MNIST digits are white on black,
+    /// so scans of dark ink on paper need inverting first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory or image cannot be read, or if a directory under `dir` is
+    /// not named after a digit.
+    pub fn from_image_dir(source: &(impl DataSource + ?Sized), dir: &str) -> ImageResult<ImageSet> {
+        let (images, labels) = read_image_dir(source, dir)?;
+        Ok(ImageSet { images, labels })
+    }
+
+    /// Write every image of the set to `dir/<label>/<index>.png`, where `index` is the image's
+    /// position in the set. Directories are created as needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory or image cannot be written.
+    pub fn export_png(&self, dir: &Path) -> ImageResult<()> {
+        export_png(dir, &self.images, &self.labels)
+    }
+}
+
+fn export_png(
+    dir: &Path,
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+) -> ImageResult<()> {
+    let mut created = [false; 256];
+    for (index, (image, &label)) in images.iter().zip(labels).enumerate() {
+        let class_dir = dir.join(label.to_string());
+        if !created[usize::from(label)] {
+            fs::create_dir_all(&class_dir)?;
+            created[usize::from(label)] = true;
+        }
+        gray_image(image).save(class_dir.join(format!("{index}.png")))?;
+    }
+    Ok(())
+}
+
+fn read_image_dir(
+    source: &(impl DataSource + ?Sized),
+    dir: &str,
+) -> ImageResult<(Vec<[u8; IMAGE_ROWS * IMAGE_COLUMNS]>, Vec<u8>)> {
+    let mut classes = Vec::new();
+    for entry in source.list(dir)? {
+        // Only directories, listed with a trailing slash, hold samples.
+        let Some(name) = entry.strip_suffix('/') else {
+            continue;
+        };
+        // Everything downstream indexes per-class tables by label, so only digits name classes.
+        let label = name
+            .parse::<u8>()
+            .ok()
+            .filter(|&label| usize::from(label) < NUM_CLASSES)
+            .ok_or_else(|| {
+                ImageError::IoError(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} is not named after a digit.", join(dir, name)),
+                ))
+            })?;
+        classes.push((label, join(dir, name)));
+    }
+    classes.sort();
+
+    let mut images = Vec::new();
+    let mut labels = Vec::new();
+    for (label, class_dir) in classes {
+        let mut files = source.list(&class_dir)?;
+        files.retain(|name| {
+            Path::new(name)
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .is_some_and(|extension| {
+                    IMAGE_EXTENSIONS
+                        .iter()
+                        .any(|known| extension.eq_ignore_ascii_case(known))
+                })
+        });
+        files.sort();
+
+        for file in files {
+            let bytes = source.read(&join(&class_dir, &file))?;
+            images.push(from_gray_image(
+                &::image::load_from_memory(&bytes)?.to_luma8(),
+            ));
+            labels.push(label);
+        }
+    }
+    Ok((images, labels))
+}
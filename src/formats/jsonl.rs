@@ -0,0 +1,75 @@
+//! JSON Lines files with one object per sample and base64-encoded pixels.
+
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl Mnist {
+    /// Write the split to a JSON Lines file, one `{"label": 7, "image_b64": "..."}` object per
+    /// line. `image_b64` holds the 784 row-major pixel bytes in standard, padded base64.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.to_jsonl(Path::new("mnist_test.jsonl"), Split::Test)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn to_jsonl(&self, path: &Path, split: Split) -> io::Result<()> {
+        write_jsonl(path, self.images(split), self.labels(split))
+    }
+}
+
+impl ImageSet {
+    /// Write the set to a JSON Lines file, laid out as by [`Mnist::to_jsonl`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn to_jsonl(&self, path: &Path) -> io::Result<()> {
+        write_jsonl(path, &self.images, &self.labels)
+    }
+}
+
+fn write_jsonl(
+    path: &Path,
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+) -> io::Result<()> {
+    let mut writer = io::BufWriter::new(fs::File::create(path)?);
+    let mut encoded = String::new();
+    for (image, label) in images.iter().zip(labels) {
+        encoded.clear();
+        base64(image, &mut encoded);
+        writeln!(writer, r#"{{"label": {label}, "image_b64": "{encoded}"}}"#)?;
+    }
+    writer.flush()
+}
+
+/// Append `bytes` to `out` in standard base64 with padding.
+pub(crate) fn base64(bytes: &[u8], out: &mut String) {
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | u32::from(byte) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (group >> (18 - 6 * i)) & 0x3f;
+                out.push(char::from(BASE64_ALPHABET[index as usize]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! LMDB databases of Caffe `Datum` records, as written by Caffe's `convert_mnist_data` tool.
+
+use super::protobuf::{write_field, write_varint_field};
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::heed::types::Bytes;
+use ::heed::{Database, EnvOpenOptions, Result};
+use std::fs;
+use std::path::Path;
+
+// Largest size the database may grow to. LMDB reserves this much address space, but only uses
+// disk space for what is written.
+const MAP_SIZE: usize = 1 << 30;
+
+impl Mnist {
+    /// Write the split to an LMDB database in the directory `path`, which is created if it does
+    /// not exist. Keys are sample indices formatted as `%08d` and values are serialized Caffe
+    /// `Datum`s holding one channel of 28x28 pixels and the label, matching the databases produced
+    /// by Caffe's `convert_mnist_data` tool for the `LeNet` examples.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be created or written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.to_lmdb(Path::new("mnist_train_lmdb"), Split::Train)?;
+    /// mnist.to_lmdb(Path::new("mnist_test_lmdb"), Split::Test)?;
+    /// # Ok::<(), heed::Error>(())
+    /// ```
+    pub fn to_lmdb(&self, path: &Path, split: Split) -> Result<()> {
+        write_lmdb(path, self.images(split), self.labels(split))
+    }
+}
+
+impl ImageSet {
+    /// Write the set to an LMDB database of Caffe `Datum`s in the directory `path`, laid out as
+    /// by [`Mnist::to_lmdb`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be created or written.
+    pub fn to_lmdb(&self, path: &Path) -> Result<()> {
+        write_lmdb(path, &self.images, &self.labels)
+    }
+}
+
+fn write_lmdb(
+    path: &Path,
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+) -> Result<()> {
+    fs::create_dir_all(path)?;
+
+    // SAFETY: the environment is only used within this function, and the database files are not
+    // expected to be modified by anything else while it is being written.
+    let env = unsafe { EnvOpenOptions::new().map_size(MAP_SIZE).open(path)? };
+    let mut transaction = env.write_txn()?;
+    let database: Database<Bytes, Bytes> = env.create_database(&mut transaction, None)?;
+    for (index, (image, &label)) in images.iter().zip(labels).enumerate() {
+        let key = format!("{index:08}");
+        database.put(&mut transaction, key.as_bytes(), &datum(image, label))?;
+    }
+    transaction.commit()
+}
+
+/// Encode a sample as a serialized Caffe `Datum` protocol buffer.
+fn datum(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], label: u8) -> Vec<u8> {
+    // Datum { channels = 1, height = 2, width = 3, data = 4, label = 5 }
+    let mut datum = Vec::with_capacity(image.len() + 16);
+    write_varint_field(&mut datum, 1, 1);
+    write_varint_field(&mut datum, 2, IMAGE_ROWS as u64);
+    write_varint_field(&mut datum, 3, IMAGE_COLUMNS as u64);
+    write_field(&mut datum, 4, image);
+    write_varint_field(&mut datum, 5, u64::from(label));
+    datum
+}
@@ -0,0 +1,29 @@
+//! Reading and writing the dataset in file formats other than IDX.
+
+mod csv;
+#[cfg(feature = "image")]
+mod gif;
+#[cfg(feature = "hdf5")]
+mod hdf5;
+#[cfg(feature = "image")]
+mod image_folder;
+pub(crate) mod jsonl;
+#[cfg(feature = "lmdb")]
+mod lmdb;
+#[cfg(feature = "image")]
+mod montage;
+#[cfg(feature = "msgpack")]
+mod msgpack;
+#[cfg(feature = "npz")]
+mod npz;
+#[cfg(feature = "parquet")]
+mod parquet;
+mod protobuf;
+pub(crate) mod shards;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod tfrecord;
+#[cfg(feature = "webdataset")]
+mod webdataset;
+#[cfg(feature = "zstd")]
+pub(crate) mod zstd;
@@ -0,0 +1,171 @@
+//! Contact sheets: several samples tiled into one labelled grid image.
+
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES};
+use ::image::{GrayImage, ImageResult, Luma};
+use std::convert::TryFrom;
+use std::path::Path;
+
+// Tile geometry, in pixels. Each tile is an image with its label in a strip underneath, and tiles
+// are separated by a gap.
+const GAP: u32 = 2;
+const LABEL_HEIGHT: u32 = 9;
+const GAP_SHADE: u8 = 64;
+
+// 3x5 bitmaps of the digits, one row per byte with the leftmost pixel in bit 2.
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+impl Mnist {
+    /// Tile the images at `indices` of the split into a grid `cols` tiles wide, each labelled
+    /// underneath, and save it to `path`. The format follows the file extension, e.g. `.png`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be saved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cols` is zero or an index is out of bounds.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let first_hundred: Vec<usize> = (0..100).collect();
+    /// mnist.export_montage(Path::new("montage.png"), Split::Train, &first_hundred, 10)?;
+    /// # Ok::<(), image::ImageError>(())
+    /// ```
+    pub fn export_montage(
+        &self,
+        path: &Path,
+        split: Split,
+        indices: &[usize],
+        cols: usize,
+    ) -> ImageResult<()> {
+        let images = self.images(split);
+        let labels = self.labels(split);
+        let samples: Vec<_> = indices.iter().map(|&i| (&images[i], labels[i])).collect();
+        montage(&samples, cols).save(path)
+    }
+
+    /// Save the split's [class mean images](Mnist::class_mean_images) to `path` as a labelled
+    /// strip, from 0 to 9. The format follows the file extension, e.g. `.png`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be saved.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.export_class_means(Path::new("means.png"), Split::Train)?;
+    /// # Ok::<(), image::ImageError>(())
+    /// ```
+    pub fn export_class_means(&self, path: &Path, split: Split) -> ImageResult<()> {
+        class_mean_strip(&self.class_mean_images(split)).save(path)
+    }
+}
+
+impl ImageSet {
+    /// Tile the images at `indices` of the set into a labelled grid `cols` tiles wide and save it
+    /// to `path`, as [`Mnist::export_montage`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be saved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cols` is zero or an index is out of bounds.
+    pub fn export_montage(&self, path: &Path, indices: &[usize], cols: usize) -> ImageResult<()> {
+        let samples: Vec<_> = indices
+            .iter()
+            .map(|&i| (&self.images[i], self.labels[i]))
+            .collect();
+        montage(&samples, cols).save(path)
+    }
+
+    /// Save the set's [class mean images](ImageSet::class_mean_images) to `path` as a labelled
+    /// strip, as [`Mnist::export_class_means`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be saved.
+    pub fn export_class_means(&self, path: &Path) -> ImageResult<()> {
+        class_mean_strip(&self.class_mean_images()).save(path)
+    }
+}
+
+/// Tile the mean image of every class into a single labelled row.
+fn class_mean_strip(means: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]; NUM_CLASSES]) -> GrayImage {
+    let samples: Vec<_> = means.iter().zip(0..).collect();
+    montage(&samples, NUM_CLASSES)
+}
+
+/// Tile samples into a grid `cols` tiles wide, each with its label drawn underneath.
+pub(crate) fn montage(
+    samples: &[(&[u8; IMAGE_ROWS * IMAGE_COLUMNS], u8)],
+    cols: usize,
+) -> GrayImage {
+    assert!(cols > 0, "Montage must be at least one column wide.");
+    let rows = samples.len().div_ceil(cols);
+    let cols = cols.min(samples.len());
+
+    let tile_width = u32::try_from(IMAGE_COLUMNS).unwrap();
+    let image_height = u32::try_from(IMAGE_ROWS).unwrap();
+    let tile_height = image_height + LABEL_HEIGHT;
+    let span = |tiles: usize, size: u32| u32::try_from(tiles).unwrap() * (size + GAP) + GAP;
+    let mut sheet = GrayImage::from_pixel(
+        span(cols, tile_width),
+        span(rows, tile_height),
+        Luma([GAP_SHADE]),
+    );
+
+    for (tile, (image, label)) in samples.iter().enumerate() {
+        let left = GAP + u32::try_from(tile % cols).unwrap() * (tile_width + GAP);
+        let top = GAP + u32::try_from(tile / cols).unwrap() * (tile_height + GAP);
+
+        for (y, row) in (0..).zip(image.chunks(IMAGE_COLUMNS)) {
+            for (x, &pixel) in (0..).zip(row) {
+                sheet.put_pixel(left + x, top + y, Luma([pixel]));
+            }
+        }
+
+        // Black label strip, with the label centred in white.
+        for y in 0..LABEL_HEIGHT {
+            for x in 0..tile_width {
+                sheet.put_pixel(left + x, top + image_height + y, Luma([0]));
+            }
+        }
+        let text = label.to_string();
+        let text_width = u32::try_from(text.len()).unwrap() * 4 - 1;
+        let text_left = left + (tile_width - text_width) / 2;
+        let text_top = top + image_height + (LABEL_HEIGHT - 5) / 2;
+        for (position, digit) in (0..).zip(text.bytes()) {
+            let glyph = DIGITS[usize::from(digit - b'0')];
+            for (y, bits) in (0..).zip(glyph) {
+                for x in 0..3 {
+                    if bits & (0b100 >> x) != 0 {
+                        sheet.put_pixel(text_left + position * 4 + x, text_top + y, Luma([255]));
+                    }
+                }
+            }
+        }
+    }
+    sheet
+}
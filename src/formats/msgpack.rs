@@ -0,0 +1,83 @@
+//! `MessagePack` encoding of datasets and subsets, readable from any language with a
+//! `MessagePack` library.
+//!
+//! Structs are written as maps keyed by field name, each image as a 784-byte binary value and
+//! labels as arrays of integers, so a decoded `Mnist` looks like `{"train_data": [bytes, ...],
+//! "test_data": [bytes, ...], "train_labels": [int, ...], "test_labels": [int, ...]}`.
+
+use crate::{check_labels, ImageSet, Mnist};
+use ::serde::de::DeserializeOwned;
+use ::serde::Serialize;
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+impl Mnist {
+    /// Write the dataset to a `MessagePack` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.to_msgpack(Path::new("mnist.msgpack"))?;
+    /// let mnist = Mnist::from_msgpack(Path::new("mnist.msgpack"))?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn to_msgpack(&self, path: &Path) -> io::Result<()> {
+        write_msgpack(path, self)
+    }
+
+    /// Read a dataset written by [`Mnist::to_msgpack`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not hold a dataset, or if a label is
+    /// not a digit.
+    pub fn from_msgpack(path: &Path) -> io::Result<Mnist> {
+        let mnist: Mnist = read_msgpack(path)?;
+        check_labels(&mnist.train_labels)?;
+        check_labels(&mnist.test_labels)?;
+        Ok(mnist)
+    }
+}
+
+impl ImageSet {
+    /// Write the set to a `MessagePack` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn to_msgpack(&self, path: &Path) -> io::Result<()> {
+        write_msgpack(path, self)
+    }
+
+    /// Read a set written by [`ImageSet::to_msgpack`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not hold a set, or if a label is not
+    /// a digit.
+    pub fn from_msgpack(path: &Path) -> io::Result<ImageSet> {
+        let set: ImageSet = read_msgpack(path)?;
+        check_labels(&set.labels)?;
+        Ok(set)
+    }
+}
+
+fn write_msgpack(path: &Path, value: &impl Serialize) -> io::Result<()> {
+    let mut writer = io::BufWriter::new(fs::File::create(path)?);
+    ::rmp_serde::encode::write_named(&mut writer, value).map_err(io::Error::other)?;
+    writer.flush()
+}
+
+fn read_msgpack<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let reader = io::BufReader::new(fs::File::open(path)?);
+    ::rmp_serde::from_read(reader).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
@@ -0,0 +1,242 @@
+//! `NumPy` `.npz` archives laid out like the `mnist.npz` file cached by Keras.
+
+use crate::{check_labels, DataSource, Mnist, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::zip::write::SimpleFileOptions;
+use ::zip::{CompressionMethod, ZipArchive, ZipWriter};
+use std::convert::TryFrom;
+use std::fs;
+use std::io;
+use std::io::{Read, Write};
+use std::path::Path;
+
+// `.npy` files start with this magic string, then the format version.
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+
+impl Mnist {
+    /// Load the dataset from the `.npz` archive called `name` in `source`, holding `uint8` arrays
+    /// named `x_train`, `y_train`, `x_test` and `y_test`, such as the
+    /// `~/.keras/datasets/mnist.npz` file downloaded by Keras. Image arrays may be shaped
+    /// `N x 28 x 28` or `N x 784`. The archive is read whole before it is unpacked.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, if an array is missing, has the wrong type or
+    /// shape or is larger than its file, or if a label is not a digit.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::Path;
+    /// use rust_mnist::{LocalDir, Mnist};
+    ///
+    /// let keras = LocalDir::new(Path::new(".keras/datasets"));
+    /// let mnist = Mnist::from_npz(&keras, "mnist.npz")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn from_npz(source: &(impl DataSource + ?Sized), name: &str) -> io::Result<Mnist> {
+        let mut archive = ZipArchive::new(io::Cursor::new(source.read(name)?))?;
+        let mut read_split = |x: &str, y: &str| -> io::Result<_> {
+            let mut file = archive.by_name(&format!("{x}.npy"))?;
+            let size = file.size();
+            let images = read_images(&mut file, size)?;
+            drop(file);
+            let mut file = archive.by_name(&format!("{y}.npy"))?;
+            let size = file.size();
+            let labels = read_labels(&mut file, size)?;
+            drop(file);
+            if images.len() == labels.len() {
+                Ok((images, labels))
+            } else {
+                Err(invalid_data(format!(
+                    "{} images in {x} but {} labels in {y}.",
+                    images.len(),
+                    labels.len()
+                )))
+            }
+        };
+        let (train_data, train_labels) = read_split("x_train", "y_train")?;
+        let (test_data, test_labels) = read_split("x_test", "y_test")?;
+        Ok(Mnist {
+            train_data,
+            test_data,
+            train_labels,
+            test_labels,
+        })
+    }
+
+    /// Write the dataset to a compressed `.npz` archive holding the `uint8` arrays `x_train`
+    /// (`N x 28 x 28`), `y_train`, `x_test` and `y_test`, as read by `numpy.load` and expected by
+    /// code written against `keras.datasets.mnist`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.to_npz(Path::new("mnist.npz"))?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn to_npz(&self, path: &Path) -> io::Result<()> {
+        let mut archive = ZipWriter::new(io::BufWriter::new(fs::File::create(path)?));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let arrays: [(&str, &[usize], &[u8]); 4] = [
+            (
+                "x_train",
+                &[self.train_data.len(), IMAGE_ROWS, IMAGE_COLUMNS],
+                self.train_data.as_flattened(),
+            ),
+            ("y_train", &[self.train_labels.len()], &self.train_labels),
+            (
+                "x_test",
+                &[self.test_data.len(), IMAGE_ROWS, IMAGE_COLUMNS],
+                self.test_data.as_flattened(),
+            ),
+            ("y_test", &[self.test_labels.len()], &self.test_labels),
+        ];
+        for (name, shape, data) in arrays {
+            archive.start_file(format!("{name}.npy"), options)?;
+            write_npy(&mut archive, shape, data)?;
+        }
+        archive.finish()?.flush()
+    }
+}
+
+/// Write a C-ordered `uint8` array in the `.npy` format, version 1.0.
+fn write_npy(writer: &mut impl Write, shape: &[usize], data: &[u8]) -> io::Result<()> {
+    // A one-dimensional shape needs a trailing comma to be a Python tuple.
+    let dimensions: Vec<String> = shape.iter().map(ToString::to_string).collect();
+    let shape = match dimensions.as_slice() {
+        [length] => format!("({length},)"),
+        _ => format!("({})", dimensions.join(", ")),
+    };
+    let mut header = format!("{{'descr': '|u1', 'fortran_order': False, 'shape': {shape}, }}");
+
+    // Pad with spaces so that the data starts on a 64-byte boundary, ending in a newline.
+    let preamble = NPY_MAGIC.len() + 2 + 2;
+    let padding = 63 - (preamble + header.len()) % 64;
+    header.extend(std::iter::repeat_n(' ', padding));
+    header.push('\n');
+    let header_len = u16::try_from(header.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Array shape is too long."))?;
+
+    writer.write_all(NPY_MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&header_len.to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    writer.write_all(data)
+}
+
+/// Read a `.npy` file of `size` bytes holding images.
+fn read_images(
+    reader: &mut impl Read,
+    size: u64,
+) -> io::Result<Vec<[u8; IMAGE_ROWS * IMAGE_COLUMNS]>> {
+    let shape = read_npy_header(reader, size)?;
+    let len = match *shape.as_slice() {
+        [len, IMAGE_ROWS, IMAGE_COLUMNS] => len,
+        [len, pixels] if pixels == IMAGE_ROWS * IMAGE_COLUMNS => len,
+        _ => {
+            return Err(invalid_data(format!(
+                "Unexpected image array shape {shape:?}."
+            )))
+        }
+    };
+    check_fits(&shape, len.checked_mul(IMAGE_ROWS * IMAGE_COLUMNS), size)?;
+    let mut images = vec![[0; IMAGE_ROWS * IMAGE_COLUMNS]; len];
+    reader.read_exact(images.as_flattened_mut())?;
+    Ok(images)
+}
+
+/// Read a `.npy` file of `size` bytes holding labels.
+fn read_labels(reader: &mut impl Read, size: u64) -> io::Result<Vec<u8>> {
+    let shape = read_npy_header(reader, size)?;
+    let &[len] = shape.as_slice() else {
+        return Err(invalid_data(format!(
+            "Unexpected label array shape {shape:?}."
+        )));
+    };
+    check_fits(&shape, Some(len), size)?;
+    let mut labels = vec![0; len];
+    reader.read_exact(&mut labels)?;
+    check_labels(&labels)?;
+    Ok(labels)
+}
+
+/// Check that an array of `bytes` bytes fits in its file of `size` bytes before allocating it,
+/// so a corrupt header cannot ask for more memory than the archive holds.
+fn check_fits(shape: &[usize], bytes: Option<usize>, size: u64) -> io::Result<()> {
+    match bytes.and_then(|bytes| u64::try_from(bytes).ok()) {
+        Some(bytes) if bytes <= size => Ok(()),
+        _ => Err(invalid_data(format!(
+            "Array of shape {shape:?} does not fit in its {size}-byte file."
+        ))),
+    }
+}
+
+/// Read the header of a `.npy` file of `size` bytes holding a C-ordered `uint8` array, returning
+/// its shape.
+fn read_npy_header(reader: &mut impl Read, size: u64) -> io::Result<Vec<usize>> {
+    let mut preamble = [0; 8];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..NPY_MAGIC.len()] != NPY_MAGIC {
+        return Err(invalid_data("Not a .npy file.".to_string()));
+    }
+
+    // Version 1 stores the header length in two bytes, later versions in four.
+    let header_len = if preamble[NPY_MAGIC.len()] == 1 {
+        let mut len = [0; 2];
+        reader.read_exact(&mut len)?;
+        usize::from(u16::from_le_bytes(len))
+    } else {
+        let mut len = [0; 4];
+        reader.read_exact(&mut len)?;
+        usize::try_from(u32::from_le_bytes(len)).unwrap()
+    };
+    if u64::try_from(header_len).map_or(true, |header_len| header_len > size) {
+        return Err(invalid_data(format!(
+            "Array header of {header_len} bytes does not fit in its {size}-byte file."
+        )));
+    }
+    let mut header = vec![0; header_len];
+    reader.read_exact(&mut header)?;
+    let header = String::from_utf8_lossy(&header);
+
+    let descr = header_value(&header, "descr")
+        .and_then(|value| value.split('\'').nth(1))
+        .unwrap_or_default();
+    if !["|u1", "u1", "<u1", ">u1"].contains(&descr) {
+        return Err(invalid_data(format!("Unsupported array type \"{descr}\".")));
+    }
+    if !header_value(&header, "fortran_order").is_some_and(|value| value.starts_with("False")) {
+        return Err(invalid_data(
+            "Fortran-ordered arrays are not supported.".to_string(),
+        ));
+    }
+    header_value(&header, "shape")
+        .and_then(|value| value.strip_prefix('('))
+        .and_then(|value| value.split(')').next())
+        .ok_or_else(|| invalid_data("Array header has no shape.".to_string()))?
+        .split(',')
+        .map(str::trim)
+        .filter(|dimension| !dimension.is_empty())
+        .map(|dimension| {
+            dimension
+                .parse()
+                .map_err(|_| invalid_data(format!("Invalid array dimension \"{dimension}\".")))
+        })
+        .collect()
+}
+
+/// The text following `'key':` in a `.npy` header dictionary.
+fn header_value<'h>(header: &'h str, key: &str) -> Option<&'h str> {
+    let start = header.find(&format!("'{key}':"))? + key.len() + 3;
+    Some(header[start..].trim_start())
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
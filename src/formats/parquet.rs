@@ -0,0 +1,72 @@
+//! Parquet files with one row per sample.
+
+use crate::interop::arrow::record_batches;
+use crate::{arrow_schema, ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::parquet::arrow::ArrowWriter;
+use ::parquet::basic::Compression;
+use ::parquet::errors::Result;
+use ::parquet::file::properties::WriterProperties;
+use std::fs;
+use std::path::Path;
+
+// Samples per row group, and per record batch handed to the writer.
+const ROW_GROUP_SIZE: usize = 10_000;
+
+impl Mnist {
+    /// Write the split to a Snappy-compressed Parquet file using [`arrow_schema`]: a `label`
+    /// column and an `image` column of 784 row-major pixel bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.to_parquet(Path::new("mnist_train.parquet"), Split::Train)?;
+    /// # Ok::<(), parquet::errors::ParquetError>(())
+    /// ```
+    pub fn to_parquet(&self, path: &Path, split: Split) -> Result<()> {
+        write_parquet(path, self.images(split), self.labels(split))
+    }
+}
+
+impl ImageSet {
+    /// Write the set to a Snappy-compressed Parquet file using [`arrow_schema`]: a `label` column
+    /// and an `image` column of 784 row-major pixel bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn to_parquet(&self, path: &Path) -> Result<()> {
+        write_parquet(path, &self.images, &self.labels)
+    }
+}
+
+fn write_parquet(
+    path: &Path,
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+) -> Result<()> {
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .set_max_row_group_row_count(Some(ROW_GROUP_SIZE))
+        .build();
+    let mut writer =
+        ArrowWriter::try_new(fs::File::create(path)?, arrow_schema(), Some(properties))?;
+
+    // Convert a row group at a time rather than materializing the whole split in Arrow memory.
+    for (images, labels) in images
+        .chunks(ROW_GROUP_SIZE)
+        .zip(labels.chunks(ROW_GROUP_SIZE))
+    {
+        for batch in record_batches(images, labels, ROW_GROUP_SIZE)? {
+            writer.write(&batch)?;
+        }
+    }
+    writer.close()?;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+//! Just enough protocol buffer encoding to write the messages of `TFRecord` and Caffe files.
+
+const VARINT: u64 = 0;
+const LENGTH_DELIMITED: u64 = 2;
+
+/// Append a length-delimited field: a string, bytes, packed values or a nested message.
+pub(super) fn write_field(buffer: &mut Vec<u8>, field: u64, value: &[u8]) {
+    write_varint(buffer, field << 3 | LENGTH_DELIMITED);
+    write_varint(buffer, value.len() as u64);
+    buffer.extend_from_slice(value);
+}
+
+/// Append an integer or boolean field.
+#[cfg_attr(not(feature = "lmdb"), allow(dead_code))]
+pub(super) fn write_varint_field(buffer: &mut Vec<u8>, field: u64, value: u64) {
+    write_varint(buffer, field << 3 | VARINT);
+    write_varint(buffer, value);
+}
+
+#[allow(clippy::cast_possible_truncation)]
+pub(super) fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
@@ -0,0 +1,357 @@
+//! Splitting a set into IDX shards of bounded size, listed in a JSON index.
+
+use crate::json::{invalid_data, write_string, JsonReader};
+use crate::{
+    write_images, write_labels, IdxStream, ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS,
+};
+use std::convert::TryFrom;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::io::{BufReader, Write};
+use std::iter::FusedIterator;
+use std::path::{Path, PathBuf};
+use std::slice;
+
+/// Bytes of the IDX headers of a shard's image and label files.
+const HEADER_BYTES: u64 = 16 + 8;
+/// Bytes of one sample: its pixels and its label.
+const SAMPLE_BYTES: u64 = (IMAGE_ROWS * IMAGE_COLUMNS) as u64 + 1;
+
+/// The shards written by [`ImageSet::to_shards`] or [`Mnist::to_shards`], as listed in their
+/// index file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShardIndex {
+    /// Total number of samples over all shards.
+    pub len: usize,
+    /// Rows and columns of every image.
+    pub image_shape: [usize; 2],
+    /// The shards, in sample order.
+    pub shards: Vec<Shard>,
+}
+
+/// One shard: a pair of IDX files holding a run of consecutive samples.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shard {
+    /// Filename of the images file, relative to the index.
+    pub images: String,
+    /// Filename of the labels file, relative to the index.
+    pub labels: String,
+    /// Index of the shard's first sample in the whole set.
+    pub first: usize,
+    /// Number of samples in the shard.
+    pub len: usize,
+    /// Combined size of the two files in bytes.
+    pub bytes: u64,
+}
+
+impl ShardIndex {
+    /// Read the index written by [`ImageSet::to_shards`] or [`Mnist::to_shards`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a shard index.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::Path;
+    /// use rust_mnist::ShardIndex;
+    ///
+    /// let index = ShardIndex::load(Path::new("shards/train-index.json"))?;
+    /// for sample in index.samples(Path::new("shards")) {
+    ///     let (image, label) = sample?;
+    ///     // Train on the sample.
+    /// }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn load(path: &Path) -> io::Result<ShardIndex> {
+        ShardIndex::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Iterate over the samples of every shard in order, reading the shards from `dir`, the
+    /// directory of the index, one sample at a time with [`IdxStream`].
+    ///
+    /// Each shard is checked against its entry in the index as it is opened. The iterator ends
+    /// after the first error.
+    pub fn samples(&self, dir: &Path) -> ShardSamples<'_> {
+        ShardSamples {
+            dir: dir.to_path_buf(),
+            shards: self.shards.iter(),
+            stream: None,
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let [rows, columns] = self.image_shape;
+        let mut json = format!(
+            "{{\"len\": {}, \"image_shape\": [{rows}, {columns}], \"shards\": [\n",
+            self.len
+        );
+        for (i, shard) in self.shards.iter().enumerate() {
+            json.push_str("  {\"images\": ");
+            write_string(&mut json, &shard.images);
+            json.push_str(", \"labels\": ");
+            write_string(&mut json, &shard.labels);
+            let _ = write!(
+                json,
+                ", \"first\": {}, \"len\": {}, \"bytes\": {}}}",
+                shard.first, shard.len, shard.bytes
+            );
+            json.push_str(if i + 1 < self.shards.len() {
+                ",\n"
+            } else {
+                "\n"
+            });
+        }
+        json.push_str("]}\n");
+        json
+    }
+
+    fn from_json(text: &str) -> io::Result<ShardIndex> {
+        let mut json = JsonReader::new(text);
+        let (mut len, mut image_shape, mut shards) = (None, None, None);
+        json.object(|json, key| {
+            match key {
+                "len" => len = Some(json.number()?),
+                "image_shape" => match json.array(JsonReader::number)?[..] {
+                    [rows, columns] => image_shape = Some([rows, columns]),
+                    _ => return Err(invalid_data("Image shape must have two dimensions.")),
+                },
+                "shards" => shards = Some(json.array(read_shard)?),
+                _ => return Err(invalid_data(format!("Unexpected key \"{key}\"."))),
+            }
+            Ok(())
+        })?;
+        json.end()?;
+
+        match (len, image_shape, shards) {
+            (Some(len), Some(image_shape), Some(shards)) => Ok(ShardIndex {
+                len,
+                image_shape,
+                shards,
+            }),
+            _ => Err(invalid_data(
+                "Shard index needs \"len\", \"image_shape\" and \"shards\".",
+            )),
+        }
+    }
+}
+
+fn read_shard(json: &mut JsonReader<'_>) -> io::Result<Shard> {
+    let (mut images, mut labels, mut first, mut len, mut bytes) = (None, None, None, None, None);
+    json.object(|json, key| {
+        match key {
+            "images" => images = Some(json.string()?),
+            "labels" => labels = Some(json.string()?),
+            "first" => first = Some(json.number()?),
+            "len" => len = Some(json.number()?),
+            "bytes" => bytes = Some(json.number()?),
+            _ => return Err(invalid_data(format!("Unexpected key \"{key}\"."))),
+        }
+        Ok(())
+    })?;
+    match (images, labels, first, len, bytes) {
+        (Some(images), Some(labels), Some(first), Some(len), Some(bytes)) => Ok(Shard {
+            images,
+            labels,
+            first,
+            len,
+            bytes,
+        }),
+        _ => Err(invalid_data(
+            "Shard needs \"images\", \"labels\", \"first\", \"len\" and \"bytes\".",
+        )),
+    }
+}
+
+type ShardStream = IdxStream<BufReader<fs::File>, BufReader<fs::File>>;
+
+/// Iterator over the samples of a set of shards, created by [`ShardIndex::samples`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ShardSamples<'a> {
+    dir: PathBuf,
+    shards: slice::Iter<'a, Shard>,
+    /// The shard being read, or `None` before the first and after the last or an error.
+    stream: Option<ShardStream>,
+}
+
+impl ShardSamples<'_> {
+    fn open(&self, shard: &Shard) -> io::Result<ShardStream> {
+        let open = |name: &str| -> io::Result<_> {
+            fs::File::open(self.dir.join(name))
+                .map(BufReader::new)
+                .map_err(|err| io::Error::new(err.kind(), format!("Cannot open {name}: {err}")))
+        };
+        let stream = IdxStream::new(open(&shard.images)?, open(&shard.labels)?)?;
+        if stream.len() != shard.len {
+            return Err(invalid_data(format!(
+                "{} holds {} samples, but the index says {}.",
+                shard.images,
+                stream.len(),
+                shard.len
+            )));
+        }
+        Ok(stream)
+    }
+
+    /// Stop iterating, passing `err` through.
+    fn fail(&mut self, err: io::Error) -> io::Error {
+        self.stream = None;
+        self.shards = [].iter();
+        err
+    }
+}
+
+impl Iterator for ShardSamples<'_> {
+    type Item = io::Result<([u8; IMAGE_ROWS * IMAGE_COLUMNS], u8)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(stream) = &mut self.stream {
+                let mut image = [0; IMAGE_ROWS * IMAGE_COLUMNS];
+                match stream.next_into(&mut image) {
+                    Ok(Some(label)) => return Some(Ok((image, label))),
+                    Ok(None) => self.stream = None,
+                    Err(err) => return Some(Err(self.fail(err))),
+                }
+            }
+            let shard = self.shards.next()?;
+            match self.open(shard) {
+                Ok(stream) => self.stream = Some(stream),
+                Err(err) => return Some(Err(self.fail(err))),
+            }
+        }
+    }
+}
+
+impl FusedIterator for ShardSamples<'_> {}
+
+impl Mnist {
+    /// Write the split to `dir` as IDX shards of at most `max_shard_bytes` each, as
+    /// [`ImageSet::to_shards`] does, named after the split: `train-00000-images-idx3-ubyte`,
+    /// ..., listed in `train-index.json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a file cannot be written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_shard_bytes` is too small to hold a single sample.
+    pub fn to_shards(
+        &self,
+        dir: &Path,
+        split: Split,
+        max_shard_bytes: u64,
+    ) -> io::Result<ShardIndex> {
+        let name = match split {
+            Split::Train => "train",
+            Split::Test => "test",
+        };
+        write_shards(
+            dir,
+            name,
+            self.images(split),
+            self.labels(split),
+            max_shard_bytes,
+        )
+    }
+}
+
+impl ImageSet {
+    /// Write the set to `dir`, which is created if it does not exist, as shards of at most
+    /// `max_shard_bytes` each, so that large generated or augmented sets can be streamed a shard
+    /// at a time.
+    ///
+    /// Shard `i` is a pair of IDX files, `<name>-<i>-images-idx3-ubyte` and
+    /// `<name>-<i>-labels-idx1-ubyte` with `i` in five digits, each readable with
+    /// [`IdxStream`](crate::IdxStream). Every shard but the last holds as many samples as fit.
+    /// The shards are listed in `<name>-index.json`:
+    ///
+    /// ```json
+    /// {"len": 70000, "image_shape": [28, 28], "shards": [
+    ///   {"images": "infimnist-00000-images-idx3-ubyte", "labels": "infimnist-00000-labels-idx1-ubyte",
+    ///    "first": 0, "len": 1335, "bytes": 1047999},
+    ///   ...
+    /// ]}
+    /// ```
+    ///
+    /// [`ShardIndex::load`] reads the index back, and [`ShardIndex::samples`] walks the shards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a file cannot be written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_shard_bytes` is too small to hold a single sample.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let (train, _) = mnist.into_splits();
+    /// let index = train.to_shards(Path::new("shards"), "train", 1 << 20)?;
+    /// println!("{} shards", index.shards.len());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn to_shards(
+        &self,
+        dir: &Path,
+        name: &str,
+        max_shard_bytes: u64,
+    ) -> io::Result<ShardIndex> {
+        write_shards(dir, name, &self.images, &self.labels, max_shard_bytes)
+    }
+}
+
+fn write_shards(
+    dir: &Path,
+    name: &str,
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+    max_shard_bytes: u64,
+) -> io::Result<ShardIndex> {
+    let per_shard = max_shard_bytes.saturating_sub(HEADER_BYTES) / SAMPLE_BYTES;
+    assert!(
+        per_shard > 0,
+        "A shard of {} bytes cannot hold a single sample.",
+        max_shard_bytes
+    );
+    let per_shard = usize::try_from(per_shard).unwrap_or(usize::MAX);
+
+    fs::create_dir_all(dir)?;
+    let mut shards = Vec::new();
+    for (i, (images, labels)) in images
+        .chunks(per_shard)
+        .zip(labels.chunks(per_shard))
+        .enumerate()
+    {
+        let shard = Shard {
+            images: format!("{name}-{i:05}-images-idx3-ubyte"),
+            labels: format!("{name}-{i:05}-labels-idx1-ubyte"),
+            first: i * per_shard,
+            len: labels.len(),
+            bytes: HEADER_BYTES + labels.len() as u64 * SAMPLE_BYTES,
+        };
+        let mut writer = io::BufWriter::new(fs::File::create(dir.join(&shard.images))?);
+        write_images(&mut writer, images)?;
+        writer.flush()?;
+        let mut writer = io::BufWriter::new(fs::File::create(dir.join(&shard.labels))?);
+        write_labels(&mut writer, labels)?;
+        writer.flush()?;
+        shards.push(shard);
+    }
+
+    let index = ShardIndex {
+        len: labels.len(),
+        image_shape: [IMAGE_ROWS, IMAGE_COLUMNS],
+        shards,
+    };
+    fs::write(dir.join(format!("{name}-index.json")), index.to_json())?;
+    Ok(index)
+}
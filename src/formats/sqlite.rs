@@ -0,0 +1,52 @@
+//! `SQLite` databases with a row per sample.
+
+use crate::{Mnist, Split};
+use ::rusqlite::{params, Connection, Result};
+use std::path::Path;
+
+impl Mnist {
+    /// Write the dataset to a `samples(split, idx, label, image)` table in the `SQLite` database
+    /// at `path`, creating the database if needed. `split` is `'train'` or `'test'`, `idx` the
+    /// sample's position in its split and `image` a 784-byte BLOB of row-major pixels.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be opened or written, including when it already
+    /// has a `samples` table.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.to_sqlite(Path::new("mnist.db"))?;
+    /// # Ok::<(), rusqlite::Error>(())
+    /// ```
+    pub fn to_sqlite(&self, path: &Path) -> Result<()> {
+        let mut connection = Connection::open(path)?;
+        let transaction = connection.transaction()?;
+        transaction.execute(
+            "CREATE TABLE samples (
+                split TEXT NOT NULL,
+                idx INTEGER NOT NULL,
+                label INTEGER NOT NULL,
+                image BLOB NOT NULL,
+                PRIMARY KEY (split, idx)
+            )",
+            [],
+        )?;
+        {
+            let mut insert = transaction.prepare(
+                "INSERT INTO samples (split, idx, label, image) VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (split, name) in [(Split::Train, "train"), (Split::Test, "test")] {
+                let samples = self.images(split).iter().zip(self.labels(split));
+                for (index, (image, label)) in (0_i64..).zip(samples) {
+                    insert.execute(params![name, index, label, &image[..]])?;
+                }
+            }
+        }
+        transaction.commit()
+    }
+}
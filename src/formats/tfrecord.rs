@@ -0,0 +1,147 @@
+//! `TFRecord` files of `tf.train.Example` records, as read by `tf.data.TFRecordDataset`.
+
+use super::protobuf::{write_field, write_varint};
+use crate::{Mnist, IMAGE_COLUMNS, IMAGE_ROWS};
+use std::fs;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+impl Mnist {
+    /// Write both splits to `TFRecord` files in `dir`, one `tf.train.Example` per sample with the
+    /// features `image_raw` (784 row-major pixel bytes), `label`, `height`, `width` and `depth`
+    /// (all `int64`), the layout of TensorFlow's classic MNIST conversion script.
+    ///
+    /// With one shard, the files are `train.tfrecord` and `test.tfrecord`. With more, each split
+    /// is divided evenly across `train.tfrecord-00000-of-0000N` and so on. `dir` is created if it
+    /// does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a file cannot be written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.to_tfrecord(Path::new("mnist_tfrecord"), 4)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn to_tfrecord(&self, dir: &Path, shards: usize) -> io::Result<()> {
+        assert!(shards > 0, "Number of shards must be at least one.");
+        fs::create_dir_all(dir)?;
+        for (name, images, labels) in [
+            ("train", &self.train_data, &self.train_labels),
+            ("test", &self.test_data, &self.test_labels),
+        ] {
+            let shard_size = labels.len().div_ceil(shards).max(1);
+            let mut chunks = images.chunks(shard_size).zip(labels.chunks(shard_size));
+            for shard in 0..shards {
+                let filename = if shards == 1 {
+                    format!("{name}.tfrecord")
+                } else {
+                    format!("{name}.tfrecord-{shard:05}-of-{shards:05}")
+                };
+                let mut writer = io::BufWriter::new(fs::File::create(dir.join(filename))?);
+
+                // Later shards are left empty when there are fewer samples than shards.
+                if let Some((images, labels)) = chunks.next() {
+                    for (image, &label) in images.iter().zip(labels) {
+                        write_record(&mut writer, &example(image, label))?;
+                    }
+                }
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Encode a sample as a serialized `tf.train.Example` protocol buffer.
+fn example(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], label: u8) -> Vec<u8> {
+    let int64 = |value: u64| {
+        // Feature { Int64List int64_list = 3 }, Int64List { repeated int64 value = 1 [packed] }
+        let mut packed = Vec::new();
+        write_varint(&mut packed, value);
+        let mut list = Vec::new();
+        write_field(&mut list, 1, &packed);
+        let mut feature = Vec::new();
+        write_field(&mut feature, 3, &list);
+        feature
+    };
+    let bytes = |value: &[u8]| {
+        // Feature { BytesList bytes_list = 1 }, BytesList { repeated bytes value = 1 }
+        let mut list = Vec::new();
+        write_field(&mut list, 1, value);
+        let mut feature = Vec::new();
+        write_field(&mut feature, 1, &list);
+        feature
+    };
+    let features = [
+        ("depth", int64(1)),
+        ("height", int64(IMAGE_ROWS as u64)),
+        ("image_raw", bytes(image)),
+        ("label", int64(u64::from(label))),
+        ("width", int64(IMAGE_COLUMNS as u64)),
+    ];
+
+    // Features { map<string, Feature> feature = 1 }, where each map entry is a message with the
+    // key as field 1 and the value as field 2.
+    let mut map = Vec::new();
+    for (key, feature) in features {
+        let mut entry = Vec::new();
+        write_field(&mut entry, 1, key.as_bytes());
+        write_field(&mut entry, 2, &feature);
+        write_field(&mut map, 1, &entry);
+    }
+
+    // Example { Features features = 1 }
+    let mut example = Vec::new();
+    write_field(&mut example, 1, &map);
+    example
+}
+
+/// Frame a record as `length, crc(length), data, crc(data)`.
+fn write_record(writer: &mut impl Write, data: &[u8]) -> io::Result<()> {
+    let length = (data.len() as u64).to_le_bytes();
+    writer.write_all(&length)?;
+    writer.write_all(&masked_crc(&length).to_le_bytes())?;
+    writer.write_all(data)?;
+    writer.write_all(&masked_crc(data).to_le_bytes())
+}
+
+/// CRC-32C of `data`, masked as `TFRecord` requires.
+fn masked_crc(data: &[u8]) -> u32 {
+    let crc = !data.iter().fold(!0u32, |crc, &byte| {
+        CRC_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    });
+    crc.rotate_right(15).wrapping_add(0xa282_ead8)
+}
+
+/// Lookup table for the reflected CRC-32C (Castagnoli) polynomial.
+const CRC_TABLE: [u32; 256] = {
+    const POLYNOMIAL: u32 = 0x82f6_3b78;
+    let mut table = [0; 256];
+    let mut byte = 0u32;
+    while byte < 256 {
+        let mut crc = byte;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[byte as usize] = crc;
+        byte += 1;
+    }
+    table
+};
@@ -0,0 +1,73 @@
+//! `WebDataset` shards: tar files holding a `<key>.png` and `<key>.cls` member per sample.
+
+use crate::interop::image::gray_image;
+use crate::Mnist;
+use ::image::{ImageFormat, ImageResult};
+use ::tar::{Builder, Header};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+impl Mnist {
+    /// Write both splits to `WebDataset` tar shards of up to `shard_size` samples each, named
+    /// `train-000000.tar`, `train-000001.tar`, ..., `test-000000.tar`, ... in `dir`, which is
+    /// created if it does not exist.
+    ///
+    /// Each sample is stored as a PNG image, `<key>.png`, and its label in decimal, `<key>.cls`,
+    /// where the key is the sample's index within its split.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a shard cannot be written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shard_size` is zero.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.to_webdataset(Path::new("mnist_wds"), 10_000)?;
+    /// # Ok::<(), image::ImageError>(())
+    /// ```
+    pub fn to_webdataset(&self, dir: &Path, shard_size: usize) -> ImageResult<()> {
+        assert!(shard_size > 0, "Shard size must be at least one.");
+        fs::create_dir_all(dir)?;
+        for (name, images, labels) in [
+            ("train", &self.train_data, &self.train_labels),
+            ("test", &self.test_data, &self.test_labels),
+        ] {
+            for (shard, (images, labels)) in images
+                .chunks(shard_size)
+                .zip(labels.chunks(shard_size))
+                .enumerate()
+            {
+                let file = fs::File::create(dir.join(format!("{name}-{shard:06}.tar")))?;
+                let mut builder = Builder::new(io::BufWriter::new(file));
+                for (offset, (image, label)) in images.iter().zip(labels).enumerate() {
+                    let key = format!("{:08}", shard * shard_size + offset);
+                    let mut png = io::Cursor::new(Vec::new());
+                    gray_image(image).write_to(&mut png, ImageFormat::Png)?;
+                    append(&mut builder, &format!("{key}.png"), png.get_ref())?;
+                    append(
+                        &mut builder,
+                        &format!("{key}.cls"),
+                        label.to_string().as_bytes(),
+                    )?;
+                }
+                builder.into_inner()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn append(builder: &mut Builder<impl io::Write>, path: &str, data: &[u8]) -> io::Result<()> {
+    let mut header = Header::new_ustar();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    builder.append_data(&mut header, path, data)
+}
@@ -0,0 +1,57 @@
+//! IDX files wrapped in a zstd frame, named like the standard files with a `.zst` suffix.
+//!
+//! [`Mnist::new`] reads these transparently when the uncompressed files are missing.
+
+use crate::{Mnist, IDX_FILENAMES};
+use ::zstd::stream::read::Decoder;
+use ::zstd::stream::write::Encoder;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+impl Mnist {
+    /// Write the dataset to `dir` as zstd-compressed IDX files, `train-images-idx3-ubyte.zst` and
+    /// so on, creating `dir` if it does not exist. `level` is the zstd compression level, from 1
+    /// to 22; 0 selects zstd's default.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a file cannot be written.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.to_idx_zst(Path::new("mnist_zst"), 19)?;
+    ///
+    /// // Later loads decompress the files on the fly.
+    /// let mnist = Mnist::new(Path::new("mnist_zst"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn to_idx_zst(&self, dir: &Path, level: i32) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        for filename in IDX_FILENAMES {
+            let file = fs::File::create(zst_path(&dir.join(filename)))?;
+            let mut encoder = Encoder::new(io::BufWriter::new(file), level)?;
+            self.write_idx(filename, &mut encoder)?;
+            encoder.finish()?.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Open the zstd-compressed copy of the IDX file `filename`.
+pub(crate) fn open(filename: &Path) -> io::Result<Box<dyn Read>> {
+    let file = fs::File::open(zst_path(filename))?;
+    Ok(Box::new(Decoder::new(file)?))
+}
+
+fn zst_path(filename: &Path) -> PathBuf {
+    let mut path = OsString::from(filename);
+    path.push(".zst");
+    PathBuf::from(path)
+}
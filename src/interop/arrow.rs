@@ -0,0 +1,73 @@
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::arrow_array::{ArrayRef, FixedSizeBinaryArray, RecordBatch, UInt8Array};
+use ::arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+use std::sync::Arc;
+
+// Image size in the type Arrow uses for fixed-size binary widths.
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+const IMAGE_BYTES: i32 = (IMAGE_ROWS * IMAGE_COLUMNS) as i32;
+
+/// Schema of the record batches produced by [`Mnist::to_arrow`]: a non-null `UInt8` `label`
+/// column and a non-null `FixedSizeBinary(784)` `image` column of row-major pixels.
+#[must_use]
+pub fn arrow_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("label", DataType::UInt8, false),
+        Field::new("image", DataType::FixedSizeBinary(IMAGE_BYTES), false),
+    ]))
+}
+
+impl Mnist {
+    /// The split as Arrow record batches of up to `rows_per_batch` samples each, using
+    /// [`arrow_schema`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Arrow fails to assemble a record batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows_per_batch` is zero.
+    pub fn to_arrow(
+        &self,
+        split: Split,
+        rows_per_batch: usize,
+    ) -> Result<Vec<RecordBatch>, ArrowError> {
+        record_batches(self.images(split), self.labels(split), rows_per_batch)
+    }
+}
+
+impl ImageSet {
+    /// The set as Arrow record batches of up to `rows_per_batch` samples each, using
+    /// [`arrow_schema`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Arrow fails to assemble a record batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows_per_batch` is zero.
+    pub fn to_arrow(&self, rows_per_batch: usize) -> Result<Vec<RecordBatch>, ArrowError> {
+        record_batches(&self.images, &self.labels, rows_per_batch)
+    }
+}
+
+pub(crate) fn record_batches(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+    rows_per_batch: usize,
+) -> Result<Vec<RecordBatch>, ArrowError> {
+    assert!(rows_per_batch > 0, "Rows per batch must be at least one.");
+
+    let schema = arrow_schema();
+    images
+        .chunks(rows_per_batch)
+        .zip(labels.chunks(rows_per_batch))
+        .map(|(images, labels)| {
+            let labels: ArrayRef = Arc::new(UInt8Array::from(labels.to_vec()));
+            let images: ArrayRef = Arc::new(FixedSizeBinaryArray::try_from_iter(images.iter())?);
+            RecordBatch::try_new(Arc::clone(&schema), vec![labels, images])
+        })
+        .collect()
+}
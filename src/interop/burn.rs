@@ -0,0 +1,39 @@
+use crate::{ImageSet, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::burn_dataset::{Dataset, DatasetError};
+
+/// A sample as handed out by `burn` datasets, matching `burn`'s own MNIST item: pixels are
+/// unscaled, from 0 to 255, so existing `burn` batchers work unchanged.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MnistItem {
+    pub image: [[f32; IMAGE_COLUMNS]; IMAGE_ROWS],
+    pub label: u8,
+}
+
+/// Use a split as a `burn` dataset, in place of `burn`'s own MNIST dataset:
+///
+/// ```ignore
+/// let (train, test) = Mnist::new(&path).into_splits();
+/// let loader = DataLoaderBuilder::new(batcher).batch_size(64).build(train);
+/// ```
+impl Dataset<MnistItem> for ImageSet {
+    fn get(&self, index: usize) -> Result<MnistItem, DatasetError> {
+        let mut image = [[0.0; IMAGE_COLUMNS]; IMAGE_ROWS];
+        for (row, pixels) in image
+            .iter_mut()
+            .zip(self.images[index].chunks(IMAGE_COLUMNS))
+        {
+            for (pixel, &value) in row.iter_mut().zip(pixels) {
+                *pixel = f32::from(value);
+            }
+        }
+
+        Ok(MnistItem {
+            image,
+            label: self.labels[index],
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.labels.len()
+    }
+}
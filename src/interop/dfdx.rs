@@ -0,0 +1,70 @@
+use crate::{Batch, IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES};
+use ::dfdx::shapes::{Const, Rank2, Rank4};
+use ::dfdx::tensor::{Tensor, TensorFromVec};
+
+impl Batch {
+    /// The batch as a `(B, 784)` image tensor and a `(B, 10)` one-hot label tensor on `device`,
+    /// or `None` if the batch does not hold exactly `B` samples or holds a label that is not a
+    /// digit. Use
+    /// [`DataLoader::drop_last`](crate::DataLoader::drop_last) to make every batch full.
+    #[must_use]
+    #[allow(clippy::type_complexity)]
+    pub fn to_dfdx<const B: usize, D: TensorFromVec<f32>>(
+        &self,
+        device: &D,
+    ) -> Option<(
+        Tensor<Rank2<B, { IMAGE_ROWS * IMAGE_COLUMNS }>, f32, D>,
+        Tensor<Rank2<B, NUM_CLASSES>, f32, D>,
+    )> {
+        if !self.holds(B) {
+            return None;
+        }
+        let labels = self.one_hot(device)?;
+        let images = device.tensor_from_vec(self.images.clone(), (Const, Const));
+        Some((images, labels))
+    }
+
+    /// The batch as a `(B, 1, 28, 28)` image tensor and a `(B, 10)` one-hot label tensor on
+    /// `device`, or `None` if the batch does not hold exactly `B` samples or holds a label that
+    /// is not a digit.
+    #[must_use]
+    #[allow(clippy::type_complexity)]
+    pub fn to_dfdx_images<const B: usize, D: TensorFromVec<f32>>(
+        &self,
+        device: &D,
+    ) -> Option<(
+        Tensor<Rank4<B, 1, IMAGE_ROWS, IMAGE_COLUMNS>, f32, D>,
+        Tensor<Rank2<B, NUM_CLASSES>, f32, D>,
+    )> {
+        if !self.holds(B) {
+            return None;
+        }
+        let labels = self.one_hot(device)?;
+        let images = device.tensor_from_vec(self.images.clone(), (Const, Const, Const, Const));
+        Some((images, labels))
+    }
+
+    /// Whether the batch holds `len` images and labels, which it may not if its public fields
+    /// were changed, or its images were packed into [`Batch::staging`].
+    fn holds(&self, len: usize) -> bool {
+        self.len == len
+            && self.labels.len() == len
+            && self.images.len() == len * IMAGE_ROWS * IMAGE_COLUMNS
+    }
+
+    /// The labels one-hot encoded, or `None` if one is not a digit.
+    fn one_hot<const B: usize, D: TensorFromVec<f32>>(
+        &self,
+        device: &D,
+    ) -> Option<Tensor<Rank2<B, NUM_CLASSES>, f32, D>> {
+        let mut one_hot = vec![0.0; B * NUM_CLASSES];
+        for (row, &label) in self.labels.iter().enumerate() {
+            let label = usize::from(label);
+            if label >= NUM_CLASSES {
+                return None;
+            }
+            one_hot[row * NUM_CLASSES + label] = 1.0;
+        }
+        Some(device.tensor_from_vec(one_hot, (Const, Const)))
+    }
+}
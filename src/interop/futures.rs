@@ -0,0 +1,158 @@
+use crate::{Batch, DataLoader};
+use futures_core::Stream;
+use std::any::Any;
+use std::panic;
+use std::pin::Pin;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+impl DataLoader<'static> {
+    /// Stream the batches of the next epoch, for async pipelines to consume with
+    /// `while let Some(batch) = stream.next().await`.
+    ///
+    /// The batches are built on a thread of their own, with the loader's
+    /// [workers](DataLoader::workers) if it has any, so polling never blocks the executor. Up to
+    /// [`prefetch`](DataLoader::prefetch) built batches, and at least one, wait to be polled.
+    /// The thread owns the loader for the epoch, so it must not borrow its samples; create it
+    /// with [`DataLoader::shared`]. Once the stream has ended, [`BatchStream::into_loader`]
+    /// gives the loader back for the next epoch.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::future;
+    /// use std::path::Path;
+    /// use std::pin::Pin;
+    /// use std::sync::Arc;
+    /// use futures_core::Stream;
+    /// use rust_mnist::{DataLoader, Mnist, Split};
+    ///
+    /// async fn train() {
+    ///     let mnist = Arc::new(Mnist::new(Path::new("data")));
+    ///     let mut loader = DataLoader::shared(mnist, Split::Train, 64).shuffle(true).workers(4);
+    ///     for _epoch in 0..5 {
+    ///         let mut stream = loader.stream();
+    ///         while let Some(batch) =
+    ///             future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await
+    ///         {
+    ///             assert_eq!(batch.images.len(), batch.len * 784);
+    ///         }
+    ///         loader = stream.into_loader().expect("The epoch is over.");
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn stream(self) -> BatchStream {
+        let remaining = self.num_batches();
+        let (sender, messages) = mpsc::sync_channel(self.prefetch_depth().max(1));
+        let waker = Arc::new(Mutex::new(None::<Waker>));
+        let wake = {
+            let waker = Arc::clone(&waker);
+            move || {
+                if let Some(waker) = waker.lock().unwrap_or_else(PoisonError::into_inner).take() {
+                    waker.wake();
+                }
+            }
+        };
+
+        thread::spawn(move || {
+            let mut loader = self;
+            let built = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                thread::scope(|scope| {
+                    for batch in loader.epoch_in(scope) {
+                        // The stream was dropped, so nothing is waiting for the rest.
+                        if sender.send(Message::Batch(batch)).is_err() {
+                            return false;
+                        }
+                        wake();
+                    }
+                    true
+                })
+            }));
+            let last = match built {
+                Ok(true) => Message::End(loader),
+                Ok(false) => return,
+                Err(payload) => Message::Panicked(payload),
+            };
+            // The stream may have been dropped meanwhile, leaving nobody to tell.
+            let _ = sender.send(last);
+            wake();
+        });
+
+        BatchStream {
+            messages,
+            waker,
+            loader: None,
+            remaining,
+        }
+    }
+}
+
+/// What the thread building a [`BatchStream`]'s batches sends it.
+enum Message {
+    Batch(Batch),
+    /// The epoch is over; here is the loader back.
+    End(DataLoader<'static>),
+    /// A transform panicked with this payload.
+    Panicked(Box<dyn Any + Send>),
+}
+
+/// A [`Stream`] of the batches of one epoch, built off the executor, created by
+/// [`DataLoader::stream`].
+///
+/// Dropping the stream before it ends stops the epoch, and the loader with it.
+pub struct BatchStream {
+    messages: mpsc::Receiver<Message>,
+    /// Waker of the task that last polled.
+    waker: Arc<Mutex<Option<Waker>>>,
+    /// The loader, once the epoch is over.
+    loader: Option<DataLoader<'static>>,
+    remaining: usize,
+}
+
+impl BatchStream {
+    /// Give back the loader, to stream its next epoch, or `None` if the stream has not ended or
+    /// a transform panicked. Never waits.
+    #[must_use]
+    pub fn into_loader(mut self) -> Option<DataLoader<'static>> {
+        if self.loader.is_none() {
+            // Every batch may have been taken without polling the end of the stream.
+            if let Ok(Message::End(loader)) = self.messages.try_recv() {
+                self.loader = Some(loader);
+            }
+        }
+        self.loader
+    }
+}
+
+impl Stream for BatchStream {
+    type Item = Batch;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Batch>> {
+        let stream = self.get_mut();
+        // Register before looking, so a batch sent in between still wakes the task.
+        stream
+            .waker
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .replace(cx.waker().clone());
+        match stream.messages.try_recv() {
+            Ok(Message::Batch(batch)) => {
+                stream.remaining = stream.remaining.saturating_sub(1);
+                Poll::Ready(Some(batch))
+            }
+            Ok(Message::End(loader)) => {
+                stream.loader = Some(loader);
+                Poll::Ready(None)
+            }
+            Ok(Message::Panicked(payload)) => panic::resume_unwind(payload),
+            Err(mpsc::TryRecvError::Empty) => Poll::Pending,
+            Err(mpsc::TryRecvError::Disconnected) => Poll::Ready(None),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::image::imageops::{self, FilterType};
+use ::image::GrayImage;
+
+// Image dimensions in the type the `image` crate uses.
+#[allow(clippy::cast_possible_truncation)]
+const WIDTH: u32 = IMAGE_COLUMNS as u32;
+#[allow(clippy::cast_possible_truncation)]
+const HEIGHT: u32 = IMAGE_ROWS as u32;
+
+impl Mnist {
+    /// Image `index` of the split as an [`image::GrayImage`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn to_gray_image(&self, split: Split, index: usize) -> GrayImage {
+        gray_image(&self.images(split)[index])
+    }
+}
+
+impl ImageSet {
+    /// Image `index` of the set as an [`image::GrayImage`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn to_gray_image(&self, index: usize) -> GrayImage {
+        gray_image(&self.images[index])
+    }
+}
+
+/// Convert a grayscale image into the dataset's format, resizing it to 28x28 if needed.
+///
+/// MNIST digits are white on black; invert darker-on-lighter images before converting them.
+#[must_use]
+pub fn from_gray_image(image: &GrayImage) -> [u8; IMAGE_ROWS * IMAGE_COLUMNS] {
+    let mut pixels = [0; IMAGE_ROWS * IMAGE_COLUMNS];
+    if image.dimensions() == (WIDTH, HEIGHT) {
+        pixels.copy_from_slice(image.as_raw());
+    } else {
+        let resized = imageops::resize(image, WIDTH, HEIGHT, FilterType::Triangle);
+        pixels.copy_from_slice(resized.as_raw());
+    }
+    pixels
+}
+
+pub(crate) fn gray_image(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> GrayImage {
+    GrayImage::from_raw(WIDTH, HEIGHT, image.to_vec())
+        .expect("Image buffer matches its dimensions.")
+}
@@ -0,0 +1,32 @@
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::linfa::Dataset;
+use ::ndarray::{Array1, Array2, Ix1};
+
+impl Mnist {
+    /// The split as a `linfa` dataset, with one record of 784 pixels scaled to `[0, 1]` per image
+    /// and the digit as its target.
+    #[must_use]
+    pub fn to_linfa(&self, split: Split) -> Dataset<f64, usize, Ix1> {
+        linfa_dataset(self.images(split), self.labels(split))
+    }
+}
+
+impl ImageSet {
+    /// The set as a `linfa` dataset, with one record of 784 pixels scaled to `[0, 1]` per image
+    /// and the digit as its target.
+    #[must_use]
+    pub fn to_linfa(&self) -> Dataset<f64, usize, Ix1> {
+        linfa_dataset(&self.images, &self.labels)
+    }
+}
+
+fn linfa_dataset(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+) -> Dataset<f64, usize, Ix1> {
+    let records = Array2::from_shape_fn((images.len(), IMAGE_ROWS * IMAGE_COLUMNS), |(i, j)| {
+        f64::from(images[i][j]) / 255.0
+    });
+    let targets: Array1<usize> = labels.iter().map(|&label| usize::from(label)).collect();
+    Dataset::new(records, targets)
+}
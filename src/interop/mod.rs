@@ -0,0 +1,31 @@
+//! Conversions to and from the types of other crates, and implementations of their traits. Each
+//! integration sits behind its own feature.
+
+#[cfg(feature = "arrow")]
+pub(crate) mod arrow;
+#[cfg(feature = "burn")]
+pub(crate) mod burn;
+#[cfg(feature = "dfdx")]
+mod dfdx;
+#[cfg(feature = "async")]
+pub(crate) mod futures;
+#[cfg(feature = "image")]
+pub(crate) mod image;
+#[cfg(feature = "linfa")]
+mod linfa;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+#[cfg(feature = "ort")]
+pub(crate) mod ort;
+#[cfg(feature = "polars")]
+pub(crate) mod polars;
+#[cfg(feature = "serde")]
+pub(crate) mod serde;
+#[cfg(feature = "smartcore")]
+mod smartcore;
+#[cfg(feature = "tch")]
+mod tch;
+#[cfg(feature = "wgpu")]
+pub(crate) mod wgpu;
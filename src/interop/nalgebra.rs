@@ -0,0 +1,35 @@
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::nalgebra::{DMatrix, DVector};
+
+impl Mnist {
+    /// Images of the split as a matrix with one row of 784 pixels, scaled to `[0, 1]`, per
+    /// image, and their labels.
+    #[must_use]
+    pub fn to_dmatrix(&self, split: Split) -> (DMatrix<f64>, DVector<u8>) {
+        images_to_dmatrix(self.images(split), self.labels(split))
+    }
+}
+
+impl ImageSet {
+    /// Images as a matrix with one row of 784 pixels, scaled to `[0, 1]`, per image, and their
+    /// labels.
+    #[must_use]
+    pub fn to_dmatrix(&self) -> (DMatrix<f64>, DVector<u8>) {
+        images_to_dmatrix(&self.images, &self.labels)
+    }
+}
+
+fn images_to_dmatrix(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+) -> (DMatrix<f64>, DVector<u8>) {
+    let matrix = DMatrix::from_row_iterator(
+        images.len(),
+        IMAGE_ROWS * IMAGE_COLUMNS,
+        images
+            .iter()
+            .flatten()
+            .map(|&pixel| f64::from(pixel) / 255.0),
+    );
+    (matrix, DVector::from_column_slice(labels))
+}
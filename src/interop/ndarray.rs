@@ -0,0 +1,84 @@
+use crate::{Batch, BatchLayout, Epoch, ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::ndarray::{Array1, Array2, Array3, Array4};
+
+impl Mnist {
+    /// Images of the split as an array of shape `(n, rows, columns)`, and their labels.
+    #[must_use]
+    pub fn to_ndarray(&self, split: Split) -> (Array3<u8>, Array1<u8>) {
+        images_to_array3(self.images(split), self.labels(split))
+    }
+
+    /// Images of the split as an array of shape `(n, 784)` with pixels scaled to `[0, 1]`, and
+    /// their labels.
+    #[must_use]
+    pub fn to_ndarray_f32(&self, split: Split) -> (Array2<f32>, Array1<u8>) {
+        images_to_array2(self.images(split), self.labels(split))
+    }
+}
+
+impl ImageSet {
+    /// Images as an array of shape `(n, rows, columns)`, and their labels.
+    #[must_use]
+    pub fn to_ndarray(&self) -> (Array3<u8>, Array1<u8>) {
+        images_to_array3(&self.images, &self.labels)
+    }
+
+    /// Images as an array of shape `(n, 784)` with pixels scaled to `[0, 1]`, and their labels.
+    #[must_use]
+    pub fn to_ndarray_f32(&self) -> (Array2<f32>, Array1<u8>) {
+        images_to_array2(&self.images, &self.labels)
+    }
+}
+
+impl Batch {
+    /// Convert the batch into an image array and a label array.
+    ///
+    /// The images have shape `(batch, rows, columns, 1)` for [`BatchLayout::Nhwc`] batches and
+    /// `(batch, 1, rows, columns)` otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `images` does not hold exactly `len` images, as when the loader packed them into
+    /// [`Batch::staging`] instead, or if the batch was modified after the loader built it.
+    #[must_use]
+    pub fn into_ndarray(self) -> (Array4<f32>, Array1<u8>) {
+        let shape = match self.layout {
+            BatchLayout::Nhwc => (self.len, IMAGE_ROWS, IMAGE_COLUMNS, 1),
+            BatchLayout::Flat | BatchLayout::Nchw => (self.len, 1, IMAGE_ROWS, IMAGE_COLUMNS),
+        };
+        let images = Array4::from_shape_vec(shape, self.images)
+            .expect("Batch does not hold a whole number of images.");
+        (images, Array1::from(self.labels))
+    }
+}
+
+impl<'l> Epoch<'l, '_> {
+    /// Yield the epoch's batches as `ndarray` arrays; see [`Batch::into_ndarray`].
+    pub fn ndarray(self) -> impl Iterator<Item = (Array4<f32>, Array1<u8>)> + 'l {
+        self.map(Batch::into_ndarray)
+    }
+}
+
+fn images_to_array3(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+) -> (Array3<u8>, Array1<u8>) {
+    let pixels = images.iter().flatten().copied().collect();
+    let images = Array3::from_shape_vec((images.len(), IMAGE_ROWS, IMAGE_COLUMNS), pixels)
+        .expect("Images are all the same size.");
+    (images, Array1::from(labels.to_vec()))
+}
+
+fn images_to_array2(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+) -> (Array2<f32>, Array1<u8>) {
+    let pixels = images
+        .iter()
+        .flatten()
+        .map(|&pixel| f32::from(pixel) / 255.0)
+        .collect();
+    let images = Array2::from_shape_vec((images.len(), IMAGE_ROWS * IMAGE_COLUMNS), pixels)
+        .expect("Images are all the same size.");
+    (images, Array1::from(labels.to_vec()))
+}
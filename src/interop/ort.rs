@@ -0,0 +1,31 @@
+use crate::{Normalization, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::ort::value::Tensor;
+
+/// A single image as a `(1, 1, 28, 28)` input tensor for an ONNX Runtime session.
+///
+/// # Errors
+///
+/// Returns an error if ONNX Runtime fails to create the tensor.
+pub fn onnx_input(
+    image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS],
+    normalization: Normalization,
+) -> ort::Result<Tensor<f32>> {
+    onnx_batch(&[image], normalization)
+}
+
+/// Several images as a `(n, 1, 28, 28)` input tensor for an ONNX Runtime session.
+///
+/// # Errors
+///
+/// Returns an error if ONNX Runtime fails to create the tensor.
+pub fn onnx_batch(
+    images: &[&[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    normalization: Normalization,
+) -> ort::Result<Tensor<f32>> {
+    let pixels: Vec<f32> = images
+        .iter()
+        .flat_map(|image| image.iter())
+        .map(|&pixel| normalization.apply(pixel))
+        .collect();
+    Tensor::from_array(([images.len(), 1, IMAGE_ROWS, IMAGE_COLUMNS], pixels))
+}
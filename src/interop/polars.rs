@@ -0,0 +1,54 @@
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::polars::prelude::{Column, DataFrame, PolarsResult};
+
+/// How [`Mnist::to_polars`] stores the images in the data frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PolarsImages {
+    /// One `u8` column per pixel, named `pixel0` to `pixel783` as in the Kaggle CSV files.
+    Pixels,
+    /// A single binary column, `image`, holding each image's 784 row-major pixels.
+    Binary,
+}
+
+impl Mnist {
+    /// The split as a data frame with a `label` column followed by the images.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `polars` fails to assemble the data frame.
+    pub fn to_polars(&self, split: Split, images: PolarsImages) -> PolarsResult<DataFrame> {
+        data_frame(self.images(split), self.labels(split), images)
+    }
+}
+
+impl ImageSet {
+    /// The set as a data frame with a `label` column followed by the images.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `polars` fails to assemble the data frame.
+    pub fn to_polars(&self, images: PolarsImages) -> PolarsResult<DataFrame> {
+        data_frame(&self.images, &self.labels, images)
+    }
+}
+
+fn data_frame(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+    layout: PolarsImages,
+) -> PolarsResult<DataFrame> {
+    let mut columns = vec![Column::new("label".into(), labels)];
+    match layout {
+        PolarsImages::Pixels => {
+            for pixel in 0..IMAGE_ROWS * IMAGE_COLUMNS {
+                let values: Vec<u8> = images.iter().map(|image| image[pixel]).collect();
+                columns.push(Column::new(format!("pixel{pixel}").into(), values));
+            }
+        }
+        PolarsImages::Binary => {
+            let values: Vec<&[u8]> = images.iter().map(|image| &image[..]).collect();
+            columns.push(Column::new("image".into(), values));
+        }
+    }
+    DataFrame::new(labels.len(), columns)
+}
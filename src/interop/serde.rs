@@ -0,0 +1,84 @@
+//! Serialization of image lists. Images are too large for `serde`'s built-in array support, so
+//! each is written as a byte string.
+
+use crate::{IMAGE_COLUMNS, IMAGE_ROWS};
+use ::serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use ::serde::ser::{Serialize, Serializer};
+use std::fmt;
+
+type Image = [u8; IMAGE_ROWS * IMAGE_COLUMNS];
+
+/// Use with `#[serde(with = "...")]` on `Vec<[u8; 784]>` fields.
+pub(crate) mod images {
+    use super::{Bytes, Image, ImageBytes};
+    use ::serde::de::Deserializer;
+    use ::serde::ser::{SerializeSeq, Serializer};
+    use ::serde::Deserialize;
+
+    pub(crate) fn serialize<S: Serializer>(
+        images: &[Image],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(images.len()))?;
+        for image in images {
+            seq.serialize_element(&Bytes(image))?;
+        }
+        seq.end()
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Image>, D::Error> {
+        let images = Vec::<ImageBytes>::deserialize(deserializer)?;
+        Ok(images.into_iter().map(|ImageBytes(image)| image).collect())
+    }
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+struct ImageBytes(Image);
+
+impl<'de> Deserialize<'de> for ImageBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(ImageVisitor)
+    }
+}
+
+struct ImageVisitor;
+
+impl<'de> Visitor<'de> for ImageVisitor {
+    type Value = ImageBytes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} pixel bytes", IMAGE_ROWS * IMAGE_COLUMNS)
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<ImageBytes, E> {
+        let mut image = [0; IMAGE_ROWS * IMAGE_COLUMNS];
+        if bytes.len() != image.len() {
+            return Err(E::invalid_length(bytes.len(), &self));
+        }
+        image.copy_from_slice(bytes);
+        Ok(ImageBytes(image))
+    }
+
+    // Formats without a byte string type, like JSON, write bytes as a sequence of numbers.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ImageBytes, A::Error> {
+        let mut image = [0; IMAGE_ROWS * IMAGE_COLUMNS];
+        for (len, pixel) in image.iter_mut().enumerate() {
+            *pixel = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(len, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(image.len() + 1, &self));
+        }
+        Ok(ImageBytes(image))
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::smartcore::linalg::basic::matrix::DenseMatrix;
+
+impl Mnist {
+    /// The split as a `smartcore` matrix with one row of 784 pixels, scaled to `[0, 1]`, per
+    /// image, and the matching target vector of digits.
+    #[must_use]
+    pub fn to_smartcore(&self, split: Split) -> (DenseMatrix<f64>, Vec<u32>) {
+        smartcore_matrix(self.images(split), self.labels(split))
+    }
+}
+
+impl ImageSet {
+    /// The set as a `smartcore` matrix with one row of 784 pixels, scaled to `[0, 1]`, per image,
+    /// and the matching target vector of digits.
+    #[must_use]
+    pub fn to_smartcore(&self) -> (DenseMatrix<f64>, Vec<u32>) {
+        smartcore_matrix(&self.images, &self.labels)
+    }
+}
+
+fn smartcore_matrix(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+) -> (DenseMatrix<f64>, Vec<u32>) {
+    let values = images
+        .iter()
+        .flatten()
+        .map(|&pixel| f64::from(pixel) / 255.0)
+        .collect();
+    let matrix = DenseMatrix::new(images.len(), IMAGE_ROWS * IMAGE_COLUMNS, values, false)
+        .expect("Images are all the same size.");
+    let targets = labels.iter().map(|&label| u32::from(label)).collect();
+    (matrix, targets)
+}
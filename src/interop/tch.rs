@@ -0,0 +1,54 @@
+use crate::{Batch, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use ::tch::{Device, Kind, Tensor};
+use std::convert::TryFrom;
+
+impl Mnist {
+    /// Training images as a `(60000, 1, 28, 28)` float tensor with pixels scaled to `[0, 1]`,
+    /// and their labels as an `i64` tensor, both on `device`.
+    #[must_use]
+    pub fn train_tensor(&self, device: Device) -> (Tensor, Tensor) {
+        self.split_tensor(Split::Train, device)
+    }
+
+    /// Testing images as a `(10000, 1, 28, 28)` float tensor with pixels scaled to `[0, 1]`, and
+    /// their labels as an `i64` tensor, both on `device`.
+    #[must_use]
+    pub fn test_tensor(&self, device: Device) -> (Tensor, Tensor) {
+        self.split_tensor(Split::Test, device)
+    }
+
+    fn split_tensor(&self, split: Split, device: Device) -> (Tensor, Tensor) {
+        let images = self.images(split);
+        let pixels: Vec<u8> = images.iter().flatten().copied().collect();
+        let shape = [dim(images.len()), 1, dim(IMAGE_ROWS), dim(IMAGE_COLUMNS)];
+        let images = Tensor::from_slice(&pixels).view(shape).to_kind(Kind::Float) / 255.0;
+        (
+            images.to_device(device),
+            labels_tensor(self.labels(split), device),
+        )
+    }
+}
+
+impl Batch {
+    /// The batch's images, shaped according to its layout, and its labels as an `i64` tensor,
+    /// both on `device`.
+    #[must_use]
+    pub fn to_tensors(&self, device: Device) -> (Tensor, Tensor) {
+        let shape: Vec<i64> = self.shape.iter().map(|&size| dim(size)).collect();
+        let images = Tensor::from_slice(&self.images)
+            .view(&shape[..])
+            .to_device(device);
+        (images, labels_tensor(&self.labels, device))
+    }
+}
+
+/// Labels as an `i64` tensor, the type `tch`'s loss functions expect for class indices.
+fn labels_tensor(labels: &[u8], device: Device) -> Tensor {
+    Tensor::from_slice(labels)
+        .to_kind(Kind::Int64)
+        .to_device(device)
+}
+
+fn dim(size: usize) -> i64 {
+    i64::try_from(size).expect("Tensor dimension does not fit in an i64.")
+}
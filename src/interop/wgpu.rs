@@ -0,0 +1,68 @@
+use crate::{BatchLayout, Mnist, Split};
+use ::wgpu::{Buffer, BufferAddress, BufferDescriptor, BufferUsages, Device, Queue};
+use std::convert::TryFrom;
+
+/// A split uploaded to the GPU by [`Mnist::upload_train`] or [`Mnist::upload_test`].
+pub struct GpuImages {
+    /// Little-endian `f32` pixels scaled to `[0, 1]`, one image after another.
+    pub images: Buffer,
+    /// Little-endian `u32` labels, as WGSL has no 8-bit integers.
+    pub labels: Buffer,
+    /// Number of images.
+    pub len: usize,
+    /// Shape of `images`, outermost dimension first.
+    pub shape: Vec<usize>,
+}
+
+impl Mnist {
+    /// Upload the training images and labels into new storage buffers on `device`.
+    #[must_use]
+    pub fn upload_train(&self, device: &Device, queue: &Queue, layout: BatchLayout) -> GpuImages {
+        self.upload(Split::Train, device, queue, layout)
+    }
+
+    /// Upload the testing images and labels into new storage buffers on `device`.
+    #[must_use]
+    pub fn upload_test(&self, device: &Device, queue: &Queue, layout: BatchLayout) -> GpuImages {
+        self.upload(Split::Test, device, queue, layout)
+    }
+
+    fn upload(
+        &self,
+        split: Split,
+        device: &Device,
+        queue: &Queue,
+        layout: BatchLayout,
+    ) -> GpuImages {
+        let pixels: Vec<u8> = self
+            .images(split)
+            .iter()
+            .flatten()
+            .flat_map(|&pixel| (f32::from(pixel) / 255.0).to_le_bytes())
+            .collect();
+        let labels: Vec<u8> = self
+            .labels(split)
+            .iter()
+            .flat_map(|&label| u32::from(label).to_le_bytes())
+            .collect();
+
+        let len = self.labels(split).len();
+        GpuImages {
+            images: upload_buffer(device, queue, "MNIST images", &pixels),
+            labels: upload_buffer(device, queue, "MNIST labels", &labels),
+            len,
+            shape: layout.shape(len),
+        }
+    }
+}
+
+fn upload_buffer(device: &Device, queue: &Queue, label: &str, contents: &[u8]) -> Buffer {
+    let buffer = device.create_buffer(&BufferDescriptor {
+        label: Some(label),
+        size: BufferAddress::try_from(contents.len()).expect("Buffer size does not fit in a u64."),
+        usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    queue.write_buffer(&buffer, 0, contents);
+    buffer
+}
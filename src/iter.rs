@@ -0,0 +1,201 @@
+//! Iterators over samples, and adaptors for composing lightweight training loops.
+
+use crate::{ImageSet, Mnist, IMAGE_COLUMNS, IMAGE_ROWS};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Iterator over `(image, label)` pairs.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::{Mnist, SampleIteratorExt};
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+/// for batch in mnist.train_iter().shuffled(7).batched(64).epochs(5) {
+///     for (image, label) in batch {
+///         // Train on the sample.
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Samples<'a> {
+    images: &'a [[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &'a [u8],
+    /// Indices to visit, or `None` to visit every sample in order.
+    order: Option<Vec<usize>>,
+    position: usize,
+    end: usize,
+}
+
+impl<'a> Samples<'a> {
+    fn new(images: &'a [[u8; IMAGE_ROWS * IMAGE_COLUMNS]], labels: &'a [u8]) -> Samples<'a> {
+        Samples {
+            images,
+            labels,
+            order: None,
+            position: 0,
+            end: labels.len(),
+        }
+    }
+
+    /// Visit the remaining samples in a random order determined by `seed`.
+    #[must_use]
+    pub fn shuffled(self, seed: u64) -> Samples<'a> {
+        let mut order: Vec<usize> = (self.position..self.end)
+            .map(|position| self.index(position))
+            .collect();
+        order.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+
+        Samples {
+            images: self.images,
+            labels: self.labels,
+            end: order.len(),
+            order: Some(order),
+            position: 0,
+        }
+    }
+
+    /// Visit at most `n` of the remaining samples.
+    #[must_use]
+    pub fn take_samples(mut self, n: usize) -> Samples<'a> {
+        self.end = self.end.min(self.position + n);
+        self
+    }
+
+    /// Index of the sample visited at `position`.
+    fn index(&self, position: usize) -> usize {
+        match &self.order {
+            Some(order) => order[position],
+            None => position,
+        }
+    }
+}
+
+impl<'a> Iterator for Samples<'a> {
+    type Item = (&'a [u8; IMAGE_ROWS * IMAGE_COLUMNS], u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position == self.end {
+            return None;
+        }
+
+        let index = self.index(self.position);
+        self.position += 1;
+        Some((&self.images[index], self.labels[index]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.position;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Samples<'_> {}
+
+/// Adaptors for iterators over samples, or over anything else.
+pub trait SampleIteratorExt: Iterator + Sized {
+    /// Group items into batches of `batch_size`. The last batch may be smaller.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    fn batched(self, batch_size: usize) -> Batched<Self> {
+        assert!(batch_size > 0, "Batch size must be at least one.");
+        Batched {
+            iter: self,
+            batch_size,
+        }
+    }
+
+    /// Repeat the iterator `epochs` times. Every epoch replays the iterator as it was when this
+    /// was called, so a shuffled iterator visits samples in the same order each epoch; use a
+    /// [`DataLoader`](crate::DataLoader) for a fresh shuffle every epoch.
+    fn epochs(self, epochs: usize) -> Epochs<Self>
+    where
+        Self: Clone,
+    {
+        Epochs {
+            original: self.clone(),
+            current: self,
+            remaining: epochs,
+        }
+    }
+}
+
+impl<I: Iterator> SampleIteratorExt for I {}
+
+/// Iterator adaptor grouping items into batches, created by [`SampleIteratorExt::batched`].
+#[derive(Clone)]
+pub struct Batched<I> {
+    iter: I,
+    batch_size: usize,
+}
+
+impl<I: Iterator> Iterator for Batched<I> {
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch: Vec<_> = self.iter.by_ref().take(self.batch_size).collect();
+        if batch.is_empty() {
+            None
+        } else {
+            Some(batch)
+        }
+    }
+}
+
+/// Iterator adaptor repeating an iterator, created by [`SampleIteratorExt::epochs`].
+#[derive(Clone)]
+pub struct Epochs<I> {
+    original: I,
+    current: I,
+    remaining: usize,
+}
+
+impl<I: Iterator + Clone> Iterator for Epochs<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.remaining > 0 {
+            if let Some(item) = self.current.next() {
+                return Some(item);
+            }
+            self.remaining -= 1;
+            self.current = self.original.clone();
+        }
+        None
+    }
+}
+
+impl Mnist {
+    /// Iterate over the training samples.
+    #[must_use]
+    pub fn train_iter(&self) -> Samples<'_> {
+        Samples::new(&self.train_data, &self.train_labels)
+    }
+
+    /// Iterate over the testing samples.
+    #[must_use]
+    pub fn test_iter(&self) -> Samples<'_> {
+        Samples::new(&self.test_data, &self.test_labels)
+    }
+}
+
+impl ImageSet {
+    /// Iterate over the samples in the set.
+    #[must_use]
+    pub fn iter(&self) -> Samples<'_> {
+        Samples::new(&self.images, &self.labels)
+    }
+}
+
+impl<'a> IntoIterator for &'a ImageSet {
+    type Item = (&'a [u8; IMAGE_ROWS * IMAGE_COLUMNS], u8);
+    type IntoIter = Samples<'a>;
+
+    fn into_iter(self) -> Samples<'a> {
+        self.iter()
+    }
+}
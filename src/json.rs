@@ -0,0 +1,162 @@
+//! Just enough JSON for the crate's own files: objects with string keys, arrays, numbers and
+//! strings.
+
+use std::fmt::Write;
+use std::io;
+
+/// Reader over JSON text, consuming it from the front.
+pub(crate) struct JsonReader<'t> {
+    pub(crate) rest: &'t str,
+}
+
+impl<'t> JsonReader<'t> {
+    pub(crate) fn new(text: &'t str) -> JsonReader<'t> {
+        JsonReader { rest: text }
+    }
+
+    /// Read an object, handing each key to `field` to read its value.
+    pub(crate) fn object(
+        &mut self,
+        mut field: impl FnMut(&mut Self, &str) -> io::Result<()>,
+    ) -> io::Result<()> {
+        self.expect('{')?;
+        if self.eat('}') {
+            return Ok(());
+        }
+        loop {
+            let key = self.string()?;
+            self.expect(':')?;
+            field(self, &key)?;
+            if !self.eat(',') {
+                return self.expect('}');
+            }
+        }
+    }
+
+    /// Read an array, using `item` to read each element.
+    pub(crate) fn array<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> io::Result<T>,
+    ) -> io::Result<Vec<T>> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        if self.eat(']') {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if !self.eat(',') {
+                self.expect(']')?;
+                return Ok(items);
+            }
+        }
+    }
+
+    pub(crate) fn number<T: std::str::FromStr>(&mut self) -> io::Result<T> {
+        self.skip_whitespace();
+        let end = self
+            .rest
+            .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+            .unwrap_or(self.rest.len());
+        let number = self.rest[..end]
+            .parse()
+            .map_err(|_| invalid_data(format!("Invalid number \"{}\".", &self.rest[..end])))?;
+        self.rest = &self.rest[end..];
+        Ok(number)
+    }
+
+    /// Read a string, undoing the escapes written by [`write_string`].
+    pub(crate) fn string(&mut self) -> io::Result<String> {
+        self.expect('"')?;
+        let mut string = String::new();
+        let mut chars = self.rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.rest = &self.rest[i + 1..];
+                    return Ok(string);
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('"') => string.push('"'),
+                    Some('\\') => string.push('\\'),
+                    Some('/') => string.push('/'),
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('u') => {
+                        let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                        let c = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| {
+                                invalid_data(format!("Invalid escape \"\\u{hex}\" in JSON."))
+                            })?;
+                        string.push(c);
+                    }
+                    _ => return Err(invalid_data("Invalid escape in JSON.")),
+                },
+                c => string.push(c),
+            }
+        }
+        Err(invalid_data("Unterminated string."))
+    }
+
+    /// Skip whitespace and then `expected`, returning whether it was there.
+    pub(crate) fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(expected) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn expect(&mut self, expected: char) -> io::Result<()> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(invalid_data(format!("Expected '{expected}' in JSON.")))
+        }
+    }
+
+    /// Check that nothing but whitespace is left.
+    pub(crate) fn end(&mut self) -> io::Result<()> {
+        self.skip_whitespace();
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid_data("Trailing data after JSON object."))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+}
+
+/// Append `string` to `out` as a quoted JSON string.
+pub(crate) fn write_string(out: &mut String, string: &str) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+pub(crate) fn invalid_data(
+    message: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
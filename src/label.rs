@@ -0,0 +1,161 @@
+//! Class labels together with the names of the classes they stand for.
+
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::fmt;
+
+/// The classes of a dataset in the MNIST format, which give meaning to its numeric labels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClassNames {
+    /// Handwritten digits, as in MNIST itself: labels 0 to 9 are "0" to "9".
+    Digits,
+    /// Fashion-MNIST's ten kinds of clothing, from "T-shirt/top" to "Ankle boot".
+    Fashion,
+    /// Kuzushiji-MNIST's ten hiragana characters, from "お" to "を".
+    Kuzushiji,
+}
+
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+const FASHION: [&str; 10] = [
+    "T-shirt/top",
+    "Trouser",
+    "Pullover",
+    "Dress",
+    "Coat",
+    "Sandal",
+    "Shirt",
+    "Sneaker",
+    "Bag",
+    "Ankle boot",
+];
+
+const KUZUSHIJI: [&str; 10] = ["お", "き", "す", "つ", "な", "は", "ま", "や", "れ", "を"];
+
+impl ClassNames {
+    /// Name of every class, in label order, from label 0.
+    #[must_use]
+    pub fn all(self) -> &'static [&'static str] {
+        match self {
+            ClassNames::Digits => &DIGITS,
+            ClassNames::Fashion => &FASHION,
+            ClassNames::Kuzushiji => &KUZUSHIJI,
+        }
+    }
+
+    /// Name of the class with label `class`, or `None` if the dataset has no such label.
+    #[must_use]
+    pub fn name(self, class: u8) -> Option<&'static str> {
+        self.all().get(usize::from(class)).copied()
+    }
+}
+
+#[cfg(feature = "download")]
+impl From<crate::Dataset> for ClassNames {
+    fn from(dataset: crate::Dataset) -> ClassNames {
+        match dataset {
+            crate::Dataset::Mnist => ClassNames::Digits,
+            crate::Dataset::Fashion => ClassNames::Fashion,
+            crate::Dataset::Kmnist => ClassNames::Kuzushiji,
+        }
+    }
+}
+
+/// A numeric class label together with the classes of its dataset, so that it can be shown by
+/// name.
+///
+/// Its [`Display`](fmt::Display) implementation prints the class name.
+///
+/// # Examples
+/// ```
+/// use rust_mnist::{ClassNames, Label};
+///
+/// let label = Label::new(9, ClassNames::Fashion);
+/// assert_eq!(label.name(), "Ankle boot");
+/// assert_eq!(label.to_string(), "Ankle boot");
+/// assert_eq!(u8::from(label), 9);
+/// assert_eq!(ClassNames::Digits.name(10), None);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawLabel"))]
+pub struct Label {
+    class: u8,
+    names: ClassNames,
+}
+
+impl Label {
+    /// Label `class` of a dataset with classes `names`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `names` has no class `class`.
+    #[must_use]
+    pub fn new(class: u8, names: ClassNames) -> Label {
+        assert!(
+            names.name(class).is_some(),
+            "Label {} is not a class of {:?}.",
+            class,
+            names
+        );
+        Label { class, names }
+    }
+
+    /// The numeric label.
+    #[must_use]
+    pub fn class(self) -> u8 {
+        self.class
+    }
+
+    /// The classes of the label's dataset.
+    #[must_use]
+    pub fn names(self) -> ClassNames {
+        self.names
+    }
+
+    /// Name of the class, such as "7", "Sneaker" or "を".
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        // Creating and deserializing labels both check the class.
+        self.names.all()[usize::from(self.class)]
+    }
+}
+
+/// A [`Label`] as serialized, checked by [`Label::new`] when deserialized.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawLabel {
+    class: u8,
+    names: ClassNames,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawLabel> for Label {
+    type Error = String;
+
+    fn try_from(raw: RawLabel) -> Result<Label, String> {
+        match raw.names.name(raw.class) {
+            Some(_) => Ok(Label {
+                class: raw.class,
+                names: raw.names,
+            }),
+            None => Err(format!(
+                "Label {} is not a class of {:?}.",
+                raw.class, raw.names
+            )),
+        }
+    }
+}
+
+impl From<Label> for u8 {
+    fn from(label: Label) -> u8 {
+        label.class
+    }
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,149 @@
+//! How a model's accuracy grows with the amount of training data.
+
+use crate::metrics::Metrics;
+use crate::subset::gather;
+use crate::{ImageSet, Mnist, IMAGE_COLUMNS, IMAGE_ROWS};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+
+/// Metrics of a model trained on growing subsets of the data, created by
+/// [`Mnist::learning_curve`].
+///
+/// Its [`Display`](fmt::Display) implementation prints the accuracy and macro F1 at each size.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LearningCurve {
+    /// Number of training samples and the resulting metrics, smallest subset first.
+    pub points: Vec<(usize, Metrics)>,
+}
+
+impl LearningCurve {
+    /// Fractions of the training data commonly used for data-efficiency studies: 1%, 5%, 10%,
+    /// 25%, 50% and all of it.
+    pub const STANDARD_FRACTIONS: [f64; 6] = [0.01, 0.05, 0.1, 0.25, 0.5, 1.0];
+
+    /// Accuracy at each subset size, smallest subset first.
+    #[must_use]
+    pub fn accuracies(&self) -> Vec<(usize, f64)> {
+        self.points
+            .iter()
+            .map(|(size, metrics)| (*size, metrics.accuracy))
+            .collect()
+    }
+}
+
+impl fmt::Display for LearningCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, " Samples  Accuracy  Macro F1")?;
+        for (size, metrics) in &self.points {
+            writeln!(
+                f,
+                "{size:>8}  {:>8.4}  {:>8.4}",
+                metrics.accuracy,
+                metrics.macro_f1()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Mnist {
+    /// Train and score a model on growing subsets of the training data.
+    ///
+    /// The training samples are shuffled once with `seed`, and each subset is the first
+    /// `fraction` of them, so every subset contains the smaller ones. Subsets are visited from
+    /// smallest to largest; for each, `run` is handed the subset and returns the metrics of a
+    /// model trained on it, usually scored on the testing data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a fraction is not greater than 0 and at most 1, or if `run` panics.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{LearningCurve, Mnist};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    ///
+    /// // How many samples does a nearest-class-mean classifier need?
+    /// let curve = mnist.learning_curve(&LearningCurve::STANDARD_FRACTIONS, 7, |train| {
+    ///     let means = train.class_mean_images();
+    ///     mnist.evaluate(4, |image| {
+    ///         let distance = |mean: &[u8; 784]| -> u32 {
+    ///             mean.iter().zip(image.iter()).map(|(&a, &b)| u32::from(a.abs_diff(b))).sum()
+    ///         };
+    ///         (0..10u8).min_by_key(|&class| distance(&means[usize::from(class)])).unwrap()
+    ///     })
+    /// });
+    /// print!("{}", curve);
+    /// ```
+    pub fn learning_curve(
+        &self,
+        fractions: &[f64],
+        seed: u64,
+        run: impl FnMut(&ImageSet) -> Metrics,
+    ) -> LearningCurve {
+        learning_curve(&self.train_data, &self.train_labels, fractions, seed, run)
+    }
+}
+
+impl ImageSet {
+    /// Train and score a model on growing subsets of the set, as [`Mnist::learning_curve`] does
+    /// with the training data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a fraction is not greater than 0 and at most 1, or if `run` panics.
+    pub fn learning_curve(
+        &self,
+        fractions: &[f64],
+        seed: u64,
+        run: impl FnMut(&ImageSet) -> Metrics,
+    ) -> LearningCurve {
+        learning_curve(&self.images, &self.labels, fractions, seed, run)
+    }
+}
+
+fn learning_curve(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+    fractions: &[f64],
+    seed: u64,
+    mut run: impl FnMut(&ImageSet) -> Metrics,
+) -> LearningCurve {
+    assert!(
+        fractions
+            .iter()
+            .all(|fraction| *fraction > 0.0 && *fraction <= 1.0),
+        "Learning curve fractions must be greater than 0 and at most 1."
+    );
+
+    let mut order: Vec<usize> = (0..images.len()).collect();
+    order.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+
+    let mut sizes: Vec<usize> = fractions
+        .iter()
+        .map(|&fraction| subset_size(fraction, images.len()))
+        .collect();
+    sizes.sort_unstable();
+
+    // Subsets are nested: each one is a longer prefix of the same shuffled order.
+    let points = sizes
+        .into_iter()
+        .map(|size| (size, run(&gather(images, labels, &order[..size]))))
+        .collect();
+    LearningCurve { points }
+}
+
+/// Number of samples in `fraction` of `len` samples, rounded, but at least one if there are any.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn subset_size(fraction: f64, len: usize) -> usize {
+    ((fraction * len as f64).round() as usize).clamp(len.min(1), len)
+}
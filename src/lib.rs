@@ -1,31 +1,229 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(clippy::pedantic)]
 //! A simple struct build by parsing the MNIST dataset.
-
-use log::info;
+//!
+//! Everything but [`IdxStream`] needs the default `std` feature; without it, the crate is
+//! `no_std` and streams samples from a [`SliceSource`] or a [`FnSource`] on targets without an
+//! OS.
+
+#[cfg(feature = "std")]
+use rand::Rng;
+#[cfg(feature = "std")]
 use std::convert::TryFrom;
+#[cfg(feature = "std")]
 use std::fs;
+#[cfg(feature = "std")]
 use std::io;
-use std::io::Read;
-use std::path::PathBuf;
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+#[cfg(feature = "std")]
+mod augment;
+#[cfg(feature = "baselines")]
+pub mod baselines;
+#[cfg(feature = "std")]
+mod centroids;
+#[cfg(feature = "compat")]
+pub mod compat;
+#[cfg(feature = "std")]
+mod cross_validation;
+#[cfg(feature = "download")]
+mod download;
+#[cfg(feature = "std")]
+mod duplicates;
+#[cfg(feature = "std")]
+mod embedding;
+#[cfg(feature = "std")]
+mod evaluate;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+mod formats;
+#[cfg(feature = "std")]
+mod interop;
+#[cfg(feature = "std")]
+mod iter;
+#[cfg(feature = "std")]
+mod json;
+#[cfg(feature = "std")]
+mod label;
+#[cfg(feature = "std")]
+mod learning_curve;
+#[cfg(feature = "std")]
+mod loader;
+#[cfg(feature = "std")]
+mod manifest;
+#[cfg(feature = "download")]
+mod md5;
+#[cfg(feature = "std")]
+mod memory;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "std")]
+mod mnist_image;
+#[cfg(feature = "std")]
+mod normalize;
+#[cfg(feature = "std")]
+mod options;
+#[cfg(feature = "std")]
+mod outliers;
+#[cfg(feature = "std")]
+mod pca;
+#[cfg(feature = "std")]
+mod pixels;
+#[cfg(feature = "plot")]
+mod plot;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "std")]
+mod render;
+#[cfg(feature = "std")]
+mod sequence;
+#[cfg(feature = "std")]
+mod source;
+#[cfg(feature = "std")]
+mod split;
+#[cfg(feature = "std")]
+mod staging;
+#[cfg(feature = "std")]
+mod stats;
+mod stream;
+#[cfg(feature = "std")]
+mod subset;
+#[cfg(feature = "std")]
+mod summary;
+#[cfg(feature = "std")]
+mod verify;
+#[cfg(feature = "std")]
+mod view;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+#[cfg(feature = "std")]
+pub use augment::Augmentation;
+#[cfg(feature = "std")]
+pub use centroids::Confusability;
+#[cfg(feature = "std")]
+pub use cross_validation::CrossValidation;
+#[cfg(feature = "download")]
+pub use download::{download, download_from, Dataset};
+#[cfg(feature = "std")]
+pub use duplicates::{average_hash, difference_hash};
+#[cfg(feature = "std")]
+pub use embedding::Projection;
+#[cfg(feature = "std")]
+pub use formats::shards::{Shard, ShardIndex, ShardSamples};
+#[cfg(feature = "arrow")]
+pub use interop::arrow::arrow_schema;
+#[cfg(feature = "burn")]
+pub use interop::burn::MnistItem;
+#[cfg(feature = "async")]
+pub use interop::futures::BatchStream;
+#[cfg(feature = "image")]
+pub use interop::image::from_gray_image;
+#[cfg(feature = "ort")]
+pub use interop::ort::{onnx_batch, onnx_input};
+#[cfg(feature = "polars")]
+pub use interop::polars::PolarsImages;
+#[cfg(feature = "wgpu")]
+pub use interop::wgpu::GpuImages;
+#[cfg(feature = "std")]
+pub use iter::{Batched, Epochs, SampleIteratorExt, Samples};
+#[cfg(feature = "std")]
+pub use label::{ClassNames, Label};
+#[cfg(feature = "std")]
+pub use learning_curve::LearningCurve;
+#[cfg(feature = "std")]
+pub use loader::{Batch, BatchLayout, DataLoader, Epoch, LoaderState};
+#[cfg(feature = "std")]
+pub use manifest::Manifest;
+#[cfg(feature = "std")]
+pub use memory::{MemoryReport, SplitMemory};
+#[cfg(feature = "std")]
+pub use mnist_image::{Columns, MnistImage};
+#[cfg(feature = "std")]
+pub use normalize::Normalization;
+#[cfg(feature = "download")]
+pub use options::DownloadPolicy;
+#[cfg(feature = "std")]
+pub use options::LoadOptions;
+#[cfg(feature = "std")]
+pub use outliers::OutlierMethod;
+#[cfg(feature = "std")]
+pub use pca::Pca;
+#[cfg(feature = "std")]
+pub use render::{
+    image_to_ansi, image_to_braille, image_to_string, print_grid, print_image, print_prediction,
+    write_grid, write_image, write_prediction, AsciiRamp, ColorSupport,
+};
+#[cfg(feature = "termgfx")]
+pub use render::{image_to_graphics, GraphicsProtocol};
+#[cfg(feature = "std")]
+pub use sequence::ScanOrder;
+#[cfg(feature = "mmap")]
+pub use source::MmapDir;
+#[cfg(feature = "std")]
+pub use source::{CachedSource, DataSource, LocalDir, MemorySource};
+#[cfg(feature = "download")]
+pub use source::{HttpSource, ObjectStore};
+#[cfg(feature = "std")]
+pub use split::TaskLabels;
+#[cfg(feature = "std")]
+pub use staging::{StagingBuffer, StagingLayout};
+#[cfg(feature = "std")]
+pub use stats::ClassStats;
+pub use stream::{ByteSource, FnSource, IdxStream, SliceSource, StreamError};
+#[cfg(feature = "std")]
+pub use subset::{SubsetIter, SubsetView};
+#[cfg(feature = "std")]
+pub use summary::{SplitSummary, Summary};
+#[cfg(feature = "std")]
+pub use view::ImageSetView;
 
 // Filenames
-#[allow(dead_code)]
+#[cfg(feature = "std")]
 const TRAIN_DATA_FILENAME: &str = "train-images-idx3-ubyte";
+#[cfg(feature = "std")]
 const TEST_DATA_FILENAME: &str = "t10k-images-idx3-ubyte";
+#[cfg(feature = "std")]
 const TRAIN_LABEL_FILENAME: &str = "train-labels-idx1-ubyte";
+#[cfg(feature = "std")]
 const TEST_LABEL_FILENAME: &str = "t10k-labels-idx1-ubyte";
+#[cfg(feature = "std")]
+const IDX_FILENAMES: [&str; 4] = [
+    TRAIN_DATA_FILENAME,
+    TEST_DATA_FILENAME,
+    TRAIN_LABEL_FILENAME,
+    TEST_LABEL_FILENAME,
+];
 
 // Constants relating to the MNIST dataset. All usize for array/vec indexing.
 const IMAGES_MAGIC_NUMBER: usize = 2051;
 const LABELS_MAGIC_NUMBER: usize = 2049;
+#[cfg(feature = "std")]
 const NUM_TRAIN_IMAGES: usize = 60_000;
+#[cfg(feature = "std")]
 const NUM_TEST_IMAGES: usize = 10_000;
 const IMAGE_ROWS: usize = 28;
 const IMAGE_COLUMNS: usize = 28;
 
+/// Number of classes (digits) in the dataset.
+pub const NUM_CLASSES: usize = 10;
+
+/// The training and testing splits of the dataset.
+///
+/// Equality and hashing compare every pixel and label, so two values are equal exactly when they
+/// hold the same data.
+#[cfg(feature = "std")]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Mnist {
     // Arrays of images.
+    #[cfg_attr(feature = "serde", serde(with = "crate::interop::serde::images"))]
     pub train_data: Vec<[u8; IMAGE_ROWS * IMAGE_COLUMNS]>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::interop::serde::images"))]
     pub test_data: Vec<[u8; IMAGE_ROWS * IMAGE_COLUMNS]>,
 
     // Arrays of labels.
@@ -33,254 +231,448 @@ pub struct Mnist {
     pub test_labels: Vec<u8>,
 }
 
+/// One of the two halves of the dataset.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Split {
+    Train,
+    Test,
+}
+
+/// An owned collection of images and their labels, such as a subset carved out of [`Mnist`].
+///
+/// Like [`Mnist`], it compares and hashes by content, so equal sets can serve as cache keys.
+#[cfg(feature = "std")]
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImageSet {
+    #[cfg_attr(feature = "serde", serde(with = "crate::interop::serde::images"))]
+    pub images: Vec<[u8; IMAGE_ROWS * IMAGE_COLUMNS]>,
+    pub labels: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl ImageSet {
+    /// Number of samples in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Whether the set holds no samples.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// A sample picked uniformly at random with `rng`, as its index, image and label.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the set is empty.
+    pub fn random_sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> (usize, &[u8; IMAGE_ROWS * IMAGE_COLUMNS], u8) {
+        random_sample(&self.images, &self.labels, rng)
+    }
+}
+
+#[cfg(feature = "std")]
 impl Mnist {
     /// Load MNIST dataset.
     ///
+    /// This is [`Mnist::load`] with [`LoadOptions::new`] reading from `mnist_path`, but panics
+    /// where that returns errors.
+    ///
     /// # Panics
     ///
     /// Panics if the MNIST dataset is not present at the specified path, or if the dataset is
     /// malformed.
     #[must_use]
-    pub fn new(mnist_path: &PathBuf) -> Mnist {
-        // Get Training Data.
-        info!("Reading MNIST training data.");
-        let data_filepath = mnist_path.join(TRAIN_LABEL_FILENAME);
-        let train_data = parse_images(&data_filepath).expect(
-            &format!(
-                "Training data file \"{}\" not found; did you \
-                     remember to download and extract it?",
-                data_filepath.to_string_lossy(),
-            )[..],
-        );
-
-        // Assert that numbers extracted from the file were as expected.
-        assert_eq!(
-            train_data.magic_number, IMAGES_MAGIC_NUMBER,
-            "Magic number for training data does not match expected value."
-        );
-        assert_eq!(
-            train_data.num_images, NUM_TRAIN_IMAGES,
-            "Number of images in training data does not match expected value."
-        );
-        assert_eq!(
-            train_data.num_rows, IMAGE_ROWS,
-            "Number of rows per image in training data does not match expected value."
-        );
-        assert_eq!(
-            train_data.num_cols, IMAGE_COLUMNS,
-            "Number of columns per image in training data does not match expected value."
-        );
-
-        // Get Testing Data.
-        info!("Reading MNIST testing data.");
-        let test_filepath = mnist_path.join(TEST_DATA_FILENAME);
-        let test_data = parse_images(&test_filepath).expect(
-            &format!(
-                "Test data file \"{}\" not found; did you \
-                     remember to download and extract it?",
-                test_filepath.display()
-            )[..],
-        );
-
-        // Assert that numbers extracted from the file were as expected.
-        assert_eq!(
-            test_data.magic_number, IMAGES_MAGIC_NUMBER,
-            "Magic number for testing data does not match expected value."
-        );
-        assert_eq!(
-            test_data.num_images, NUM_TEST_IMAGES,
-            "Number of images in testing data does not match expected value."
-        );
-        assert_eq!(
-            test_data.num_rows, IMAGE_ROWS,
-            "Number of rows per image in testing data does not match expected value."
-        );
-        assert_eq!(
-            test_data.num_cols, IMAGE_COLUMNS,
-            "Number of columns per image in testing data does not match expected value."
-        );
-
-        // Get Training Labels.
-        info!("Reading MNIST training labels.");
-        let train_filepath = mnist_path.join(TRAIN_LABEL_FILENAME);
-        let (magic_number, num_labels, train_labels) = parse_labels(&train_filepath).expect(
-            &format!(
-                "Training label file \"{}\" not found; did you \
-                     remember to download and extract it?",
-                train_filepath.display()
-            )[..],
-        );
-
-        // Assert that numbers extracted from the file were as expected.
-        assert_eq!(
-            magic_number, LABELS_MAGIC_NUMBER,
-            "Magic number for training labels does not match expected value."
-        );
-        assert_eq!(
-            num_labels, NUM_TRAIN_IMAGES,
-            "Number of labels in training labels does not match expected value."
-        );
-
-        // Get Testing Labels.
-        info!("Reading MNIST testing labels.");
-        let test_filepath = mnist_path.join(TEST_LABEL_FILENAME);
-        let (magic_number, num_labels, test_labels) = parse_labels(&test_filepath).expect(
-            &format!(
-                "Test labels file \"{}\" not found; did you \
-                     remember to download and extract it?",
-                test_filepath.to_string_lossy()
-            )[..],
-        );
-
-        // Assert that numbers extracted from the file were as expected.
-        assert_eq!(
-            magic_number, LABELS_MAGIC_NUMBER,
-            "Magic number for testing labels does not match expected value."
-        );
-        assert_eq!(
-            num_labels, NUM_TEST_IMAGES,
-            "Number of labels in testing labels does not match expected value."
-        );
+    pub fn new(mnist_path: &Path) -> Mnist {
+        Mnist::load(&LoadOptions::new().cache_dir(mnist_path)).unwrap_or_else(|err| {
+            let hint = if err.kind() == io::ErrorKind::NotFound {
+                "; did you remember to download and extract it?"
+            } else {
+                ""
+            };
+            panic!(
+                "Cannot load MNIST from \"{}\"{hint} ({err})",
+                mnist_path.display()
+            )
+        })
+    }
 
-        Mnist {
-            train_data: train_data.images,
-            test_data: test_data.images,
+    /// Parse the dataset from the contents of its four IDX files, uncompressed, without touching
+    /// the file system.
+    ///
+    /// This is the way in for WebAssembly in the browser, where [`Mnist::new`] cannot read files:
+    /// a web page fetches the files, copies each `ArrayBuffer` into bytes (e.g. with
+    /// `js_sys::Uint8Array::to_vec`) and hands them over. The files are checked as
+    /// [`Mnist::new`] checks them, except that a split may hold any number of samples, as long as
+    /// it has as many labels as images.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a buffer does not hold an IDX file of 28x28 images or of labels as
+    /// expected, is shorter than its header says, holds a label of 10 or more, or if a split has
+    /// different numbers of images and labels.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::fs;
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::from_array_buffers(
+    ///     &fs::read("train-images-idx3-ubyte")?,
+    ///     &fs::read("train-labels-idx1-ubyte")?,
+    ///     &fs::read("t10k-images-idx3-ubyte")?,
+    ///     &fs::read("t10k-labels-idx1-ubyte")?,
+    /// )?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn from_array_buffers(
+        train_images: &[u8],
+        train_labels: &[u8],
+        test_images: &[u8],
+        test_labels: &[u8],
+    ) -> io::Result<Mnist> {
+        let (train_data, train_labels) = read_split(train_images, train_labels, "training")?;
+        let (test_data, test_labels) = read_split(test_images, test_labels, "testing")?;
+        Ok(Mnist {
+            train_data,
+            test_data,
             train_labels,
             test_labels,
+        })
+    }
+
+    /// Images of the given split.
+    #[must_use]
+    pub fn images(&self, split: Split) -> &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]] {
+        match split {
+            Split::Train => &self.train_data,
+            Split::Test => &self.test_data,
         }
     }
-}
 
-/// Print a sample image.
-///
-/// # Examples
-/// ```
-/// use std::path::PathBuf;
-/// use rust_mnist::{print_image, Mnist};
-///
-/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
-///
-/// // Print one image (the one at index 5).
-/// print_image(&mnist.train_data[5], mnist.train_labels[5]);
-/// ```
-pub fn print_image(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], label: u8) {
-    println!("Sample image label: {label} \nSample image:");
-
-    // Print each row.
-    for row in 0..IMAGE_ROWS {
-        for col in 0..IMAGE_COLUMNS {
-            if image[row * IMAGE_COLUMNS + col] == 0 {
-                print!("__");
-            } else {
-                print!("##");
-            }
+    /// Labels of the given split.
+    #[must_use]
+    pub fn labels(&self, split: Split) -> &[u8] {
+        match split {
+            Split::Train => &self.train_labels,
+            Split::Test => &self.test_labels,
         }
-        println!();
+    }
+
+    /// A training sample picked uniformly at random with `rng`, as its index, image and label.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no training samples.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{print_image, Mnist};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let (index, image, label) = mnist.random_train_sample(&mut rand::thread_rng());
+    /// println!("Training sample {index}:");
+    /// print_image(image, label);
+    /// ```
+    pub fn random_train_sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> (usize, &[u8; IMAGE_ROWS * IMAGE_COLUMNS], u8) {
+        random_sample(&self.train_data, &self.train_labels, rng)
+    }
+
+    /// A testing sample picked uniformly at random with `rng`, as its index, image and label.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no testing samples.
+    pub fn random_test_sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> (usize, &[u8; IMAGE_ROWS * IMAGE_COLUMNS], u8) {
+        random_sample(&self.test_data, &self.test_labels, rng)
+    }
+
+    /// Write the dataset to `dir` as the four IDX files read by [`Mnist::new`], creating `dir` if
+    /// it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or a file cannot be written.
+    pub fn to_idx(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        for filename in IDX_FILENAMES {
+            let mut writer = io::BufWriter::new(fs::File::create(dir.join(filename))?);
+            self.write_idx(filename, &mut writer)?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write the contents of the IDX file named `filename`, one of [`IDX_FILENAMES`].
+    pub(crate) fn write_idx(&self, filename: &str, writer: &mut impl Write) -> io::Result<()> {
+        match filename {
+            TRAIN_DATA_FILENAME => write_images(writer, &self.train_data),
+            TEST_DATA_FILENAME => write_images(writer, &self.test_data),
+            TRAIN_LABEL_FILENAME => write_labels(writer, &self.train_labels),
+            TEST_LABEL_FILENAME => write_labels(writer, &self.test_labels),
+            _ => unreachable!("Not an IDX filename: {}", filename),
+        }
+    }
+
+    /// Put a dataset together from its training and testing sets, the inverse of
+    /// [`Mnist::into_splits`]. The sets may be any size, such as subsets of another dataset.
+    #[must_use]
+    pub fn from_splits(train: ImageSet, test: ImageSet) -> Mnist {
+        Mnist {
+            train_data: train.images,
+            test_data: test.images,
+            train_labels: train.labels,
+            test_labels: test.labels,
+        }
+    }
+
+    /// Take the dataset apart into its training and testing sets.
+    #[must_use]
+    pub fn into_splits(self) -> (ImageSet, ImageSet) {
+        (
+            ImageSet {
+                images: self.train_data,
+                labels: self.train_labels,
+            },
+            ImageSet {
+                images: self.test_data,
+                labels: self.test_labels,
+            },
+        )
+    }
+
+    /// A copy of the first `n_train` training and `n_test` testing samples, or of the whole split
+    /// if it has fewer, such as a small dataset for fast smoke tests.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data")).take(500, 100);
+    /// assert_eq!(mnist.train_labels.len(), 500);
+    /// ```
+    #[must_use]
+    pub fn take(&self, n_train: usize, n_test: usize) -> Mnist {
+        Mnist::from_splits(
+            take(&self.train_data, &self.train_labels, n_train),
+            take(&self.test_data, &self.test_labels, n_test),
+        )
     }
 }
 
-struct MnistImages {
-    magic_number: usize,
-    num_images: usize,
-    num_rows: usize,
-    num_cols: usize,
-    images: Vec<[u8; IMAGE_ROWS * IMAGE_COLUMNS]>,
+#[cfg(feature = "std")]
+impl ImageSet {
+    /// A copy of the first `n` samples, or of the whole set if it has fewer.
+    #[must_use]
+    pub fn take(&self, n: usize) -> ImageSet {
+        take(&self.images, &self.labels, n)
+    }
+}
+
+#[cfg(feature = "std")]
+fn take(images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]], labels: &[u8], n: usize) -> ImageSet {
+    let n = n.min(images.len()).min(labels.len());
+    ImageSet {
+        images: images[..n].to_vec(),
+        labels: labels[..n].to_vec(),
+    }
+}
+
+/// Open an IDX file for reading, falling back to a zstd-compressed copy at `<filename>.zst` when
+/// the `zstd` feature is enabled.
+#[cfg(feature = "std")]
+fn open_idx(filename: &Path) -> io::Result<Box<dyn Read>> {
+    match fs::File::open(filename) {
+        Ok(file) => Ok(Box::new(io::BufReader::new(file))),
+        #[cfg(feature = "zstd")]
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            formats::zstd::open(filename).map_err(|zstd_err| {
+                // Report the uncompressed file as missing unless the compressed one is unreadable.
+                if zstd_err.kind() == io::ErrorKind::NotFound {
+                    err
+                } else {
+                    zstd_err
+                }
+            })
+        }
+        Err(err) => Err(err),
+    }
 }
 
-fn parse_images(filename: &PathBuf) -> io::Result<MnistImages> {
-    // Open the file.
-    let images_data_bytes = fs::File::open(filename)?;
-    let images_data_bytes = io::BufReader::new(images_data_bytes);
+#[cfg(feature = "std")]
+fn read_u32(reader: &mut impl Read) -> io::Result<usize> {
     let mut buffer_32: [u8; 4] = [0; 4];
+    reader.read_exact(&mut buffer_32)?;
+    Ok(usize::try_from(u32::from_be_bytes(buffer_32)).unwrap())
+}
 
-    // Get the magic number.
-    images_data_bytes
-        .get_ref()
-        .take(4)
-        .read_exact(&mut buffer_32)?;
-    let magic_number = usize::try_from(u32::from_be_bytes(buffer_32)).unwrap();
+/// Read the pixels of an IDX file of 28x28 images whose header has already been checked.
+#[cfg(feature = "std")]
+fn read_images(
+    images_data_bytes: &mut impl Read,
+) -> io::Result<Vec<[u8; IMAGE_ROWS * IMAGE_COLUMNS]>> {
+    // Skip the magic number.
+    read_u32(images_data_bytes)?;
 
     // Get number of images.
-    images_data_bytes
-        .get_ref()
-        .take(4)
-        .read_exact(&mut buffer_32)?;
-    let num_images = usize::try_from(u32::from_be_bytes(buffer_32)).unwrap();
-
-    // Get number or rows per image.
-    images_data_bytes
-        .get_ref()
-        .take(4)
-        .read_exact(&mut buffer_32)?;
-    let num_rows = usize::try_from(u32::from_be_bytes(buffer_32)).unwrap();
-
-    // Get number or columns per image.
-    images_data_bytes
-        .get_ref()
-        .take(4)
-        .read_exact(&mut buffer_32)?;
-    let num_cols = usize::try_from(u32::from_be_bytes(buffer_32)).unwrap();
-
-    // Buffer for holding image pixels.
-    let mut image_buffer: [u8; IMAGE_ROWS * IMAGE_COLUMNS] = [0; IMAGE_ROWS * IMAGE_COLUMNS];
+    let num_images = read_u32(images_data_bytes)?;
+
+    // Skip the numbers of rows and columns per image.
+    read_u32(images_data_bytes)?;
+    read_u32(images_data_bytes)?;
 
     // Vector to hold all images in the file.
-    let mut images: Vec<[u8; IMAGE_ROWS * IMAGE_COLUMNS]> = Vec::with_capacity(num_images);
-
-    // Get images from file.
-    for _image in 0..num_images {
-        images_data_bytes
-            .get_ref()
-            .take(u64::try_from(num_rows * num_cols).unwrap())
-            .read_exact(&mut image_buffer)
-            .unwrap();
-        images.push(image_buffer);
-    }
+    let mut images = vec![[0; IMAGE_ROWS * IMAGE_COLUMNS]; num_images];
+    images_data_bytes.read_exact(images.as_flattened_mut())?;
+    Ok(images)
+}
+
+/// Read the labels of an IDX file of labels whose header has already been checked.
+#[cfg(feature = "std")]
+fn read_labels(labels_data_bytes: &mut impl Read) -> io::Result<Vec<u8>> {
+    // Skip the magic number.
+    read_u32(labels_data_bytes)?;
 
-    Ok(MnistImages {
-        magic_number,
-        num_images,
-        num_rows,
-        num_cols,
-        images,
-    })
+    // Get number of labels.
+    let num_labels = read_u32(labels_data_bytes)?;
+
+    // Get labels from file.
+    let mut labels: Vec<u8> = vec![0; num_labels];
+    labels_data_bytes.read_exact(&mut labels)?;
+    check_labels(&labels)?;
+    Ok(labels)
 }
 
-fn parse_labels(filename: &PathBuf) -> io::Result<(usize, usize, Vec<u8>)> {
-    let labels_data_bytes = fs::File::open(filename)?;
-    let labels_data_bytes = io::BufReader::new(labels_data_bytes);
-    let mut buffer_32: [u8; 4] = [0; 4];
+/// Refuse labels that are not below [`NUM_CLASSES`], as every importer must: everything
+/// downstream indexes per-class tables by label.
+#[cfg(feature = "std")]
+pub(crate) fn check_labels(labels: &[u8]) -> io::Result<()> {
+    match labels
+        .iter()
+        .position(|&label| usize::from(label) >= NUM_CLASSES)
+    {
+        Some(index) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Label {} of sample {index} is not below {NUM_CLASSES}.",
+                labels[index]
+            ),
+        )),
+        None => Ok(()),
+    }
+}
 
-    // Get the magic number.
-    labels_data_bytes
-        .get_ref()
-        .take(4)
-        .read_exact(&mut buffer_32)
-        .unwrap();
-    let magic_number = usize::try_from(u32::from_be_bytes(buffer_32)).unwrap();
+#[cfg(feature = "std")]
+fn random_sample<'a, R: Rng + ?Sized>(
+    images: &'a [[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+    rng: &mut R,
+) -> (usize, &'a [u8; IMAGE_ROWS * IMAGE_COLUMNS], u8) {
+    assert!(!labels.is_empty(), "Cannot pick a sample from no samples.");
+    let index = rng.gen_range(0..labels.len());
+    (index, &images[index], labels[index])
+}
 
-    // Get number of labels.
-    labels_data_bytes
-        .get_ref()
-        .take(4)
-        .read_exact(&mut buffer_32)
-        .unwrap();
-    let num_labels = usize::try_from(u32::from_be_bytes(buffer_32)).unwrap();
+/// Parse the images and labels of one split, named `split` in errors, from the bytes of its IDX
+/// files.
+#[cfg(feature = "std")]
+fn read_split(
+    images: &[u8],
+    labels: &[u8],
+    split: &str,
+) -> io::Result<(Vec<[u8; IMAGE_ROWS * IMAGE_COLUMNS]>, Vec<u8>)> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+    // Check the headers against the buffers before parsing allocates room for the samples they
+    // declare.
+    let word = |bytes: &[u8], index: usize| -> Option<usize> {
+        let word = bytes.get(4 * index..4 * index + 4)?;
+        usize::try_from(u32::from_be_bytes([word[0], word[1], word[2], word[3]])).ok()
+    };
+    if word(images, 0) != Some(IMAGES_MAGIC_NUMBER) {
+        return Err(invalid(format!(
+            "Magic number for {split} data does not match expected value."
+        )));
+    }
+    if word(labels, 0) != Some(LABELS_MAGIC_NUMBER) {
+        return Err(invalid(format!(
+            "Magic number for {split} labels does not match expected value."
+        )));
+    }
+    if (word(images, 2), word(images, 3)) != (Some(IMAGE_ROWS), Some(IMAGE_COLUMNS)) {
+        return Err(invalid(format!(
+            "Images in {split} data are not {IMAGE_ROWS}x{IMAGE_COLUMNS}."
+        )));
+    }
+    let num_images = word(images, 1).unwrap_or_default();
+    let num_labels = word(labels, 1).unwrap_or_default();
+    if num_images != num_labels {
+        return Err(invalid(format!(
+            "There are {num_images} {split} images but {num_labels} labels."
+        )));
+    }
+    let short = |what: &str| {
+        io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!("The {split} {what} are shorter than their header says."),
+        )
+    };
+    if images.len()
+        < num_images
+            .saturating_mul(IMAGE_ROWS * IMAGE_COLUMNS)
+            .saturating_add(16)
+    {
+        return Err(short("images"));
+    }
+    if labels.len() < num_labels.saturating_add(8) {
+        return Err(short("labels"));
+    }
 
-    // Buffer for holding image label.
-    let mut label_buffer: [u8; 1] = [0; 1];
+    let images = read_images(&mut &*images)?;
+    let labels = read_labels(&mut &*labels)?;
+    Ok((images, labels))
+}
 
-    // Vector to hold all labels in the file.
-    let mut labels: Vec<u8> = Vec::with_capacity(num_labels);
+/// Write images in the IDX format read by [`Mnist::new`].
+#[cfg(feature = "std")]
+fn write_images(
+    writer: &mut impl Write,
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+) -> io::Result<()> {
+    for value in [IMAGES_MAGIC_NUMBER, images.len(), IMAGE_ROWS, IMAGE_COLUMNS] {
+        writer.write_all(&idx_u32(value)?)?;
+    }
+    writer.write_all(images.as_flattened())
+}
 
-    // Get labels from file.
-    for _label in 0..num_labels {
-        labels_data_bytes
-            .get_ref()
-            .take(1)
-            .read_exact(&mut label_buffer)
-            .unwrap();
-        labels.push(label_buffer[0]);
+/// Write labels in the IDX format read by [`Mnist::new`].
+#[cfg(feature = "std")]
+fn write_labels(writer: &mut impl Write, labels: &[u8]) -> io::Result<()> {
+    for value in [LABELS_MAGIC_NUMBER, labels.len()] {
+        writer.write_all(&idx_u32(value)?)?;
     }
-    Ok((magic_number, num_labels, labels))
+    writer.write_all(labels)
+}
+
+#[cfg(feature = "std")]
+fn idx_u32(value: usize) -> io::Result<[u8; 4]> {
+    u32::try_from(value)
+        .map(u32::to_be_bytes)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Too many samples for IDX."))
 }
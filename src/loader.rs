@@ -0,0 +1,625 @@
+//! Batching images and labels for training loops.
+
+use crate::{Mnist, Normalization, Split, StagingBuffer, StagingLayout, IMAGE_COLUMNS, IMAGE_ROWS};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::collections::BTreeMap;
+use std::panic;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+
+/// A transform applied to the pixels of a single image.
+pub(crate) type Transform<'a> = Box<dyn Fn(&mut [f32]) + Send + Sync + 'a>;
+
+/// How the pixels of a batch are arranged, and so what shape it reports.
+///
+/// MNIST images have a single channel, so all layouts share the same memory order; they differ
+/// only in the shape reported to the framework the batch is handed to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BatchLayout {
+    /// `(batch, 784)`: one flat vector per image.
+    Flat,
+    /// `(batch, channels, rows, columns)`, as expected by e.g. `PyTorch`.
+    Nchw,
+    /// `(batch, rows, columns, channels)`, as expected by e.g. `TensorFlow`.
+    Nhwc,
+}
+
+impl BatchLayout {
+    /// Shape of a batch of `len` images in this layout.
+    #[must_use]
+    pub fn shape(self, len: usize) -> Vec<usize> {
+        match self {
+            BatchLayout::Flat => vec![len, IMAGE_ROWS * IMAGE_COLUMNS],
+            BatchLayout::Nchw => vec![len, 1, IMAGE_ROWS, IMAGE_COLUMNS],
+            BatchLayout::Nhwc => vec![len, IMAGE_ROWS, IMAGE_COLUMNS, 1],
+        }
+    }
+}
+
+/// A batch of samples, with pixels [normalized](DataLoader::normalization), by default to `[0, 1]`.
+pub struct Batch {
+    /// Pixels of every image in the batch, one image after another, or nothing if they were
+    /// packed into `staging` instead.
+    pub images: Vec<f32>,
+    pub labels: Vec<u8>,
+    /// Number of samples in the batch.
+    pub len: usize,
+    /// Layout of `images`.
+    pub layout: BatchLayout,
+    /// Shape of `images` in that layout, outermost dimension first.
+    pub shape: Vec<usize>,
+    /// Pixels of every image in the batch packed for GPU upload, if the loader was asked for
+    /// them with [`DataLoader::staging`].
+    pub staging: Option<StagingBuffer>,
+}
+
+/// A snapshot of a [`DataLoader`]'s progress, for checkpointing and resuming training.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoaderState {
+    /// Number of the epoch in progress, counting from zero.
+    pub epoch: u64,
+    /// Number of samples of the epoch already handed out.
+    pub position: usize,
+    /// Seed the loader shuffles with.
+    pub seed: u64,
+}
+
+/// Splits images and labels into batches, optionally reshuffling them every epoch.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::{DataLoader, Mnist};
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+/// let mut loader = DataLoader::new(&mnist.train_data, &mnist.train_labels, 64)
+///     .shuffle(true)
+///     .seed(7)
+///     .drop_last(true)
+///     .transform(|image| image.iter_mut().for_each(|pixel| *pixel = *pixel * 2.0 - 1.0));
+///
+/// for _epoch in 0..5 {
+///     for batch in loader.epoch() {
+///         assert_eq!(batch.images.len(), 64 * 784);
+///     }
+/// }
+/// ```
+pub struct DataLoader<'a> {
+    samples: Samples<'a>,
+    batch_size: usize,
+    shuffle: bool,
+    seed: u64,
+    drop_last: bool,
+    layout: BatchLayout,
+    staging: Option<StagingLayout>,
+    normalization: Normalization,
+    transforms: Vec<Transform<'a>>,
+    workers: usize,
+    prefetch: usize,
+    epoch: u64,
+}
+
+impl DataLoader<'static> {
+    /// Create a loader like [`DataLoader::new`] over a split of a dataset it shares ownership
+    /// of, rather than borrows, so it can be moved to another thread, such as the one building
+    /// the batches of a `DataLoader::stream`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::Path;
+    /// use std::sync::Arc;
+    /// use rust_mnist::{DataLoader, Mnist, Split};
+    ///
+    /// let mnist = Arc::new(Mnist::new(Path::new("data")));
+    /// let mut loader = DataLoader::shared(Arc::clone(&mnist), Split::Train, 64).shuffle(true);
+    /// let batches = loader.epoch().count();
+    /// ```
+    #[must_use]
+    pub fn shared(mnist: Arc<Mnist>, split: Split, batch_size: usize) -> DataLoader<'static> {
+        DataLoader::with_samples(Samples::Shared(mnist, split), batch_size)
+    }
+}
+
+impl<'a> DataLoader<'a> {
+    /// Create a loader yielding batches of `batch_size` samples, in order, without transforms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero, or if there is not exactly one label per image.
+    #[must_use]
+    pub fn new(
+        images: &'a [[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+        labels: &'a [u8],
+        batch_size: usize,
+    ) -> DataLoader<'a> {
+        assert_eq!(
+            images.len(),
+            labels.len(),
+            "Number of images does not match number of labels."
+        );
+        DataLoader::with_samples(Samples::Borrowed { images, labels }, batch_size)
+    }
+
+    fn with_samples(samples: Samples<'a>, batch_size: usize) -> DataLoader<'a> {
+        assert!(batch_size > 0, "Batch size must be at least one.");
+        DataLoader {
+            samples,
+            batch_size,
+            shuffle: false,
+            seed: 0,
+            drop_last: false,
+            layout: BatchLayout::Flat,
+            staging: None,
+            normalization: Normalization::UnitRange,
+            transforms: Vec::new(),
+            workers: 0,
+            prefetch: 0,
+            epoch: 0,
+        }
+    }
+
+    /// Reshuffle the samples at the start of every epoch.
+    #[must_use]
+    pub fn shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Seed for the shuffling, so that runs can be reproduced.
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Skip the last batch of an epoch if it would hold fewer than `batch_size` samples.
+    #[must_use]
+    pub fn drop_last(mut self, drop_last: bool) -> Self {
+        self.drop_last = drop_last;
+        self
+    }
+
+    /// Layout of the batches; [`BatchLayout::Flat`] by default.
+    #[must_use]
+    pub fn layout(mut self, layout: BatchLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Collate every batch straight into an aligned [`StagingBuffer`] with padded rows, ready to
+    /// be copied into a GPU staging buffer without repacking, instead of into
+    /// [`Batch::images`], which is left empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the alignment is not a power of two, or if the row pitch is too small for a row
+    /// of `f32` pixels or not a multiple of the pixel size.
+    #[must_use]
+    pub fn staging(mut self, layout: StagingLayout) -> Self {
+        layout.validate();
+        self.staging = Some(layout);
+        self
+    }
+
+    /// How pixels are turned into `f32` values; [`Normalization::UnitRange`] by default.
+    #[must_use]
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Add a transform applied to the pixels of every image, after
+    /// [normalizing](DataLoader::normalization) them. Transforms run in the order they were
+    /// added.
+    #[must_use]
+    pub fn transform(mut self, transform: impl Fn(&mut [f32]) + Send + Sync + 'a) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Build batches on `workers` threads, so transforms run concurrently. Batches are still
+    /// yielded in order. With zero workers (the default) batches are built on the calling thread.
+    ///
+    /// Only epochs started with [`DataLoader::epoch_in`] or [`DataLoader::resume_in`], or
+    /// streamed with `DataLoader::stream`, use the workers, as they need a scope to run in;
+    /// [`DataLoader::epoch`] and [`DataLoader::resume`] debug-assert that none are set.
+    #[must_use]
+    pub fn workers(mut self, workers: usize) -> Self {
+        self.workers = workers;
+        self
+    }
+
+    /// Number of batches the workers may build ahead of the consumer, bounding the memory they
+    /// hold; defaults to one per worker.
+    #[must_use]
+    pub fn prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    /// Number of batches in each epoch.
+    #[must_use]
+    pub fn num_batches(&self) -> usize {
+        if self.drop_last {
+            self.samples.labels().len() / self.batch_size
+        } else {
+            self.samples.labels().len().div_ceil(self.batch_size)
+        }
+    }
+
+    /// Start the next epoch, returning an iterator over its batches, built on the calling thread
+    /// as they are asked for.
+    ///
+    /// In debug builds, panics if [workers](DataLoader::workers) are set, as they would go
+    /// unused; start the epoch with [`DataLoader::epoch_in`] to use them.
+    pub fn epoch(&mut self) -> Epoch<'_, 'a> {
+        self.assert_no_workers();
+        self.start_epoch(0)
+    }
+
+    /// Start the next epoch with its batches built ahead of the consumer by the
+    /// [workers](DataLoader::workers), which are spawned in `scope` and run until the epoch is
+    /// dropped.
+    ///
+    /// The workers take batches in order as they are free, and the epoch puts them back in
+    /// order, so it yields exactly the batches [`DataLoader::epoch`] would. At most
+    /// [`prefetch`](DataLoader::prefetch) batches are built or waiting at any time. A panic in a
+    /// transform is passed on to the consumer.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::thread;
+    /// use rust_mnist::DataLoader;
+    ///
+    /// let images: Vec<[u8; 784]> = (0..100).map(|i| [i as u8; 784]).collect();
+    /// let labels: Vec<u8> = (0..100).map(|i| i % 10).collect();
+    /// let loader = || {
+    ///     DataLoader::new(&images, &labels, 8)
+    ///         .shuffle(true)
+    ///         .transform(|image| image.iter_mut().for_each(|pixel| *pixel = pixel.sqrt()))
+    /// };
+    ///
+    /// let in_order: Vec<Vec<u8>> = loader().epoch().map(|batch| batch.labels).collect();
+    /// let mut prefetching = loader().workers(4).prefetch(8);
+    /// thread::scope(|scope| {
+    ///     let prefetched: Vec<Vec<u8>> =
+    ///         prefetching.epoch_in(scope).map(|batch| batch.labels).collect();
+    ///     assert_eq!(prefetched, in_order);
+    /// });
+    /// ```
+    pub fn epoch_in<'scope>(
+        &'scope mut self,
+        scope: &'scope thread::Scope<'scope, '_>,
+    ) -> Epoch<'scope, 'a> {
+        self.start_epoch(0).prefetch_in(scope)
+    }
+
+    /// Continue from a state saved with [`Epoch::state`], partway through an epoch.
+    ///
+    /// The loader must be configured the same way as the one the state was saved from, apart
+    /// from the seed, which is restored from the state.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{DataLoader, Mnist};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let mut loader = DataLoader::new(&mnist.train_data, &mnist.train_labels, 64).shuffle(true);
+    /// let mut epoch = loader.epoch();
+    /// epoch.next();
+    /// let state = epoch.state();
+    ///
+    /// // Later, perhaps in another process:
+    /// let mut loader = DataLoader::new(&mnist.train_data, &mnist.train_labels, 64).shuffle(true);
+    /// for batch in loader.resume(state) {
+    ///     // Carries on with the second batch of the first epoch.
+    /// }
+    /// ```
+    ///
+    /// In debug builds, panics if [workers](DataLoader::workers) are set, as for
+    /// [`DataLoader::epoch`]; resume with [`DataLoader::resume_in`] to use them.
+    pub fn resume(&mut self, state: LoaderState) -> Epoch<'_, 'a> {
+        self.assert_no_workers();
+        self.resume_epoch(state)
+    }
+
+    /// Continue from a state saved with [`Epoch::state`], like [`DataLoader::resume`], with the
+    /// batches built ahead by workers spawned in `scope`, like [`DataLoader::epoch_in`].
+    pub fn resume_in<'scope>(
+        &'scope mut self,
+        state: LoaderState,
+        scope: &'scope thread::Scope<'scope, '_>,
+    ) -> Epoch<'scope, 'a> {
+        self.resume_epoch(state).prefetch_in(scope)
+    }
+
+    /// Catch workers set on a loader whose epochs are built on the calling thread.
+    fn assert_no_workers(&self) {
+        debug_assert!(
+            self.workers == 0,
+            "DataLoader workers are only used by epoch_in, resume_in and stream."
+        );
+    }
+
+    /// Restore the seed and epoch number of `state` and start that epoch where it left off.
+    fn resume_epoch(&mut self, state: LoaderState) -> Epoch<'_, 'a> {
+        self.seed = state.seed;
+        self.epoch = state.epoch;
+        self.start_epoch(state.position.min(self.samples.labels().len()))
+    }
+
+    /// Start the next epoch at `position` samples in.
+    fn start_epoch(&mut self, position: usize) -> Epoch<'_, 'a> {
+        let epoch = self.epoch;
+        let mut order: Vec<usize> = (0..self.samples.labels().len()).collect();
+        if self.shuffle {
+            // Derive each epoch's shuffle from the seed alone, with a portable generator, so any
+            // epoch can be reproduced.
+            let seed = self.seed ^ epoch.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+            order.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+        }
+        self.epoch += 1;
+
+        Epoch {
+            loader: self,
+            number: epoch,
+            order,
+            position,
+            prefetch: None,
+        }
+    }
+
+    /// Number of batches built ahead of the consumer.
+    pub(crate) fn prefetch_depth(&self) -> usize {
+        if self.prefetch == 0 {
+            self.workers
+        } else {
+            self.prefetch
+        }
+    }
+
+    /// Gather and transform the samples at `indices` into a batch.
+    fn collate(&self, indices: &[usize]) -> Batch {
+        let (samples, sample_labels) = (self.samples.images(), self.samples.labels());
+        let mut labels = Vec::with_capacity(indices.len());
+        let mut images = Vec::new();
+        let mut staging = self
+            .staging
+            .map(|layout| StagingBuffer::zeroed(indices.len(), layout));
+        if staging.is_none() {
+            images.reserve_exact(indices.len() * IMAGE_ROWS * IMAGE_COLUMNS);
+        }
+
+        // Transform each image in place, then pack it into the staging buffer if there is one.
+        let mut staged = [0.0; IMAGE_ROWS * IMAGE_COLUMNS];
+        for (position, &index) in indices.iter().enumerate() {
+            let pixels = samples[index]
+                .iter()
+                .map(|&pixel| self.normalization.apply(pixel));
+            let image = if staging.is_some() {
+                staged
+                    .iter_mut()
+                    .zip(pixels)
+                    .for_each(|(to, from)| *to = from);
+                &mut staged[..]
+            } else {
+                let start = images.len();
+                images.extend(pixels);
+                &mut images[start..]
+            };
+            for transform in &self.transforms {
+                transform(image);
+            }
+            if let Some(staging) = &mut staging {
+                staging.write_image(position, &staged);
+            }
+            labels.push(sample_labels[index]);
+        }
+
+        Batch {
+            staging,
+            images,
+            labels,
+            len: indices.len(),
+            layout: self.layout,
+            shape: self.layout.shape(indices.len()),
+        }
+    }
+}
+
+/// The samples a [`DataLoader`] batches.
+enum Samples<'a> {
+    Borrowed {
+        images: &'a [[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+        labels: &'a [u8],
+    },
+    Shared(Arc<Mnist>, Split),
+}
+
+impl Samples<'_> {
+    fn images(&self) -> &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]] {
+        match self {
+            Samples::Borrowed { images, .. } => images,
+            Samples::Shared(mnist, split) => mnist.images(*split),
+        }
+    }
+
+    fn labels(&self) -> &[u8] {
+        match self {
+            Samples::Borrowed { labels, .. } => labels,
+            Samples::Shared(mnist, split) => mnist.labels(*split),
+        }
+    }
+}
+
+/// Iterator over the batches of one epoch, created by [`DataLoader::epoch`] or
+/// [`DataLoader::epoch_in`].
+pub struct Epoch<'l, 'a> {
+    loader: &'l DataLoader<'a>,
+    number: u64,
+    order: Vec<usize>,
+    /// Number of samples taken for batches, whether handed out or still with the workers.
+    position: usize,
+    prefetch: Option<Prefetch>,
+}
+
+/// The consumer's side of the workers building an epoch's batches ahead of it.
+struct Prefetch {
+    /// Batches for the workers to build, numbered from the start of the epoch (or resume).
+    tickets: mpsc::SyncSender<(usize, Vec<usize>)>,
+    /// Batches the workers have built, in whatever order they finished.
+    built: mpsc::Receiver<(usize, thread::Result<Batch>)>,
+    /// Built batches waiting for those before them.
+    ready: BTreeMap<usize, Batch>,
+    /// Number of batches handed to the workers.
+    issued: usize,
+    /// Number of batches handed out.
+    yielded: usize,
+    /// Number of samples in batches handed to the workers but not handed out yet.
+    ahead: usize,
+}
+
+impl<'l, 'a> Epoch<'l, 'a> {
+    /// Where the epoch has got to, for resuming it later with [`DataLoader::resume`].
+    #[must_use]
+    pub fn state(&self) -> LoaderState {
+        let ahead = self.prefetch.as_ref().map_or(0, |prefetch| prefetch.ahead);
+        LoaderState {
+            epoch: self.number,
+            position: self.position - ahead,
+            seed: self.loader.seed,
+        }
+    }
+
+    /// Take the indices of the next batch, or `None` at the end of the epoch.
+    fn next_indices(&mut self) -> Option<&[usize]> {
+        let remaining = self.order.len() - self.position;
+        let len = remaining.min(self.loader.batch_size);
+        if len == 0 || (self.loader.drop_last && len < self.loader.batch_size) {
+            return None;
+        }
+
+        self.position += len;
+        Some(&self.order[self.position - len..self.position])
+    }
+
+    /// Spawn the loader's workers in `scope` and hand them the first batches to build.
+    fn prefetch_in(mut self, scope: &'l thread::Scope<'l, '_>) -> Self
+    where
+        'a: 'l,
+    {
+        let loader = self.loader;
+        if loader.workers == 0 {
+            return self;
+        }
+        let depth = loader.prefetch_depth();
+
+        // Every ticket sent has room, as no more than `depth` batches are ever outstanding.
+        let (tickets, queue) = mpsc::sync_channel::<(usize, Vec<usize>)>(depth);
+        let (finished, built) = mpsc::channel();
+        let queue = Arc::new(Mutex::new(queue));
+        for _ in 0..loader.workers {
+            let queue = Arc::clone(&queue);
+            let finished = finished.clone();
+            scope.spawn(move || loop {
+                // Dropping the epoch drops the sender, which stops the workers.
+                let ticket = queue.lock().unwrap_or_else(PoisonError::into_inner).recv();
+                let Ok((number, indices)) = ticket else { break };
+                let batch =
+                    panic::catch_unwind(panic::AssertUnwindSafe(|| loader.collate(&indices)));
+                if finished.send((number, batch)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        self.prefetch = Some(Prefetch {
+            tickets,
+            built,
+            ready: BTreeMap::new(),
+            issued: 0,
+            yielded: 0,
+            ahead: 0,
+        });
+        for _ in 0..depth {
+            self.issue();
+        }
+        self
+    }
+
+    /// Hand the workers the next batch to build, if the epoch has one left.
+    fn issue(&mut self) {
+        let Some(indices) = self.next_indices().map(<[usize]>::to_vec) else {
+            return;
+        };
+        let prefetch = self
+            .prefetch
+            .as_mut()
+            .expect("Only prefetching epochs issue batches.");
+        prefetch.ahead += indices.len();
+        prefetch
+            .tickets
+            .send((prefetch.issued, indices))
+            .expect("DataLoader workers stopped.");
+        prefetch.issued += 1;
+    }
+
+    /// Wait for the workers to build the next batch in order, or `None` at the end of the epoch.
+    fn next_prefetched(&mut self) -> Option<Batch> {
+        let prefetch = self.prefetch.as_mut()?;
+        let batch = loop {
+            if let Some(batch) = prefetch.ready.remove(&prefetch.yielded) {
+                break batch;
+            }
+            if prefetch.yielded == prefetch.issued {
+                return None;
+            }
+            match prefetch.built.recv().expect("DataLoader workers stopped.") {
+                (number, Ok(batch)) => {
+                    prefetch.ready.insert(number, batch);
+                }
+                (_, Err(payload)) => panic::resume_unwind(payload),
+            }
+        };
+        prefetch.yielded += 1;
+        prefetch.ahead -= batch.len;
+        self.issue();
+        Some(batch)
+    }
+}
+
+impl Iterator for Epoch<'_, '_> {
+    type Item = Batch;
+
+    fn next(&mut self) -> Option<Batch> {
+        if self.prefetch.is_some() {
+            return self.next_prefetched();
+        }
+        let loader = self.loader;
+        self.next_indices().map(|indices| loader.collate(indices))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.order.len() - self.position;
+        let batches = if self.loader.drop_last {
+            remaining / self.loader.batch_size
+        } else {
+            remaining.div_ceil(self.loader.batch_size)
+        };
+        let ahead = self
+            .prefetch
+            .as_ref()
+            .map_or(0, |prefetch| prefetch.issued - prefetch.yielded);
+        (batches + ahead, Some(batches + ahead))
+    }
+}
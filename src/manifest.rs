@@ -0,0 +1,394 @@
+//! A record of how a derived dataset was made, for reproducing it exactly.
+
+use crate::json::{invalid_data, write_string, JsonReader};
+use crate::summary::checksum;
+use crate::{Augmentation, LoadOptions, Mnist, Normalization};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Everything needed to make a derived dataset again: the checksums of the source data, the
+/// version of this crate, the random seeds, the split ratios, the normalization and the
+/// transforms applied, in order.
+///
+/// Transforms are recorded as [`Augmentation`]s, since closures cannot be saved, so that
+/// [`Manifest::apply`] replays them. Saved as JSON, a manifest reads back unchanged with
+/// [`Manifest::load`], and [`Manifest::check`] confirms that freshly loaded source data is what
+/// it was made from.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::{Path, PathBuf};
+/// use rust_mnist::{Augmentation, LoadOptions, Manifest, Mnist, Normalization, Split};
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+/// let manifest = Manifest::new(&mnist)
+///     .seed("shuffle", 7)
+///     .split_ratio("validation", 0.1)
+///     .normalization(Normalization::MNIST)
+///     .transform(Augmentation::Shift { dx: 1, dy: 0 });
+/// manifest.save(Path::new("manifest.json"))?;
+///
+/// // Later, perhaps on another machine:
+/// let manifest = Manifest::load(Path::new("manifest.json"))?;
+/// for problem in manifest.check(&mnist) {
+///     eprintln!("{problem}");
+/// }
+/// let shuffle_seed = manifest.seeds["shuffle"];
+/// let options = manifest.apply(LoadOptions::new());
+/// let batches = options.data_loader(&mnist, Split::Train, 64);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Manifest {
+    /// Version of this crate that wrote the manifest, and so of its download registry and
+    /// transforms.
+    pub version: String,
+    /// Checksum of the source training split, as in [`SplitSummary::checksum`](crate::SplitSummary::checksum).
+    pub train_checksum: u64,
+    /// Checksum of the source testing split.
+    pub test_checksum: u64,
+    /// Random seeds by what they were used for, such as `"shuffle"`.
+    pub seeds: BTreeMap<String, u64>,
+    /// Split ratios by name, such as `"validation"`.
+    pub split_ratios: BTreeMap<String, f64>,
+    /// How pixels were turned into `f32` values.
+    pub normalization: Normalization,
+    /// Transforms applied to the normalized images, in the order they were applied.
+    pub transforms: Vec<Augmentation>,
+}
+
+impl Manifest {
+    /// A manifest for data derived from `source`, with no seeds, ratios or transforms yet, and
+    /// the default normalization of [`LoadOptions`].
+    #[must_use]
+    pub fn new(source: &Mnist) -> Manifest {
+        Manifest {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            train_checksum: checksum(&source.train_data, &source.train_labels),
+            test_checksum: checksum(&source.test_data, &source.test_labels),
+            seeds: BTreeMap::new(),
+            split_ratios: BTreeMap::new(),
+            normalization: Normalization::UnitRange,
+            transforms: Vec::new(),
+        }
+    }
+
+    /// Record the random seed used for `name`.
+    #[must_use]
+    pub fn seed(mut self, name: impl Into<String>, seed: u64) -> Self {
+        self.seeds.insert(name.into(), seed);
+        self
+    }
+
+    /// Record the ratio of the split `name`.
+    #[must_use]
+    pub fn split_ratio(mut self, name: impl Into<String>, ratio: f64) -> Self {
+        self.split_ratios.insert(name.into(), ratio);
+        self
+    }
+
+    /// Record the normalization used.
+    #[must_use]
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Record a transform applied after those already recorded.
+    #[must_use]
+    pub fn transform(mut self, transform: Augmentation) -> Self {
+        self.transforms.push(transform);
+        self
+    }
+
+    /// `options` with the recorded normalization and transforms, added after any transforms
+    /// they already have, so that [`LoadOptions::data_loader`] makes the derived data again.
+    #[must_use]
+    pub fn apply(&self, options: LoadOptions) -> LoadOptions {
+        self.transforms.iter().fold(
+            options.normalization(self.normalization),
+            |options, &transform| options.transform(move |image| transform.apply(image)),
+        )
+    }
+
+    /// Problems that stop `source` from reproducing the derived data: source checksums that do
+    /// not match, or a different crate version. An empty list means it can be reproduced.
+    #[must_use]
+    pub fn check(&self, source: &Mnist) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (name, expected, actual) in [
+            (
+                "training",
+                self.train_checksum,
+                checksum(&source.train_data, &source.train_labels),
+            ),
+            (
+                "testing",
+                self.test_checksum,
+                checksum(&source.test_data, &source.test_labels),
+            ),
+        ] {
+            if expected != actual {
+                problems.push(format!(
+                    "The {name} data has checksum {actual:016x} rather than {expected:016x}."
+                ));
+            }
+        }
+        let version = env!("CARGO_PKG_VERSION");
+        if self.version != version {
+            problems.push(format!(
+                "The manifest was written by version {} rather than {version}.",
+                self.version
+            ));
+        }
+        problems
+    }
+
+    /// Write the manifest to `path` as JSON:
+    ///
+    /// ```json
+    /// {
+    ///   "version": "0.2.0",
+    ///   "checksums": {"train": "8c8e3f0b2a7d9e11", "test": "5d2c0a96e4f1b873"},
+    ///   "seeds": {"shuffle": 7},
+    ///   "split_ratios": {"validation": 0.1},
+    ///   "normalization": {"kind": "standardize", "mean": 0.1307, "std": 0.3081},
+    ///   "transforms": [{"kind": "shift", "dx": 1, "dy": 0}, {"kind": "flip_horizontal"}]
+    /// }
+    /// ```
+    ///
+    /// Checksums are hexadecimal strings, since JSON numbers cannot hold every `u64` exactly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written or a split ratio or other number is not
+    /// finite.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_json()?)
+    }
+
+    /// Read a manifest written by [`Manifest::save`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a manifest.
+    pub fn load(path: &Path) -> io::Result<Manifest> {
+        Manifest::from_json(&fs::read_to_string(path)?)
+    }
+
+    fn to_json(&self) -> io::Result<String> {
+        let mut json = String::from("{\n  \"version\": ");
+        write_string(&mut json, &self.version);
+        let _ = write!(
+            json,
+            ",\n  \"checksums\": {{\"train\": \"{:016x}\", \"test\": \"{:016x}\"}}",
+            self.train_checksum, self.test_checksum
+        );
+
+        json.push_str(",\n  \"seeds\": {");
+        for (i, (name, seed)) in self.seeds.iter().enumerate() {
+            json.push_str(if i == 0 { "" } else { ", " });
+            write_string(&mut json, name);
+            let _ = write!(json, ": {seed}");
+        }
+
+        json.push_str("},\n  \"split_ratios\": {");
+        for (i, (name, ratio)) in self.split_ratios.iter().enumerate() {
+            if !ratio.is_finite() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "JSON cannot hold non-finite split ratios.",
+                ));
+            }
+            json.push_str(if i == 0 { "" } else { ", " });
+            write_string(&mut json, name);
+            // `f64`'s `Display` prints the shortest text that reads back as the same value.
+            let _ = write!(json, ": {ratio}");
+        }
+
+        json.push_str("},\n  \"normalization\": ");
+        write_normalization(&mut json, self.normalization)?;
+        json.push_str(",\n  \"transforms\": [");
+        for (i, &transform) in self.transforms.iter().enumerate() {
+            json.push_str(if i == 0 { "" } else { ", " });
+            write_augmentation(&mut json, transform)?;
+        }
+        json.push_str("]\n}\n");
+        Ok(json)
+    }
+
+    fn from_json(text: &str) -> io::Result<Manifest> {
+        let mut json = JsonReader::new(text);
+        let (mut version, mut train_checksum, mut test_checksum) = (None, None, None);
+        let mut seeds = BTreeMap::new();
+        let mut split_ratios = BTreeMap::new();
+        let mut normalization = Normalization::UnitRange;
+        let mut transforms = Vec::new();
+        json.object(|json, key| {
+            match key {
+                "version" => version = Some(json.string()?),
+                "checksums" => json.object(|json, split| {
+                    let checksum = u64::from_str_radix(&json.string()?, 16)
+                        .map_err(|_| invalid_data("Invalid checksum in manifest."))?;
+                    match split {
+                        "train" => train_checksum = Some(checksum),
+                        "test" => test_checksum = Some(checksum),
+                        _ => return Err(invalid_data(format!("Unexpected split \"{split}\"."))),
+                    }
+                    Ok(())
+                })?,
+                "seeds" => json.object(|json, name| {
+                    seeds.insert(name.to_string(), json.number()?);
+                    Ok(())
+                })?,
+                "split_ratios" => json.object(|json, name| {
+                    split_ratios.insert(name.to_string(), json.number()?);
+                    Ok(())
+                })?,
+                "normalization" => normalization = read_normalization(json)?,
+                "transforms" => transforms = json.array(read_augmentation)?,
+                _ => return Err(invalid_data(format!("Unexpected key \"{key}\"."))),
+            }
+            Ok(())
+        })?;
+        json.end()?;
+
+        match (version, train_checksum, test_checksum) {
+            (Some(version), Some(train_checksum), Some(test_checksum)) => Ok(Manifest {
+                version,
+                train_checksum,
+                test_checksum,
+                seeds,
+                split_ratios,
+                normalization,
+                transforms,
+            }),
+            _ => Err(invalid_data(
+                "Manifest needs \"version\" and both \"checksums\".",
+            )),
+        }
+    }
+}
+
+/// Append `normalization` to `json` as an object naming its kind.
+fn write_normalization(json: &mut String, normalization: Normalization) -> io::Result<()> {
+    let kind = match normalization {
+        Normalization::Raw => "raw",
+        Normalization::UnitRange => "unit_range",
+        Normalization::SymmetricRange => "symmetric_range",
+        Normalization::Standardize { .. } => "standardize",
+    };
+    let _ = write!(json, "{{\"kind\": \"{kind}\"");
+    if let Normalization::Standardize { mean, std } = normalization {
+        write_number(json, "mean", mean)?;
+        write_number(json, "std", std)?;
+    }
+    json.push('}');
+    Ok(())
+}
+
+/// Append `augmentation` to `json` as an object naming its kind.
+fn write_augmentation(json: &mut String, augmentation: Augmentation) -> io::Result<()> {
+    match augmentation {
+        Augmentation::Shift { dx, dy } => {
+            let _ = write!(json, "{{\"kind\": \"shift\", \"dx\": {dx}, \"dy\": {dy}");
+        }
+        Augmentation::FlipHorizontal => json.push_str("{\"kind\": \"flip_horizontal\""),
+        Augmentation::Clamp { min, max } => {
+            json.push_str("{\"kind\": \"clamp\"");
+            write_number(json, "min", min)?;
+            write_number(json, "max", max)?;
+        }
+    }
+    json.push('}');
+    Ok(())
+}
+
+/// Append `, "name": value` to `json`.
+fn write_number(json: &mut String, name: &str, value: f32) -> io::Result<()> {
+    if !value.is_finite() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("JSON cannot hold the non-finite {name} {value}."),
+        ));
+    }
+    // `f32`'s `Display` prints the shortest text that reads back as the same value.
+    let _ = write!(json, ", \"{name}\": {value}");
+    Ok(())
+}
+
+/// Fields of a normalization or augmentation, read before knowing which kind it is.
+#[derive(Default)]
+struct Fields {
+    kind: String,
+    dx: Option<i32>,
+    dy: Option<i32>,
+    mean: Option<f32>,
+    std: Option<f32>,
+    min: Option<f32>,
+    max: Option<f32>,
+}
+
+impl Fields {
+    fn read(json: &mut JsonReader<'_>) -> io::Result<Fields> {
+        let mut fields = Fields::default();
+        json.object(|json, key| {
+            match key {
+                "kind" => fields.kind = json.string()?,
+                "dx" => fields.dx = Some(json.number()?),
+                "dy" => fields.dy = Some(json.number()?),
+                "mean" => fields.mean = Some(json.number()?),
+                "std" => fields.std = Some(json.number()?),
+                "min" => fields.min = Some(json.number()?),
+                "max" => fields.max = Some(json.number()?),
+                _ => return Err(invalid_data(format!("Unexpected key \"{key}\"."))),
+            }
+            Ok(())
+        })?;
+        Ok(fields)
+    }
+
+    /// `value`, the field `name`, or an error if it is missing.
+    fn needed<T>(&self, value: Option<T>, name: &str) -> io::Result<T> {
+        value.ok_or_else(|| {
+            invalid_data(format!(
+                "The {} transform in the manifest needs \"{name}\".",
+                self.kind
+            ))
+        })
+    }
+}
+
+fn read_normalization(json: &mut JsonReader<'_>) -> io::Result<Normalization> {
+    let fields = Fields::read(json)?;
+    match fields.kind.as_str() {
+        "raw" => Ok(Normalization::Raw),
+        "unit_range" => Ok(Normalization::UnitRange),
+        "symmetric_range" => Ok(Normalization::SymmetricRange),
+        "standardize" => Ok(Normalization::Standardize {
+            mean: fields.needed(fields.mean, "mean")?,
+            std: fields.needed(fields.std, "std")?,
+        }),
+        kind => Err(invalid_data(format!("Unknown normalization \"{kind}\"."))),
+    }
+}
+
+fn read_augmentation(json: &mut JsonReader<'_>) -> io::Result<Augmentation> {
+    let fields = Fields::read(json)?;
+    match fields.kind.as_str() {
+        "shift" => Ok(Augmentation::Shift {
+            dx: fields.needed(fields.dx, "dx")?,
+            dy: fields.needed(fields.dy, "dy")?,
+        }),
+        "flip_horizontal" => Ok(Augmentation::FlipHorizontal),
+        "clamp" => Ok(Augmentation::Clamp {
+            min: fields.needed(fields.min, "min")?,
+            max: fields.needed(fields.max, "max")?,
+        }),
+        kind => Err(invalid_data(format!("Unknown transform \"{kind}\"."))),
+    }
+}
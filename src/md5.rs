@@ -0,0 +1,136 @@
+//! MD5 digests, as published alongside the datasets' files to check downloads against.
+//!
+//! MD5 is long broken as a cryptographic hash, but is what the mirrors publish; it is only used
+//! here to catch damaged or substituted files, not to defend against tampering.
+
+use std::convert::TryFrom;
+use std::fmt::Write;
+use std::io;
+use std::io::Read;
+
+/// Per-round left rotations.
+const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// Per-round constants: the integer part of `abs(sin(i + 1)) * 2^32`.
+#[rustfmt::skip]
+const CONSTANTS: [u32; 64] = [
+    0xd76a_a478, 0xe8c7_b756, 0x2420_70db, 0xc1bd_ceee,
+    0xf57c_0faf, 0x4787_c62a, 0xa830_4613, 0xfd46_9501,
+    0x6980_98d8, 0x8b44_f7af, 0xffff_5bb1, 0x895c_d7be,
+    0x6b90_1122, 0xfd98_7193, 0xa679_438e, 0x49b4_0821,
+    0xf61e_2562, 0xc040_b340, 0x265e_5a51, 0xe9b6_c7aa,
+    0xd62f_105d, 0x0244_1453, 0xd8a1_e681, 0xe7d3_fbc8,
+    0x21e1_cde6, 0xc337_07d6, 0xf4d5_0d87, 0x455a_14ed,
+    0xa9e3_e905, 0xfcef_a3f8, 0x676f_02d9, 0x8d2a_4c8a,
+    0xfffa_3942, 0x8771_f681, 0x6d9d_6122, 0xfde5_380c,
+    0xa4be_ea44, 0x4bde_cfa9, 0xf6bb_4b60, 0xbebf_bc70,
+    0x289b_7ec6, 0xeaa1_27fa, 0xd4ef_3085, 0x0488_1d05,
+    0xd9d4_d039, 0xe6db_99e5, 0x1fa2_7cf8, 0xc4ac_5665,
+    0xf429_2244, 0x432a_ff97, 0xab94_23a7, 0xfc93_a039,
+    0x655b_59c3, 0x8f0c_cc92, 0xffef_f47d, 0x8584_5dd1,
+    0x6fa8_7e4f, 0xfe2c_e6e0, 0xa301_4314, 0x4e08_11a1,
+    0xf753_7e82, 0xbd3a_f235, 0x2ad7_d2bb, 0xeb86_d391,
+];
+
+/// Running MD5 digest of the bytes fed to it.
+#[derive(Clone, Debug)]
+pub(crate) struct Md5 {
+    state: [u32; 4],
+    block: [u8; 64],
+    buffered: usize,
+    len: u64,
+}
+
+impl Md5 {
+    pub(crate) fn new() -> Md5 {
+        Md5 {
+            state: [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476],
+            block: [0; 64],
+            buffered: 0,
+            len: 0,
+        }
+    }
+
+    /// Digest of everything `reader` holds.
+    pub(crate) fn of_reader(reader: &mut impl Read) -> io::Result<Md5> {
+        let mut md5 = Md5::new();
+        io::copy(reader, &mut md5)?;
+        Ok(md5)
+    }
+
+    pub(crate) fn update(&mut self, mut bytes: &[u8]) {
+        self.len = self.len.wrapping_add(bytes.len() as u64);
+        while !bytes.is_empty() {
+            let taken = bytes.len().min(64 - self.buffered);
+            self.block[self.buffered..self.buffered + taken].copy_from_slice(&bytes[..taken]);
+            self.buffered += taken;
+            bytes = &bytes[taken..];
+            if self.buffered == 64 {
+                let block = self.block;
+                self.compress(&block);
+                self.buffered = 0;
+            }
+        }
+    }
+
+    /// The digest as the 32 lowercase hexadecimal digits it is usually published as.
+    pub(crate) fn hex(mut self) -> String {
+        let bits = self.len.wrapping_mul(8);
+        self.update(&[0x80]);
+        while self.buffered != 56 {
+            self.update(&[0]);
+        }
+        self.update(&bits.to_le_bytes());
+
+        let mut hex = String::with_capacity(32);
+        for byte in self.state.iter().flat_map(|word| word.to_le_bytes()) {
+            let _ = write!(hex, "{byte:02x}");
+        }
+        hex
+    }
+
+    fn compress(&mut self, block: &[u8; 64]) {
+        let mut words = [0u32; 16];
+        for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes(<[u8; 4]>::try_from(bytes).expect("Chunks are four bytes."));
+        }
+
+        let [mut a, mut b, mut c, mut d] = self.state;
+        for round in 0..64 {
+            let (mixed, index) = match round / 16 {
+                0 => ((b & c) | (!b & d), round),
+                1 => ((d & b) | (!d & c), (5 * round + 1) % 16),
+                2 => (b ^ c ^ d, (3 * round + 5) % 16),
+                _ => (c ^ (b | !d), 7 * round % 16),
+            };
+            let rotated = a
+                .wrapping_add(mixed)
+                .wrapping_add(CONSTANTS[round])
+                .wrapping_add(words[index])
+                .rotate_left(SHIFTS[round]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(rotated);
+        }
+
+        for (state, value) in self.state.iter_mut().zip([a, b, c, d]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+}
+
+impl io::Write for Md5 {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.update(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+//! How much memory the dataset takes, and what copies of it would take.
+
+use crate::summary::tenths;
+use crate::{Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use std::fmt;
+use std::mem::size_of;
+
+/// Memory taken by a loaded dataset, returned by [`Mnist::memory_usage`]. Its
+/// [`Display`](fmt::Display) implementation prints a table in MiB.
+///
+/// [`Mnist`] keeps no caches: it holds each split's pixels as bytes and nothing else. The
+/// `f32_copy` and `bit_packed_copy` figures are what the common derived copies of a split would
+/// add, such as one whole split of normalized [`Batch`](crate::Batch) pixels or images
+/// binarized to one bit per pixel, so their cost can be judged before making them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryReport {
+    /// Memory of the training split.
+    pub train: SplitMemory,
+    /// Memory of the testing split.
+    pub test: SplitMemory,
+}
+
+/// Memory taken by one split of the dataset, part of a [`MemoryReport`]. All sizes are in bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SplitMemory {
+    /// Pixels of the images held.
+    pub images: usize,
+    /// Labels held.
+    pub labels: usize,
+    /// Memory allocated for images and labels, including spare capacity of their vectors.
+    pub allocated: usize,
+    /// Size of a copy of the images with `f32` pixels.
+    pub f32_copy: usize,
+    /// Size of a copy of the images binarized and packed eight pixels to a byte.
+    pub bit_packed_copy: usize,
+}
+
+impl MemoryReport {
+    /// Memory allocated by both splits.
+    #[must_use]
+    pub fn allocated(&self) -> usize {
+        self.train.allocated + self.test.allocated
+    }
+}
+
+impl Mnist {
+    /// Measure the memory taken by each split, and what copies of it would take.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let report = mnist.memory_usage();
+    /// print!("{report}");
+    /// assert!(report.train.f32_copy < 256 << 20);
+    /// ```
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryReport {
+        MemoryReport {
+            train: self.split_memory(Split::Train),
+            test: self.split_memory(Split::Test),
+        }
+    }
+
+    fn split_memory(&self, split: Split) -> SplitMemory {
+        let (images, labels) = match split {
+            Split::Train => (&self.train_data, &self.train_labels),
+            Split::Test => (&self.test_data, &self.test_labels),
+        };
+        let image_bytes = size_of::<[u8; IMAGE_ROWS * IMAGE_COLUMNS]>();
+        SplitMemory {
+            images: images.len() * image_bytes,
+            labels: labels.len(),
+            allocated: images.capacity() * image_bytes + labels.capacity(),
+            f32_copy: images.len() * IMAGE_ROWS * IMAGE_COLUMNS * size_of::<f32>(),
+            bit_packed_copy: images.len() * (IMAGE_ROWS * IMAGE_COLUMNS).div_ceil(8),
+        }
+    }
+}
+
+impl fmt::Display for MemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mib = |bytes| tenths(bytes, 1 << 20);
+        writeln!(
+            f,
+            "Split    Images    Labels  Allocated  f32 copy  Bit-packed  (MiB)"
+        )?;
+        for (name, split) in [("train", &self.train), ("test", &self.test)] {
+            writeln!(
+                f,
+                "{name:<5}  {:>8}  {:>8}  {:>9}  {:>8}  {:>10}",
+                mib(split.images),
+                mib(split.labels),
+                mib(split.allocated),
+                mib(split.f32_copy),
+                mib(split.bit_packed_copy)
+            )?;
+        }
+        writeln!(f, "Total allocated: {} MiB", mib(self.allocated()))
+    }
+}
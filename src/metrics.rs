@@ -0,0 +1,418 @@
+//! Classification metrics for models trained on the dataset.
+//!
+//! # Examples
+//! ```
+//! use rust_mnist::metrics::Metrics;
+//!
+//! let actual = [0, 1, 2, 2, 4];
+//! let predicted = [0, 1, 2, 7, 4];
+//! let metrics = Metrics::from_predictions(&predicted, &actual);
+//! assert_eq!(metrics.accuracy, 0.8);
+//! assert_eq!(metrics.classes[2].recall, 0.5);
+//! print!("{}", metrics);
+//! ```
+
+use crate::evaluate::argmax;
+use crate::NUM_CLASSES;
+use std::fmt;
+
+/// Fraction of `predicted` labels equal to the `actual` ones, or zero if there are none.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+#[must_use]
+pub fn accuracy(predicted: &[u8], actual: &[u8]) -> f64 {
+    assert_eq!(
+        predicted.len(),
+        actual.len(),
+        "Number of predictions does not match number of labels."
+    );
+    let correct = predicted.iter().zip(actual).filter(|(p, a)| p == a).count();
+    ratio(correct, actual.len())
+}
+
+/// Fraction of samples whose `actual` label is among the `k` most probable according to the
+/// predicted `probabilities` of every label, one array per sample. A label tied with the `k`th
+/// most probable counts as among them.
+///
+/// # Panics
+///
+/// Panics if `k` is zero, if the slices have different lengths or if a label is 10 or more.
+///
+/// # Examples
+/// ```
+/// use rust_mnist::metrics::top_k_accuracy;
+///
+/// let probabilities = [[0.1, 0.5, 0.4, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]];
+/// assert_eq!(top_k_accuracy(&probabilities, &[2], 1), 0.0);
+/// assert_eq!(top_k_accuracy(&probabilities, &[2], 2), 1.0);
+/// ```
+#[must_use]
+pub fn top_k_accuracy(probabilities: &[[f32; NUM_CLASSES]], actual: &[u8], k: usize) -> f64 {
+    assert!(k > 0, "k must be at least one.");
+    assert_eq!(
+        probabilities.len(),
+        actual.len(),
+        "Number of predictions does not match number of labels."
+    );
+    let correct = probabilities
+        .iter()
+        .zip(actual)
+        .filter(|(probabilities, &actual)| {
+            let probability = probabilities[usize::from(actual)];
+            let above = probabilities
+                .iter()
+                .filter(|other| other.total_cmp(&probability).is_gt())
+                .count();
+            above < k
+        })
+        .count();
+    ratio(correct, actual.len())
+}
+
+/// Counts of every combination of actual and predicted label.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfusionMatrix {
+    /// `counts[actual][predicted]` is the number of samples of digit `actual` predicted to be
+    /// `predicted`.
+    pub counts: [[usize; NUM_CLASSES]; NUM_CLASSES],
+}
+
+impl ConfusionMatrix {
+    /// Tally `predicted` labels against the `actual` ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths or a label is 10 or more.
+    #[must_use]
+    pub fn from_predictions(predicted: &[u8], actual: &[u8]) -> ConfusionMatrix {
+        assert_eq!(
+            predicted.len(),
+            actual.len(),
+            "Number of predictions does not match number of labels."
+        );
+        let mut matrix = ConfusionMatrix::default();
+        for (&predicted, &actual) in predicted.iter().zip(actual) {
+            matrix.add(actual, predicted);
+        }
+        matrix
+    }
+
+    /// Count one more prediction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a label is 10 or more.
+    pub fn add(&mut self, actual: u8, predicted: u8) {
+        self.counts[usize::from(actual)][usize::from(predicted)] += 1;
+    }
+
+    /// Number of predictions counted.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// Fraction of predictions that were correct.
+    #[must_use]
+    pub fn accuracy(&self) -> f64 {
+        let correct = (0..NUM_CLASSES)
+            .map(|class| self.counts[class][class])
+            .sum();
+        ratio(correct, self.total())
+    }
+
+    /// Precision, recall, F1 score and support of one digit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `class` is 10 or more.
+    #[must_use]
+    pub fn class_metrics(&self, class: u8) -> ClassMetrics {
+        let class = usize::from(class);
+        let correct = self.counts[class][class];
+        let predicted = self.counts.iter().map(|row| row[class]).sum();
+        let support = self.counts[class].iter().sum();
+
+        let precision = ratio(correct, predicted);
+        let recall = ratio(correct, support);
+        let f1 = if precision + recall > 0.0 {
+            2.0 * precision * recall / (precision + recall)
+        } else {
+            0.0
+        };
+        ClassMetrics {
+            precision,
+            recall,
+            f1,
+            support,
+        }
+    }
+}
+
+impl fmt::Display for ConfusionMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.total().max(1).to_string().len().max(2) + 1;
+        writeln!(f, "actual \\ predicted")?;
+        write!(f, "{:>6}", "")?;
+        for label in 0..NUM_CLASSES {
+            write!(f, "{label:>width$}")?;
+        }
+        writeln!(f)?;
+        for (label, row) in self.counts.iter().enumerate() {
+            write!(f, "{label:>6}")?;
+            for count in row {
+                write!(f, "{count:>width$}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// How well a model does on one digit, part of [`Metrics`].
+///
+/// Precision and recall are zero when undefined, i.e. when the digit is never predicted or never
+/// occurs.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassMetrics {
+    /// Fraction of predictions of the digit that were right.
+    pub precision: f64,
+    /// Fraction of samples of the digit that were predicted correctly.
+    pub recall: f64,
+    /// Harmonic mean of precision and recall.
+    pub f1: f64,
+    /// Number of samples of the digit.
+    pub support: usize,
+}
+
+/// Report of how well a model's predictions match the labels. Its [`Display`](fmt::Display)
+/// implementation prints a per-class table followed by the confusion matrix.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metrics {
+    /// Fraction of predictions that were correct.
+    pub accuracy: f64,
+    /// Metrics of each digit, indexed by label.
+    pub classes: [ClassMetrics; NUM_CLASSES],
+    /// Counts of every combination of actual and predicted label.
+    pub confusion: ConfusionMatrix,
+}
+
+impl Metrics {
+    /// Compute every metric from a confusion matrix.
+    #[must_use]
+    pub fn from_confusion(confusion: ConfusionMatrix) -> Metrics {
+        let mut classes = [ClassMetrics::default(); NUM_CLASSES];
+        for (class, metrics) in (0..).zip(classes.iter_mut()) {
+            *metrics = confusion.class_metrics(class);
+        }
+        Metrics {
+            accuracy: confusion.accuracy(),
+            classes,
+            confusion,
+        }
+    }
+
+    /// Compute every metric from `predicted` and `actual` labels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices have different lengths or a label is 10 or more.
+    #[must_use]
+    pub fn from_predictions(predicted: &[u8], actual: &[u8]) -> Metrics {
+        Metrics::from_confusion(ConfusionMatrix::from_predictions(predicted, actual))
+    }
+
+    /// Unweighted mean of the per-class F1 scores.
+    #[must_use]
+    pub fn macro_f1(&self) -> f64 {
+        self.classes.iter().map(|class| class.f1).sum::<f64>() / 10.0
+    }
+}
+
+impl fmt::Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Accuracy: {:.4}  Macro F1: {:.4}",
+            self.accuracy,
+            self.macro_f1()
+        )?;
+        writeln!(f)?;
+        writeln!(f, " Label  Precision  Recall      F1  Support")?;
+        for (label, class) in self.classes.iter().enumerate() {
+            writeln!(
+                f,
+                "{label:>6}  {:>9.4}  {:>6.4}  {:>6.4}  {:>7}",
+                class.precision, class.recall, class.f1, class.support
+            )?;
+        }
+        writeln!(f)?;
+        write!(f, "{}", self.confusion)
+    }
+}
+
+/// One bin of a reliability diagram, part of [`Calibration`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReliabilityBin {
+    /// Smallest confidence that falls in the bin.
+    pub lower: f64,
+    /// Confidence at which the next bin starts. The last bin also holds a confidence of exactly
+    /// this value.
+    pub upper: f64,
+    /// Number of predictions whose confidence falls in the bin.
+    pub count: usize,
+    /// Mean confidence of those predictions, or zero if there are none.
+    pub confidence: f64,
+    /// Fraction of those predictions that were correct, or zero if there are none.
+    pub accuracy: f64,
+}
+
+/// How well a model's confidence matches how often it is right.
+///
+/// Predictions are grouped into equal-width bins by their confidence, the probability of the
+/// predicted label. A well-calibrated model is right about 80% of the time when it is 80% sure,
+/// so the accuracy of each bin is close to its mean confidence. Its [`Display`](fmt::Display)
+/// implementation prints the bins as a table, the data for a reliability diagram.
+///
+/// # Examples
+/// ```
+/// use rust_mnist::metrics::Calibration;
+///
+/// let probabilities = [
+///     [0.9, 0.1, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+///     [0.0, 0.6, 0.4, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+/// ];
+/// let calibration = Calibration::from_probabilities(&probabilities, &[0, 2], 10);
+/// assert!((calibration.expected_calibration_error() - 0.35).abs() < 1e-6);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Calibration {
+    /// Bins in order of increasing confidence, covering `[0, 1]`.
+    pub bins: Vec<ReliabilityBin>,
+}
+
+impl Calibration {
+    /// Bin predicted `probabilities` of every label, one array per sample, against the `actual`
+    /// labels, using `bins` equal-width confidence bins. 10 or 15 bins are common choices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero, if the slices have different lengths or if a label is 10 or
+    /// more.
+    #[must_use]
+    pub fn from_probabilities(
+        probabilities: &[[f32; NUM_CLASSES]],
+        actual: &[u8],
+        bins: usize,
+    ) -> Calibration {
+        assert!(bins > 0, "Number of bins must be at least one.");
+        assert_eq!(
+            probabilities.len(),
+            actual.len(),
+            "Number of predictions does not match number of labels."
+        );
+
+        let mut counts = vec![0; bins];
+        let mut correct = vec![0; bins];
+        let mut confidence_sums = vec![0.0; bins];
+        for (probabilities, &actual) in probabilities.iter().zip(actual) {
+            assert!(usize::from(actual) < NUM_CLASSES, "Label must be below 10.");
+            let predicted = argmax(probabilities);
+            let confidence = f64::from(probabilities[usize::from(predicted)]);
+            let bin = bin_index(confidence, bins);
+            counts[bin] += 1;
+            confidence_sums[bin] += confidence;
+            if predicted == actual {
+                correct[bin] += 1;
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let bins = (0..bins)
+            .map(|bin| ReliabilityBin {
+                lower: ratio(bin, bins),
+                upper: ratio(bin + 1, bins),
+                count: counts[bin],
+                confidence: if counts[bin] == 0 {
+                    0.0
+                } else {
+                    confidence_sums[bin] / counts[bin] as f64
+                },
+                accuracy: ratio(correct[bin], counts[bin]),
+            })
+            .collect();
+        Calibration { bins }
+    }
+
+    /// Expected calibration error (ECE): the gap between accuracy and mean confidence in each
+    /// bin, averaged over the bins weighted by how many predictions they hold. Zero for a
+    /// perfectly calibrated model.
+    #[must_use]
+    pub fn expected_calibration_error(&self) -> f64 {
+        let total = self.bins.iter().map(|bin| bin.count).sum();
+        self.bins
+            .iter()
+            .map(|bin| ratio(bin.count, total) * (bin.accuracy - bin.confidence).abs())
+            .sum()
+    }
+
+    /// Maximum calibration error (MCE): the largest gap between accuracy and mean confidence
+    /// over the bins holding any predictions.
+    #[must_use]
+    pub fn maximum_calibration_error(&self) -> f64 {
+        self.bins
+            .iter()
+            .filter(|bin| bin.count > 0)
+            .map(|bin| (bin.accuracy - bin.confidence).abs())
+            .fold(0.0, f64::max)
+    }
+}
+
+impl fmt::Display for Calibration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "ECE: {:.4}  MCE: {:.4}",
+            self.expected_calibration_error(),
+            self.maximum_calibration_error()
+        )?;
+        writeln!(f)?;
+        writeln!(f, "Confidence    Count    Mean  Accuracy")?;
+        for bin in &self.bins {
+            writeln!(
+                f,
+                "{:.2}..{:.2}  {:>7}  {:.4}  {:>8.4}",
+                bin.lower, bin.upper, bin.count, bin.confidence, bin.accuracy
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Bin of `bins` equal-width bins over `[0, 1]` holding `confidence`.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn bin_index(confidence: f64, bins: usize) -> usize {
+    ((confidence * bins as f64) as usize).min(bins - 1)
+}
+
+/// `numerator / denominator`, or zero if `denominator` is zero.
+#[allow(clippy::cast_precision_loss)]
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    // Counts of predictions stay far below the 2^52 where the casts would lose precision.
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
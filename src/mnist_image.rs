@@ -0,0 +1,254 @@
+//! A single image, with two-dimensional accessors, and row and column iterators over images.
+
+use crate::{AsciiRamp, ImageSet, Mnist, Normalization, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use std::fmt;
+use std::slice::ChunksExact;
+
+/// One 28x28 grayscale image: row-major pixels from 0 (background) to 255 (ink).
+///
+/// It wraps the `[u8; 784]` arrays the dataset stores, adding row and column access so that
+/// callers need no index arithmetic. Its [`Display`](fmt::Display) implementation draws the
+/// image in ASCII.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::{Mnist, Split};
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+/// let image = mnist.image(Split::Train, 5);
+/// let centre = image.pixel(14, 14);
+/// let inked_rows = image.iter_rows().filter(|row| row.iter().any(|&pixel| pixel > 0)).count();
+/// print!("{image}");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MnistImage(pub [u8; IMAGE_ROWS * IMAGE_COLUMNS]);
+
+impl MnistImage {
+    /// Number of rows of pixels.
+    pub const ROWS: usize = IMAGE_ROWS;
+    /// Number of pixels in a row.
+    pub const COLUMNS: usize = IMAGE_COLUMNS;
+
+    /// The pixel in column `x` of row `y`, both counted from the top left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or `y` is 28 or more.
+    #[must_use]
+    pub fn pixel(&self, x: usize, y: usize) -> u8 {
+        assert!(
+            x < IMAGE_COLUMNS && y < IMAGE_ROWS,
+            "Pixel ({}, {}) is outside the image.",
+            x,
+            y
+        );
+        self.0[y * IMAGE_COLUMNS + x]
+    }
+
+    /// The 28 pixels of row `r`, left to right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` is 28 or more.
+    #[must_use]
+    pub fn row(&self, r: usize) -> &[u8] {
+        assert!(r < IMAGE_ROWS, "Row {} is outside the image.", r);
+        &self.0[r * IMAGE_COLUMNS..(r + 1) * IMAGE_COLUMNS]
+    }
+
+    /// Iterator over the rows, top to bottom, each as a slice of 28 pixels.
+    pub fn iter_rows(&self) -> ChunksExact<'_, u8> {
+        self.0.chunks_exact(IMAGE_COLUMNS)
+    }
+
+    /// The 28 pixels of column `c`, top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `c` is 28 or more.
+    #[must_use]
+    pub fn column(&self, c: usize) -> [u8; IMAGE_ROWS] {
+        assert!(c < IMAGE_COLUMNS, "Column {} is outside the image.", c);
+        column(&self.0, c)
+    }
+
+    /// Iterator over the columns, left to right, each gathered into an array of 28 pixels.
+    pub fn iter_columns(&self) -> Columns<'_> {
+        Columns::new(&self.0)
+    }
+
+    /// The pixels as `f32` values, normalized with `normalization`.
+    #[must_use]
+    pub fn to_f32(&self, normalization: Normalization) -> [f32; IMAGE_ROWS * IMAGE_COLUMNS] {
+        let mut pixels = [0.0; IMAGE_ROWS * IMAGE_COLUMNS];
+        for (value, &pixel) in pixels.iter_mut().zip(&self.0) {
+            *value = normalization.apply(pixel);
+        }
+        pixels
+    }
+
+    /// The underlying row-major pixels.
+    #[must_use]
+    pub fn pixels(&self) -> &[u8; IMAGE_ROWS * IMAGE_COLUMNS] {
+        &self.0
+    }
+}
+
+impl From<[u8; IMAGE_ROWS * IMAGE_COLUMNS]> for MnistImage {
+    fn from(pixels: [u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> MnistImage {
+        MnistImage(pixels)
+    }
+}
+
+impl From<MnistImage> for [u8; IMAGE_ROWS * IMAGE_COLUMNS] {
+    fn from(image: MnistImage) -> [u8; IMAGE_ROWS * IMAGE_COLUMNS] {
+        image.0
+    }
+}
+
+impl AsRef<[u8; IMAGE_ROWS * IMAGE_COLUMNS]> for MnistImage {
+    fn as_ref(&self) -> &[u8; IMAGE_ROWS * IMAGE_COLUMNS] {
+        &self.0
+    }
+}
+
+impl fmt::Display for MnistImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&AsciiRamp::standard().render(&self.0))
+    }
+}
+
+/// Iterator over the columns of an image, left to right, created by
+/// [`MnistImage::iter_columns`], [`Mnist::columns`] or [`ImageSet::columns`].
+///
+/// Columns are not contiguous in memory, so each is gathered into an array of 28 pixels, top to
+/// bottom.
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Columns<'a> {
+    pixels: &'a [u8; IMAGE_ROWS * IMAGE_COLUMNS],
+    front: usize,
+    back: usize,
+}
+
+impl<'a> Columns<'a> {
+    fn new(pixels: &'a [u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> Columns<'a> {
+        Columns {
+            pixels,
+            front: 0,
+            back: IMAGE_COLUMNS,
+        }
+    }
+}
+
+impl Iterator for Columns<'_> {
+    type Item = [u8; IMAGE_ROWS];
+
+    fn next(&mut self) -> Option<[u8; IMAGE_ROWS]> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(column(self.pixels, self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Columns<'_> {
+    fn next_back(&mut self) -> Option<[u8; IMAGE_ROWS]> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(column(self.pixels, self.back))
+    }
+}
+
+impl ExactSizeIterator for Columns<'_> {}
+
+fn column(pixels: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], c: usize) -> [u8; IMAGE_ROWS] {
+    let mut column = [0; IMAGE_ROWS];
+    for (pixel, row) in column.iter_mut().zip(pixels.chunks_exact(IMAGE_COLUMNS)) {
+        *pixel = row[c];
+    }
+    column
+}
+
+impl Mnist {
+    /// Image `index` of the split as an [`MnistImage`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn image(&self, split: Split, index: usize) -> MnistImage {
+        MnistImage(self.images(split)[index])
+    }
+
+    /// Iterator over the rows of image `index` of the split, top to bottom, each as a slice of 28
+    /// pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    ///
+    /// // Height and width of the ink in the first training image.
+    /// let inked = |line: &[u8]| line.iter().any(|&pixel| pixel > 0);
+    /// let height = mnist.rows(Split::Train, 0).filter(|row| inked(row)).count();
+    /// let width = mnist.columns(Split::Train, 0).filter(|column| inked(column)).count();
+    /// ```
+    pub fn rows(&self, split: Split, index: usize) -> ChunksExact<'_, u8> {
+        self.images(split)[index].chunks_exact(IMAGE_COLUMNS)
+    }
+
+    /// Iterator over the columns of image `index` of the split, left to right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn columns(&self, split: Split, index: usize) -> Columns<'_> {
+        Columns::new(&self.images(split)[index])
+    }
+}
+
+impl ImageSet {
+    /// Image `index` of the set as an [`MnistImage`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn image(&self, index: usize) -> MnistImage {
+        MnistImage(self.images[index])
+    }
+
+    /// Iterator over the rows of image `index`, top to bottom, each as a slice of 28 pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn rows(&self, index: usize) -> ChunksExact<'_, u8> {
+        self.images[index].chunks_exact(IMAGE_COLUMNS)
+    }
+
+    /// Iterator over the columns of image `index`, left to right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn columns(&self, index: usize) -> Columns<'_> {
+        Columns::new(&self.images[index])
+    }
+}
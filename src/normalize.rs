@@ -0,0 +1,36 @@
+//! Conversion of pixels to floating point.
+
+/// How `u8` pixels are turned into `f32` values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Normalization {
+    /// Keep the raw values, from 0 to 255.
+    Raw,
+    /// Scale to `[0, 1]`.
+    UnitRange,
+    /// Scale to `[-1, 1]`.
+    SymmetricRange,
+    /// Scale to `[0, 1]`, then subtract `mean` and divide by `std`.
+    Standardize { mean: f32, std: f32 },
+}
+
+impl Normalization {
+    /// Standardization with the mean and standard deviation of the MNIST training images, as
+    /// used by most reference models.
+    pub const MNIST: Normalization = Normalization::Standardize {
+        mean: 0.1307,
+        std: 0.3081,
+    };
+
+    /// Normalize a single pixel.
+    #[must_use]
+    pub fn apply(self, pixel: u8) -> f32 {
+        let pixel = f32::from(pixel);
+        match self {
+            Normalization::Raw => pixel,
+            Normalization::UnitRange => pixel / 255.0,
+            Normalization::SymmetricRange => pixel / 127.5 - 1.0,
+            Normalization::Standardize { mean, std } => (pixel / 255.0 - mean) / std,
+        }
+    }
+}
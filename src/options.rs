@@ -0,0 +1,261 @@
+//! Every setting of loading the dataset, gathered in one place.
+
+use crate::loader::Transform;
+#[cfg(feature = "download")]
+use crate::Dataset;
+use crate::{
+    DataLoader, DataSource, LocalDir, Mnist, Normalization, Split, NUM_TEST_IMAGES,
+    NUM_TRAIN_IMAGES, TEST_DATA_FILENAME, TEST_LABEL_FILENAME, TRAIN_DATA_FILENAME,
+    TRAIN_LABEL_FILENAME,
+};
+use log::info;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Whether [`Mnist::load`] may download missing files into the cache directory.
+#[cfg(feature = "download")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DownloadPolicy {
+    /// Only read what is already in the source.
+    Never,
+    /// Download the files of this dataset that are missing from the cache directory first.
+    IfMissing(Dataset),
+}
+
+/// How to load the dataset and turn it into batches, for [`Mnist::load`] and
+/// [`LoadOptions::data_loader`].
+///
+/// # Examples
+/// ```no_run
+/// use std::path::Path;
+/// use rust_mnist::{LoadOptions, Mnist, Normalization, Split};
+///
+/// let options = LoadOptions::new()
+///     .cache_dir(Path::new("/var/cache/mnist"))
+///     .verbose(false)
+///     .normalization(Normalization::MNIST)
+///     .transform(|image| image.iter_mut().for_each(|pixel| *pixel = pixel.clamp(-1.0, 3.0)));
+///
+/// let mnist = Mnist::load(&options)?;
+/// let mut loader = options.data_loader(&mnist, Split::Train, 64);
+/// for batch in loader.epoch() {
+///     // Train on the batch.
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// Any [`DataSource`] can stand in for the directory. Here the files written by
+/// [`Mnist::to_idx`] are read back from memory:
+///
+/// ```
+/// use std::fs;
+/// use rust_mnist::{ImageSet, LoadOptions, MemorySource, Mnist};
+///
+/// let train = ImageSet { images: vec![[0; 784], [255; 784]], labels: vec![3, 4] };
+/// let test = ImageSet { images: vec![[128; 784]], labels: vec![5] };
+/// let mnist = Mnist::from_splits(train, test);
+///
+/// let dir = std::env::temp_dir().join("rust-mnist-memory-source-example");
+/// mnist.to_idx(&dir)?;
+/// let mut source = MemorySource::new();
+/// for name in [
+///     "train-images-idx3-ubyte",
+///     "train-labels-idx1-ubyte",
+///     "t10k-images-idx3-ubyte",
+///     "t10k-labels-idx1-ubyte",
+/// ] {
+///     source.insert(name, fs::read(dir.join(name))?);
+/// }
+/// fs::remove_dir_all(&dir)?;
+///
+/// // Not strict, as these splits are far smaller than MNIST's.
+/// let loaded = Mnist::load(&LoadOptions::new().source(source).strict(false))?;
+/// assert_eq!(loaded, mnist);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct LoadOptions {
+    strict: bool,
+    verbose: bool,
+    /// Where to read from, if not a [`LocalDir`] of the cache directory.
+    source: Option<Box<dyn DataSource + Send + Sync>>,
+    cache_dir: PathBuf,
+    #[cfg(feature = "download")]
+    download: DownloadPolicy,
+    normalization: Normalization,
+    transforms: Vec<Transform<'static>>,
+}
+
+impl Default for LoadOptions {
+    fn default() -> LoadOptions {
+        LoadOptions::new()
+    }
+}
+
+impl LoadOptions {
+    /// Options reading the dataset from the directory `data`, as strictly and verbosely as
+    /// [`Mnist::new`], without downloading, and normalizing pixels to `[0, 1]` without
+    /// transforms.
+    #[must_use]
+    pub fn new() -> LoadOptions {
+        LoadOptions {
+            strict: true,
+            verbose: true,
+            source: None,
+            cache_dir: PathBuf::from("data"),
+            #[cfg(feature = "download")]
+            download: DownloadPolicy::Never,
+            normalization: Normalization::UnitRange,
+            transforms: Vec::new(),
+        }
+    }
+
+    /// Whether the splits must hold exactly the 60000 and 10000 samples of MNIST, as
+    /// [`Mnist::new`] requires. When off, any number is accepted, as long as every image has a
+    /// label.
+    #[must_use]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Whether to log each step of loading at the `info` level.
+    #[must_use]
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Directory receiving any files that are downloaded, and holding the IDX files unless a
+    /// [source](LoadOptions::source) is set, in either order.
+    #[must_use]
+    pub fn cache_dir(mut self, cache_dir: &Path) -> Self {
+        self.cache_dir = cache_dir.to_path_buf();
+        self
+    }
+
+    /// Where to read the four IDX files from, such as a [`MemorySource`](crate::MemorySource)
+    /// or an [`HttpSource`](crate::HttpSource); a [`LocalDir`] of the
+    /// [cache directory](LoadOptions::cache_dir) by default.
+    ///
+    /// Downloads still go to the cache directory, so only set both if the source reads it.
+    #[must_use]
+    pub fn source(mut self, source: impl DataSource + Send + Sync + 'static) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    /// Whether to download missing files; [`DownloadPolicy::Never`] by default.
+    #[cfg(feature = "download")]
+    #[must_use]
+    pub fn download(mut self, download: DownloadPolicy) -> Self {
+        self.download = download;
+        self
+    }
+
+    /// How [`LoadOptions::data_loader`] turns pixels into `f32` values.
+    #[must_use]
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Add a transform that [`LoadOptions::data_loader`] applies to every normalized image.
+    /// Transforms run in the order they were added.
+    #[must_use]
+    pub fn transform(mut self, transform: impl Fn(&mut [f32]) + Send + Sync + 'static) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// A [`DataLoader`] over the split, batching `batch_size` samples with these options'
+    /// normalization and transforms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    #[must_use]
+    pub fn data_loader<'a>(
+        &'a self,
+        mnist: &'a Mnist,
+        split: Split,
+        batch_size: usize,
+    ) -> DataLoader<'a> {
+        self.transforms.iter().fold(
+            DataLoader::new(mnist.images(split), mnist.labels(split), batch_size)
+                .normalization(self.normalization),
+            |loader, transform| loader.transform(move |image| transform(image)),
+        )
+    }
+}
+
+impl Mnist {
+    /// Load the dataset as `options` say: from their source, downloading missing files into the
+    /// cache directory first if allowed, and checking the number of samples if strict.
+    ///
+    /// Each file is read whole, then checked as by [`Mnist::from_array_buffers`]. Unlike
+    /// [`Mnist::new`], problems are returned rather than panicked on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be downloaded or read, is malformed, or if a strict load
+    /// finds a split of the wrong size.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::Path;
+    /// use rust_mnist::{LoadOptions, Mnist};
+    ///
+    /// let mnist = Mnist::load(&LoadOptions::new().cache_dir(Path::new("data")))?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn load(options: &LoadOptions) -> io::Result<Mnist> {
+        #[cfg(feature = "download")]
+        if let DownloadPolicy::IfMissing(dataset) = options.download {
+            let dir = &options.cache_dir;
+            if options.verbose {
+                info!(
+                    "Downloading any missing {dataset} files into {}.",
+                    dir.display()
+                );
+            }
+            crate::download(dataset, dir, |_, _, _| {})?;
+        }
+
+        let local;
+        let source: &dyn DataSource = if let Some(source) = &options.source {
+            &**source
+        } else {
+            local = LocalDir::new(&options.cache_dir);
+            &local
+        };
+        let read = |name: &str| {
+            if options.verbose {
+                info!("Reading {name}.");
+            }
+            source
+                .read(name)
+                .map_err(|err| io::Error::new(err.kind(), format!("Cannot read {name}: {err}")))
+        };
+        let mnist = Mnist::from_array_buffers(
+            &read(TRAIN_DATA_FILENAME)?,
+            &read(TRAIN_LABEL_FILENAME)?,
+            &read(TEST_DATA_FILENAME)?,
+            &read(TEST_LABEL_FILENAME)?,
+        )?;
+        let sizes = (mnist.train_labels.len(), mnist.test_labels.len());
+        if options.strict && sizes != (NUM_TRAIN_IMAGES, NUM_TEST_IMAGES) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Dataset has {} training and {} testing samples where MNIST has \
+                     {NUM_TRAIN_IMAGES} and {NUM_TEST_IMAGES}.",
+                    sizes.0, sizes.1
+                ),
+            ));
+        }
+        if options.verbose {
+            info!("Read {} training and {} testing samples.", sizes.0, sizes.1);
+        }
+        Ok(mnist)
+    }
+}
@@ -0,0 +1,100 @@
+//! Ranking samples by how unusual they look.
+
+use crate::centroids::euclidean;
+use crate::pca::Pca;
+use crate::stats::class_stats;
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+
+/// How [`Mnist::outlier_scores`] measures how unusual a sample is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutlierMethod {
+    /// Distance from the mean image of the sample's labelled class. High scores flag oddly
+    /// written digits and likely mislabels.
+    Centroid,
+    /// Distance from the image's reconstruction from its first `components` principal
+    /// components. High scores flag images unlike the dataset as a whole, whatever their label.
+    Reconstruction { components: usize },
+}
+
+impl Mnist {
+    /// Score every sample of the split by how unusual it looks, returning `(index, score)` pairs
+    /// with the most unusual first. Scores are Euclidean distances with pixels scaled to
+    /// `[0, 1]`.
+    ///
+    /// Class means are those of the split itself, while principal components are fitted on the
+    /// training images.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`OutlierMethod::Reconstruction`] asks for zero or more than 784 components.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{print_image, Mnist, OutlierMethod, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// for &(index, _) in &mnist.outlier_scores(Split::Train, OutlierMethod::Centroid)[..5] {
+    ///     print_image(&mnist.train_data[index], mnist.train_labels[index]);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn outlier_scores(&self, split: Split, method: OutlierMethod) -> Vec<(usize, f32)> {
+        outlier_scores(
+            &self.train_data,
+            self.images(split),
+            self.labels(split),
+            method,
+        )
+    }
+}
+
+impl ImageSet {
+    /// Score every sample of the set by how unusual it looks, as [`Mnist::outlier_scores`] does,
+    /// fitting principal components on the set itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`OutlierMethod::Reconstruction`] asks for zero or more than 784 components.
+    #[must_use]
+    pub fn outlier_scores(&self, method: OutlierMethod) -> Vec<(usize, f32)> {
+        outlier_scores(&self.images, &self.images, &self.labels, method)
+    }
+}
+
+fn outlier_scores(
+    fit_images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+    method: OutlierMethod,
+) -> Vec<(usize, f32)> {
+    let unit = |image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]| -> Vec<f32> {
+        image
+            .iter()
+            .map(|&pixel| f32::from(pixel) / 255.0)
+            .collect()
+    };
+
+    let mut scores: Vec<(usize, f32)> = match method {
+        OutlierMethod::Centroid => {
+            let stats = class_stats(images, labels);
+            images
+                .iter()
+                .zip(labels)
+                .map(|(image, &label)| euclidean(&unit(image), &stats[usize::from(label)].mean))
+                .enumerate()
+                .collect()
+        }
+        OutlierMethod::Reconstruction { components } => {
+            let pca = Pca::fit(fit_images, components, 0);
+            images
+                .iter()
+                .map(|image| euclidean(&unit(image), &pca.reconstruct(&pca.project(image))))
+                .enumerate()
+                .collect()
+        }
+    };
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scores
+}
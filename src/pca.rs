@@ -0,0 +1,471 @@
+//! Principal component analysis of the images.
+
+use crate::{ImageSet, Mnist, IMAGE_COLUMNS, IMAGE_ROWS};
+use std::convert::TryFrom;
+use std::thread;
+
+const PIXELS: usize = IMAGE_ROWS * IMAGE_COLUMNS;
+
+/// Principal components of a set of images, fitted by [`Mnist::fit_pca`].
+///
+/// Pixels are scaled to `[0, 1]` before fitting, and components are unit vectors of 784 values in
+/// row-major pixel order, ordered by decreasing explained variance. Viewed as images, they are the
+/// dataset's "eigen-digits".
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pca {
+    mean: Vec<f32>,
+    components: Vec<Vec<f32>>,
+    explained_variance: Vec<f32>,
+    total_variance: f32,
+}
+
+impl Pca {
+    /// Mean image subtracted from every image before projecting it.
+    #[must_use]
+    pub fn mean(&self) -> &[f32] {
+        &self.mean
+    }
+
+    /// The principal components, most important first.
+    #[must_use]
+    pub fn components(&self) -> &[Vec<f32>] {
+        &self.components
+    }
+
+    /// Variance of the images along each component.
+    #[must_use]
+    pub fn explained_variance(&self) -> &[f32] {
+        &self.explained_variance
+    }
+
+    /// Fraction of the images' total variance captured by each component.
+    #[must_use]
+    pub fn explained_variance_ratio(&self) -> Vec<f32> {
+        self.explained_variance
+            .iter()
+            .map(|variance| variance / self.total_variance)
+            .collect()
+    }
+
+    /// Coordinates of an image along each component.
+    #[must_use]
+    pub fn project(&self, image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> Vec<f32> {
+        let centered: Vec<f32> = image
+            .iter()
+            .zip(&self.mean)
+            .map(|(&pixel, mean)| f32::from(pixel) / 255.0 - mean)
+            .collect();
+        self.components
+            .iter()
+            .map(|component| component.iter().zip(&centered).map(|(a, b)| a * b).sum())
+            .collect()
+    }
+
+    /// Rebuild an image, with pixels scaled to `[0, 1]`, from its coordinates along the first
+    /// `coordinates.len()` components.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more coordinates than components.
+    #[must_use]
+    pub fn reconstruct(&self, coordinates: &[f32]) -> Vec<f32> {
+        assert!(
+            coordinates.len() <= self.components.len(),
+            "More coordinates than principal components."
+        );
+        let mut image = self.mean.clone();
+        for (component, &coordinate) in self.components.iter().zip(coordinates) {
+            for (pixel, weight) in image.iter_mut().zip(component) {
+                *pixel += coordinate * weight;
+            }
+        }
+        image
+    }
+}
+
+impl Mnist {
+    /// Fit the first `n_components` principal components of the training images.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_components` is zero or more than 784.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let pca = mnist.fit_pca(50);
+    /// let captured: f32 = pca.explained_variance_ratio().iter().sum();
+    ///
+    /// // A 50-dimensional representation of the first test image.
+    /// let features = pca.project(&mnist.test_data[0]);
+    /// ```
+    #[must_use]
+    pub fn fit_pca(&self, n_components: usize) -> Pca {
+        Pca::fit(&self.train_data, n_components, 0)
+    }
+}
+
+impl ImageSet {
+    /// Fit the first `n_components` principal components of the set's images, as
+    /// [`Mnist::fit_pca`] does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_components` is zero or more than 784.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::{Rng, SeedableRng};
+    /// use rust_mnist::ImageSet;
+    ///
+    /// // Faint specks, plus a vertical stroke of random brightness down the middle column.
+    /// let stroke: Vec<usize> = (8..20).map(|row| row * 28 + 14).collect();
+    /// let mut rng = StdRng::seed_from_u64(7);
+    /// let images: Vec<[u8; 784]> = (0..300)
+    ///     .map(|_| {
+    ///         let mut image = [0; 784];
+    ///         for _ in 0..50 {
+    ///             image[rng.gen_range(0..784)] = rng.gen_range(0..32);
+    ///         }
+    ///         let brightness = rng.gen_range(0..200);
+    ///         for &pixel in &stroke {
+    ///             image[pixel] += brightness;
+    ///         }
+    ///         image
+    ///     })
+    ///     .collect();
+    /// let set = ImageSet { labels: vec![0; images.len()], images };
+    ///
+    /// let pca = set.fit_pca(4);
+    /// let components = pca.components();
+    /// let dot = |a: &[f32], b: &[f32]| a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>();
+    ///
+    /// // The components are unit vectors at right angles to each other.
+    /// for (i, a) in components.iter().enumerate() {
+    ///     for (j, b) in components.iter().enumerate() {
+    ///         let expected = if i == j { 1.0 } else { 0.0 };
+    ///         assert!((dot(a, b) - expected).abs() < 1e-4);
+    ///     }
+    /// }
+    ///
+    /// // The first is the stroke, which explains far more variance than the rest.
+    /// let along_stroke: f32 = stroke.iter().map(|&pixel| components[0][pixel]).sum();
+    /// assert!(along_stroke / (stroke.len() as f32).sqrt() > 0.99);
+    /// let ratios = pca.explained_variance_ratio();
+    /// assert!(ratios.windows(2).all(|pair| pair[0] >= pair[1]));
+    /// assert!(ratios[0] > 10.0 * ratios[1]);
+    /// ```
+    #[must_use]
+    pub fn fit_pca(&self, n_components: usize) -> Pca {
+        Pca::fit(&self.images, n_components, 0)
+    }
+}
+
+impl Pca {
+    /// Fit the first `n_components` principal components of `images`, accumulating the
+    /// covariance on `workers` threads, or the calling thread if zero.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn fit(
+        images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+        n_components: usize,
+        workers: usize,
+    ) -> Pca {
+        assert!(
+            (1..=PIXELS).contains(&n_components),
+            "Number of components must be between 1 and 784."
+        );
+
+        let (mean, mut covariance) = covariance(images, workers);
+        let total_variance = (0..PIXELS).map(|i| covariance[i * PIXELS + i]).sum::<f64>();
+        let (values, vectors) = symmetric_eigen(&mut covariance);
+
+        // Eigenvalues come out in no particular order; keep the largest.
+        let mut order: Vec<usize> = (0..PIXELS).collect();
+        order.sort_by(|&a, &b| values[b].total_cmp(&values[a]));
+        let components = order[..n_components]
+            .iter()
+            .map(|&i| {
+                let mut component = vectors[i * PIXELS..(i + 1) * PIXELS].to_vec();
+                // Fix the arbitrary sign of each eigenvector so fits are reproducible.
+                let largest = component
+                    .iter()
+                    .copied()
+                    .max_by(|a, b| a.abs().total_cmp(&b.abs()))
+                    .unwrap_or(0.0);
+                if largest < 0.0 {
+                    for weight in &mut component {
+                        *weight = -*weight;
+                    }
+                }
+                component.into_iter().map(|weight| weight as f32).collect()
+            })
+            .collect();
+
+        Pca {
+            mean: mean.into_iter().map(|mean| mean as f32).collect(),
+            components,
+            // Rounding can leave zero eigenvalues slightly negative.
+            explained_variance: order[..n_components]
+                .iter()
+                .map(|&i| values[i].max(0.0) as f32)
+                .collect(),
+            total_variance: total_variance as f32,
+        }
+    }
+}
+
+/// Mean and row-major covariance matrix of the pixels, scaled to `[0, 1]`, accumulated on
+/// `workers` threads.
+fn covariance(images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]], workers: usize) -> (Vec<f64>, Vec<f64>) {
+    let count = f64::from(u32::try_from(images.len().max(1)).unwrap());
+    let (sums, mut products) = if workers == 0 {
+        raw_moments(images)
+    } else {
+        let per_worker = images.len().div_ceil(workers).max(1);
+        thread::scope(|scope| {
+            let handles: Vec<_> = images
+                .chunks(per_worker)
+                .map(|chunk| scope.spawn(move || raw_moments(chunk)))
+                .collect();
+            let mut moments = (vec![0.0; PIXELS], vec![0.0; PIXELS * PIXELS]);
+            for handle in handles {
+                let (sums, products) = handle.join().expect("PCA worker panicked.");
+                for (total, sum) in moments.0.iter_mut().zip(sums) {
+                    *total += sum;
+                }
+                for (total, product) in moments.1.iter_mut().zip(products) {
+                    *total += product;
+                }
+            }
+            moments
+        })
+    };
+
+    let mean: Vec<f64> = sums.iter().map(|sum| sum / count).collect();
+    for i in 0..PIXELS {
+        for j in 0..PIXELS {
+            products[i * PIXELS + j] = products[i * PIXELS + j] / count - mean[i] * mean[j];
+        }
+    }
+    (mean, products)
+}
+
+/// Sums of the pixels and of the products of every pair of pixels, scaled to `[0, 1]`.
+fn raw_moments(images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]]) -> (Vec<f64>, Vec<f64>) {
+    let mut sums = vec![0.0; PIXELS];
+    let mut products = vec![0.0; PIXELS * PIXELS];
+
+    // Most pixels are background, so accumulate raw products over the inked ones only and
+    // centre afterwards.
+    let mut inked = Vec::with_capacity(PIXELS);
+    for image in images {
+        inked.clear();
+        inked.extend(
+            image
+                .iter()
+                .enumerate()
+                .filter(|&(_, &pixel)| pixel > 0)
+                .map(|(i, &pixel)| (i, f64::from(pixel) / 255.0)),
+        );
+        for &(i, a) in &inked {
+            sums[i] += a;
+            let row = &mut products[i * PIXELS..(i + 1) * PIXELS];
+            for &(j, b) in &inked {
+                row[j] += a * b;
+            }
+        }
+    }
+    (sums, products)
+}
+
+/// Eigenvalues and eigenvectors of a symmetric `PIXELS` x `PIXELS` matrix, which is overwritten.
+/// Eigenvector `i` is row `i` of the returned matrix.
+///
+/// Householder reduction to tridiagonal form followed by the implicit QL method, after the
+/// `tred2` and `tql2` routines of EISPACK as adapted in JAMA.
+fn symmetric_eigen(v: &mut [f64]) -> (Vec<f64>, Vec<f64>) {
+    let (mut d, mut e) = tridiagonalize(v);
+
+    // The eigenvectors are the columns of `v`; store them as rows so the rotations of the QL
+    // method touch contiguous memory.
+    let mut vectors = vec![0.0; PIXELS * PIXELS];
+    for row in 0..PIXELS {
+        for column in 0..PIXELS {
+            vectors[column * PIXELS + row] = v[row * PIXELS + column];
+        }
+    }
+
+    diagonalize(&mut d, &mut e, &mut vectors);
+    (d, vectors)
+}
+
+/// Reduce the symmetric matrix `v` to tridiagonal form, returning its diagonal and subdiagonal
+/// and leaving the accumulated orthogonal transformation in `v`.
+#[allow(clippy::many_single_char_names)]
+fn tridiagonalize(v: &mut [f64]) -> (Vec<f64>, Vec<f64>) {
+    let n = PIXELS;
+    let at = |row: usize, column: usize| row * n + column;
+    let mut d = vec![0.0; n];
+    let mut e = vec![0.0; n];
+
+    // Householder reduction to tridiagonal form.
+    d.copy_from_slice(&v[at(n - 1, 0)..at(n, 0)]);
+    for i in (1..n).rev() {
+        let scale: f64 = d[..i].iter().map(|x| x.abs()).sum();
+        let mut h = 0.0;
+        if scale == 0.0 {
+            e[i] = d[i - 1];
+            for j in 0..i {
+                d[j] = v[at(i - 1, j)];
+                v[at(i, j)] = 0.0;
+                v[at(j, i)] = 0.0;
+            }
+        } else {
+            for x in &mut d[..i] {
+                *x /= scale;
+                h += *x * *x;
+            }
+            let mut f = d[i - 1];
+            let mut g = if f > 0.0 { -h.sqrt() } else { h.sqrt() };
+            e[i] = scale * g;
+            h -= f * g;
+            d[i - 1] = f - g;
+            e[..i].iter_mut().for_each(|x| *x = 0.0);
+
+            for j in 0..i {
+                f = d[j];
+                v[at(j, i)] = f;
+                g = e[j] + v[at(j, j)] * f;
+                for k in j + 1..i {
+                    g += v[at(k, j)] * d[k];
+                    e[k] += v[at(k, j)] * f;
+                }
+                e[j] = g;
+            }
+            f = 0.0;
+            for j in 0..i {
+                e[j] /= h;
+                f += e[j] * d[j];
+            }
+            let hh = f / (h + h);
+            for j in 0..i {
+                e[j] -= hh * d[j];
+            }
+            for j in 0..i {
+                f = d[j];
+                g = e[j];
+                for k in j..i {
+                    v[at(k, j)] -= f * e[k] + g * d[k];
+                }
+                d[j] = v[at(i - 1, j)];
+                v[at(i, j)] = 0.0;
+            }
+        }
+        d[i] = h;
+    }
+
+    // Accumulate the transformations.
+    for i in 0..n - 1 {
+        v[at(n - 1, i)] = v[at(i, i)];
+        v[at(i, i)] = 1.0;
+        let h = d[i + 1];
+        if h != 0.0 {
+            for k in 0..=i {
+                d[k] = v[at(k, i + 1)] / h;
+            }
+            for j in 0..=i {
+                let g: f64 = (0..=i).map(|k| v[at(k, i + 1)] * v[at(k, j)]).sum();
+                for k in 0..=i {
+                    v[at(k, j)] -= g * d[k];
+                }
+            }
+        }
+        for k in 0..=i {
+            v[at(k, i + 1)] = 0.0;
+        }
+    }
+    for j in 0..n {
+        d[j] = v[at(n - 1, j)];
+        v[at(n - 1, j)] = 0.0;
+    }
+    v[at(n - 1, n - 1)] = 1.0;
+    e[0] = 0.0;
+    (d, e)
+}
+
+/// Diagonalize the tridiagonal matrix with diagonal `d` and subdiagonal `e` with the implicit QL
+/// method, leaving the eigenvalues in `d` and applying the rotations to the rows of `vectors`.
+#[allow(clippy::many_single_char_names, clippy::similar_names)]
+fn diagonalize(d: &mut [f64], e: &mut [f64], vectors: &mut [f64]) {
+    let n = PIXELS;
+    for i in 1..n {
+        e[i - 1] = e[i];
+    }
+    e[n - 1] = 0.0;
+    let mut f = 0.0;
+    let mut tst1: f64 = 0.0;
+    for l in 0..n {
+        tst1 = tst1.max(d[l].abs() + e[l].abs());
+        // `e[n - 1]` is zero, so this always finds a small subdiagonal element.
+        let m = (l..n)
+            .find(|&m| e[m].abs() <= f64::EPSILON * tst1)
+            .unwrap_or(n - 1);
+        if m > l {
+            loop {
+                let mut g = d[l];
+                let mut p = (d[l + 1] - g) / (2.0 * e[l]);
+                let mut r = p.hypot(1.0);
+                if p < 0.0 {
+                    r = -r;
+                }
+                d[l] = e[l] / (p + r);
+                d[l + 1] = e[l] * (p + r);
+                let dl1 = d[l + 1];
+                let mut h = g - d[l];
+                for x in &mut d[l + 2..n] {
+                    *x -= h;
+                }
+                f += h;
+
+                p = d[m];
+                let (mut c, mut c2, mut c3) = (1.0, 1.0, 1.0);
+                let el1 = e[l + 1];
+                let (mut s, mut s2) = (0.0, 0.0);
+                for i in (l..m).rev() {
+                    c3 = c2;
+                    c2 = c;
+                    s2 = s;
+                    g = c * e[i];
+                    h = c * p;
+                    r = p.hypot(e[i]);
+                    e[i + 1] = s * r;
+                    s = e[i] / r;
+                    c = p / r;
+                    p = c * d[i] - s * g;
+                    d[i + 1] = h + s * (c * g + s * d[i]);
+
+                    let (first, second) = vectors.split_at_mut((i + 1) * n);
+                    let current = &mut first[i * n..];
+                    for (a, b) in current.iter_mut().zip(&mut second[..n]) {
+                        let old = *b;
+                        *b = s * *a + c * old;
+                        *a = c * *a - s * old;
+                    }
+                }
+                p = -s * s2 * c3 * el1 * e[l] / dl1;
+                e[l] = s * p;
+                d[l] = c * p;
+                if e[l].abs() <= f64::EPSILON * tst1 {
+                    break;
+                }
+            }
+        }
+        d[l] += f;
+        e[l] = 0.0;
+    }
+}
@@ -0,0 +1,50 @@
+//! Pixels converted to the numeric type of the caller's pipeline.
+
+use crate::{ImageSet, Mnist, MnistImage, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+
+impl Mnist {
+    /// Pixels of every image of the split, one image after another, converted to `T`.
+    ///
+    /// Values keep their range of 0 to 255; for scaled `f32` pixels, see
+    /// [`Normalization`](crate::Normalization).
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let pixels = mnist.to_vec::<f64>(Split::Test);
+    /// let centred: Vec<i16> = mnist.to_vec::<i16>(Split::Test).iter().map(|&p| p - 128).collect();
+    /// assert_eq!(pixels.len(), 10_000 * 784);
+    /// ```
+    #[must_use]
+    pub fn to_vec<T: From<u8>>(&self, split: Split) -> Vec<T> {
+        to_vec(self.images(split))
+    }
+}
+
+impl ImageSet {
+    /// Pixels of every image, one image after another, converted to `T`, as by
+    /// [`Mnist::to_vec`].
+    #[must_use]
+    pub fn to_vec<T: From<u8>>(&self) -> Vec<T> {
+        to_vec(&self.images)
+    }
+}
+
+impl MnistImage {
+    /// The image's row-major pixels converted to `T`, as by [`Mnist::to_vec`].
+    #[must_use]
+    pub fn to_vec<T: From<u8>>(&self) -> Vec<T> {
+        to_vec(std::slice::from_ref(&self.0))
+    }
+}
+
+fn to_vec<T: From<u8>>(images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]]) -> Vec<T> {
+    images
+        .as_flattened()
+        .iter()
+        .map(|&pixel| T::from(pixel))
+        .collect()
+}
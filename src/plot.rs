@@ -0,0 +1,140 @@
+//! Charts of the dataset's label and pixel distributions.
+
+use crate::stats::{intensity_histogram, label_counts};
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES};
+use ::plotters::coord::Shift;
+use ::plotters::prelude::*;
+use std::convert::TryFrom;
+use std::io;
+use std::path::Path;
+
+/// Width and height of saved charts, in pixels.
+const CHART_SIZE: (u32, u32) = (800, 600);
+
+impl Mnist {
+    /// Save a bar chart of how many samples of the split have each label to `path`. The chart is
+    /// an SVG if the extension is `.svg`, and a bitmap in the format the extension names
+    /// otherwise, e.g. `.png`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chart cannot be drawn or saved.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::{Path, PathBuf};
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// mnist.plot_label_histogram(Path::new("labels.svg"), Split::Train)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn plot_label_histogram(&self, path: &Path, split: Split) -> io::Result<()> {
+        plot_labels(path, self.labels(split))
+    }
+
+    /// Save a histogram of the intensities of the split's inked (non-zero) pixels to `path`, in
+    /// the format chosen as for [`Mnist::plot_label_histogram`].
+    ///
+    /// Background pixels are left out because they outnumber every other intensity many times
+    /// over and would flatten the rest of the chart.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chart cannot be drawn or saved.
+    pub fn plot_pixel_histogram(&self, path: &Path, split: Split) -> io::Result<()> {
+        plot_pixels(path, self.images(split))
+    }
+}
+
+impl ImageSet {
+    /// Save a bar chart of how many samples of the set have each label to `path`, as
+    /// [`Mnist::plot_label_histogram`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chart cannot be drawn or saved.
+    pub fn plot_label_histogram(&self, path: &Path) -> io::Result<()> {
+        plot_labels(path, &self.labels)
+    }
+
+    /// Save a histogram of the intensities of the set's inked pixels to `path`, as
+    /// [`Mnist::plot_pixel_histogram`] does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chart cannot be drawn or saved.
+    pub fn plot_pixel_histogram(&self, path: &Path) -> io::Result<()> {
+        plot_pixels(path, &self.images)
+    }
+}
+
+fn plot_labels(path: &Path, labels: &[u8]) -> io::Result<()> {
+    let counts = label_counts(labels).map(|count| u64::try_from(count).unwrap());
+    save_histogram(path, "Label distribution", "Label", 0, &counts)
+}
+
+fn plot_pixels(path: &Path, images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]]) -> io::Result<()> {
+    let counts: Vec<u64> = intensity_histogram(images, 256)
+        .into_iter()
+        .map(|count| u64::try_from(count).unwrap())
+        .collect();
+    save_histogram(path, "Pixel intensities", "Intensity", 1, &counts[1..])
+}
+
+/// Draw a histogram with one bar per count, the first at `first`, and save it to `path`.
+fn save_histogram(
+    path: &Path,
+    caption: &str,
+    x_desc: &str,
+    first: u32,
+    counts: &[u64],
+) -> io::Result<()> {
+    let is_svg = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"));
+    let to_io = |err: &dyn std::error::Error| io::Error::other(err.to_string());
+
+    if is_svg {
+        let root = SVGBackend::new(path, CHART_SIZE).into_drawing_area();
+        draw_histogram(&root, caption, x_desc, first, counts).map_err(|err| to_io(&err))
+    } else {
+        let root = BitMapBackend::new(path, CHART_SIZE).into_drawing_area();
+        draw_histogram(&root, caption, x_desc, first, counts).map_err(|err| to_io(&err))
+    }
+}
+
+fn draw_histogram<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    caption: &str,
+    x_desc: &str,
+    first: u32,
+    counts: &[u64],
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+    let last = first + u32::try_from(counts.len()).unwrap() - 1;
+    let highest = counts.iter().copied().max().unwrap_or(0);
+
+    root.fill(&WHITE)?;
+    let mut chart = ChartBuilder::on(root)
+        .caption(caption, ("sans-serif", 24))
+        .margin(16)
+        .x_label_area_size(40)
+        .y_label_area_size(80)
+        .build_cartesian_2d(
+            (first..last).into_segmented(),
+            0..highest + highest / 20 + 1,
+        )?;
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_desc(x_desc)
+        .y_desc("Count")
+        .draw()?;
+    chart.draw_series(
+        Histogram::vertical(&chart)
+            .style(BLUE.filled())
+            .margin(if counts.len() > NUM_CLASSES { 0 } else { 8 })
+            .data((first..).zip(counts.iter().copied())),
+    )?;
+    root.present()
+}
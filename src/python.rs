@@ -0,0 +1,87 @@
+//! Python bindings, built with `maturin build --features python`, which builds the library as a
+//! `cdylib` itself. Without maturin, `cargo rustc --lib --release --features python --crate-type
+//! cdylib` builds the extension module, to be renamed to `rust_mnist.so` (`rust_mnist.pyd` on
+//! Windows).
+//!
+//! ```python
+//! import numpy as np
+//! import rust_mnist
+//!
+//! mnist = rust_mnist.Mnist("examples/MNIST_data")
+//! images = np.frombuffer(mnist.train_images(), dtype=np.uint8).reshape(-1, 28, 28)
+//! labels = np.frombuffer(mnist.train_labels(), dtype=np.uint8)
+//! ```
+
+use crate::{LoadOptions, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use pyo3::exceptions::PyIOError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use std::path::PathBuf;
+
+/// The MNIST dataset, loaded from a directory of IDX files.
+#[pyclass(name = "Mnist", frozen)]
+struct PyMnist {
+    inner: Mnist,
+}
+
+#[pymethods]
+impl PyMnist {
+    #[new]
+    #[allow(clippy::needless_pass_by_value)] // `PyO3` extracts arguments by value.
+    fn new(path: PathBuf) -> PyResult<Self> {
+        // Load as `Mnist::new` does, but report missing or malformed files as an exception.
+        let inner = Mnist::load(&LoadOptions::new().cache_dir(&path)).map_err(|err| {
+            PyIOError::new_err(format!(
+                "Cannot load MNIST from \"{}\" ({err})",
+                path.display()
+            ))
+        })?;
+        Ok(PyMnist { inner })
+    }
+
+    /// Shape of a single image, `(rows, columns)`.
+    #[classattr]
+    fn image_shape() -> (usize, usize) {
+        (IMAGE_ROWS, IMAGE_COLUMNS)
+    }
+
+    /// Training images as row-major `uint8` pixels, one image after another.
+    fn train_images<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        self.images(py, Split::Train)
+    }
+
+    /// Training labels, one `uint8` per image.
+    fn train_labels<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.train_labels)
+    }
+
+    /// Testing images as row-major `uint8` pixels, one image after another.
+    fn test_images<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        self.images(py, Split::Test)
+    }
+
+    /// Testing labels, one `uint8` per image.
+    fn test_labels<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.test_labels)
+    }
+}
+
+impl PyMnist {
+    fn images<'py>(&self, py: Python<'py>, split: Split) -> Bound<'py, PyBytes> {
+        let images = self.inner.images(split);
+        let len = images.len() * IMAGE_ROWS * IMAGE_COLUMNS;
+        PyBytes::new_with(py, len, |buffer| {
+            for (chunk, image) in buffer.chunks_mut(IMAGE_ROWS * IMAGE_COLUMNS).zip(images) {
+                chunk.copy_from_slice(image);
+            }
+            Ok(())
+        })
+        .expect("Filling the buffer cannot fail.")
+    }
+}
+
+/// The `rust_mnist` Python module.
+#[pymodule]
+fn rust_mnist(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyMnist>()
+}
@@ -0,0 +1,93 @@
+use super::AsciiRamp;
+use crate::{IMAGE_COLUMNS, IMAGE_ROWS};
+use std::env;
+use std::fmt::Write;
+use std::io::{self, IsTerminal};
+
+/// Colors a terminal can display, deciding how [`image_to_ansi`] draws an image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorSupport {
+    /// No escape codes; images are drawn with the standard [`AsciiRamp`].
+    None,
+    /// The 24-step grayscale ramp of the 256-color palette.
+    Ansi256,
+    /// 24-bit color, showing every gray level exactly.
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Guess what stdout supports from whether it is a terminal and the `NO_COLOR`, `COLORTERM`
+    /// and `TERM` environment variables.
+    #[must_use]
+    pub fn detect() -> ColorSupport {
+        let term = env::var("TERM").unwrap_or_default();
+        if !io::stdout().is_terminal() || env::var_os("NO_COLOR").is_some() || term == "dumb" {
+            return ColorSupport::None;
+        }
+
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            ColorSupport::TrueColor
+        } else if term.contains("256color") {
+            ColorSupport::Ansi256
+        } else {
+            ColorSupport::None
+        }
+    }
+}
+
+/// Render an image in true grayscale with ANSI escape codes, drawing two pixel rows per line with
+/// upper half-block characters (`▀`), so a digit takes 28 columns by 14 lines.
+///
+/// With [`ColorSupport::None`], falls back to the plain text of [`AsciiRamp::standard`].
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::{image_to_ansi, ColorSupport, Mnist};
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+/// print!("{}", image_to_ansi(&mnist.train_data[5], ColorSupport::detect()));
+/// ```
+#[must_use]
+pub fn image_to_ansi(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], support: ColorSupport) -> String {
+    if support == ColorSupport::None {
+        return AsciiRamp::standard().render(image);
+    }
+
+    let mut text = String::new();
+    for rows in image.chunks(2 * IMAGE_COLUMNS) {
+        let (top, bottom) = rows.split_at(IMAGE_COLUMNS);
+        for (column, &upper) in top.iter().enumerate() {
+            // An odd number of rows would leave the last line's bottom half black.
+            let lower = bottom.get(column).copied().unwrap_or(0);
+            // Writing to a `String` cannot fail.
+            let _ = match support {
+                ColorSupport::TrueColor => write!(
+                    text,
+                    "\x1b[38;2;{upper};{upper};{upper}m\x1b[48;2;{lower};{lower};{lower}m▀"
+                ),
+                _ => write!(
+                    text,
+                    "\x1b[38;5;{}m\x1b[48;5;{}m▀",
+                    gray_256(upper),
+                    gray_256(lower)
+                ),
+            };
+        }
+        text.push_str("\x1b[0m\n");
+    }
+    text
+}
+
+/// Index of the closest entry on the 256-color palette's grayscale ramp (232 to 255), or black
+/// (16) and white (231) at the extremes.
+fn gray_256(pixel: u8) -> u8 {
+    // The ramp runs from level 8 to level 238 in steps of 10.
+    match pixel {
+        0..=3 => 16,
+        247..=255 => 231,
+        _ => 232 + ((pixel - 3) / 10).min(23),
+    }
+}
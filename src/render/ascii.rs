@@ -0,0 +1,81 @@
+use crate::{IMAGE_COLUMNS, IMAGE_ROWS};
+
+/// Characters used to render pixel intensities as text, from blank to fully inked.
+///
+/// Each pixel becomes two characters, so digits keep roughly their shape in a terminal.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::{AsciiRamp, Mnist};
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+/// print!("{}", AsciiRamp::standard().render(&mnist.train_data[5]));
+///
+/// // Dark ink on a light background, for light terminal themes.
+/// print!("{}", AsciiRamp::new(" .oO@").inverted().render(&mnist.train_data[5]));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AsciiRamp {
+    chars: Vec<char>,
+    inverted: bool,
+}
+
+impl AsciiRamp {
+    /// A ramp using the characters of `chars` in order, from blank to fully inked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chars` is empty.
+    #[must_use]
+    pub fn new(chars: &str) -> AsciiRamp {
+        let chars: Vec<char> = chars.chars().collect();
+        assert!(!chars.is_empty(), "Ramp must have at least one character.");
+        AsciiRamp {
+            chars,
+            inverted: false,
+        }
+    }
+
+    /// The ten-level ramp `" .:-=+*#%@"`.
+    #[must_use]
+    pub fn standard() -> AsciiRamp {
+        AsciiRamp::new(" .:-=+*#%@")
+    }
+
+    /// Swap the ends of the ramp, so blank pixels get the last character.
+    #[must_use]
+    pub fn inverted(mut self) -> AsciiRamp {
+        self.inverted = !self.inverted;
+        self
+    }
+
+    /// The character for a pixel value.
+    #[must_use]
+    pub fn char_for(&self, pixel: u8) -> char {
+        let pixel = if self.inverted { 255 - pixel } else { pixel };
+        let level = (usize::from(pixel) * (self.chars.len() - 1) + 127) / 255;
+        self.chars[level]
+    }
+
+    /// Render an image, one line per row.
+    #[must_use]
+    pub fn render(&self, image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> String {
+        let mut text = String::with_capacity(IMAGE_ROWS * (IMAGE_COLUMNS * 2 + 1));
+        for row in image.chunks(IMAGE_COLUMNS) {
+            for &pixel in row {
+                let c = self.char_for(pixel);
+                text.push(c);
+                text.push(c);
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl Default for AsciiRamp {
+    fn default() -> AsciiRamp {
+        AsciiRamp::standard()
+    }
+}
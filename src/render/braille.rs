@@ -0,0 +1,37 @@
+use crate::{IMAGE_COLUMNS, IMAGE_ROWS};
+
+// Bit of each dot in a braille character, indexed by row then column of the 2x4 cell.
+const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Render an image with Unicode braille patterns, one dot per pixel, so a digit fits in 14
+/// characters by 7 lines. Pixels brighter than `threshold` are drawn as raised dots.
+///
+/// # Examples
+/// ```
+/// use rust_mnist::image_to_braille;
+///
+/// let text = image_to_braille(&[255; 784], 127);
+/// assert_eq!(text.lines().count(), 7);
+/// assert!(text.lines().all(|line| line == "⣿".repeat(14)));
+/// ```
+#[must_use]
+pub fn image_to_braille(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], threshold: u8) -> String {
+    // 28 pixels divide evenly into 4-row, 2-column cells.
+    let mut text = String::new();
+    for top in (0..IMAGE_ROWS).step_by(4) {
+        for left in (0..IMAGE_COLUMNS).step_by(2) {
+            let mut pattern = 0;
+            for (dy, row) in DOTS.iter().enumerate() {
+                for (dx, bit) in row.iter().enumerate() {
+                    if image[(top + dy) * IMAGE_COLUMNS + left + dx] > threshold {
+                        pattern |= bit;
+                    }
+                }
+            }
+            // Every value in the braille block is a valid `char`, so the fallback is never used.
+            text.push(char::from_u32(0x2800 + pattern).unwrap_or(' '));
+        }
+        text.push('\n');
+    }
+    text
+}
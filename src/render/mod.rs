@@ -0,0 +1,284 @@
+//! Text renderings of images, for terminals, logs and test assertions.
+
+mod ansi;
+mod ascii;
+mod braille;
+#[cfg(feature = "termgfx")]
+mod termgfx;
+
+pub use ansi::{image_to_ansi, ColorSupport};
+pub use ascii::AsciiRamp;
+pub use braille::image_to_braille;
+#[cfg(feature = "termgfx")]
+pub use termgfx::{image_to_graphics, GraphicsProtocol};
+
+use crate::{IMAGE_COLUMNS, IMAGE_ROWS};
+use std::io;
+use std::io::Write;
+
+/// Space between neighbouring images printed by [`write_grid`].
+const GRID_GAP: &str = "  ";
+
+/// Length of a full bar in the chart written by [`write_prediction`].
+const PREDICTION_BAR_WIDTH: u8 = 20;
+
+/// Print a sample image.
+///
+/// # Panics
+///
+/// Panics if writing to stdout fails.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::{print_image, Mnist};
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+///
+/// // Print one image (the one at index 5).
+/// print_image(&mnist.train_data[5], mnist.train_labels[5]);
+/// ```
+pub fn print_image(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], label: u8) {
+    write_image(&mut io::stdout().lock(), image, label)
+        .unwrap_or_else(|err| panic!("Failed to print image: {}", err));
+}
+
+/// Write a sample image and its label, as printed by [`print_image`].
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+///
+/// # Examples
+/// ```
+/// use rust_mnist::write_image;
+///
+/// let mut log = Vec::new();
+/// write_image(&mut log, &[0; 784], 3)?;
+/// assert!(String::from_utf8(log).unwrap().starts_with("Sample image label: 3"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_image(
+    writer: &mut impl Write,
+    image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS],
+    label: u8,
+) -> io::Result<()> {
+    writeln!(writer, "Sample image label: {label} \nSample image:")?;
+    writer.write_all(image_to_string(image).as_bytes())
+}
+
+/// Print several sample images side by side, `cols` to a row, each with its label underneath.
+///
+/// # Panics
+///
+/// Panics if `cols` is zero or writing to stdout fails.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::{print_grid, Mnist};
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+///
+/// // Print the first eight training images, four to a row.
+/// let samples: Vec<_> = mnist.train_iter().take(8).collect();
+/// print_grid(&samples, 4);
+/// ```
+pub fn print_grid(samples: &[(&[u8; IMAGE_ROWS * IMAGE_COLUMNS], u8)], cols: usize) {
+    write_grid(&mut io::stdout().lock(), samples, cols)
+        .unwrap_or_else(|err| panic!("Failed to print images: {}", err));
+}
+
+/// Write several sample images side by side, as printed by [`print_grid`].
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+///
+/// # Panics
+///
+/// Panics if `cols` is zero.
+///
+/// # Examples
+/// ```
+/// use rust_mnist::write_grid;
+///
+/// let mut log = Vec::new();
+/// write_grid(&mut log, &[(&[0; 784], 3), (&[0; 784], 5), (&[0; 784], 7)], 2)?;
+/// let text = String::from_utf8(log).unwrap();
+/// assert_eq!(text.lines().count(), 2 * 29 + 1);
+/// assert!(text.lines().nth(28).unwrap().starts_with("Label: 3"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_grid(
+    writer: &mut impl Write,
+    samples: &[(&[u8; IMAGE_ROWS * IMAGE_COLUMNS], u8)],
+    cols: usize,
+) -> io::Result<()> {
+    assert!(cols > 0, "Grid must have at least one column.");
+
+    for (index, line_of_samples) in samples.chunks(cols).enumerate() {
+        if index > 0 {
+            writeln!(writer)?;
+        }
+        let rendered: Vec<String> = line_of_samples
+            .iter()
+            .map(|(image, _)| image_to_string(image))
+            .collect();
+        let mut lines: Vec<_> = rendered.iter().map(|text| text.lines()).collect();
+        for _ in 0..IMAGE_ROWS {
+            let row: Vec<&str> = lines.iter_mut().filter_map(Iterator::next).collect();
+            writeln!(writer, "{}", row.join(GRID_GAP))?;
+        }
+
+        // Pad each label to the width of an image so they line up under their digits.
+        let labels: Vec<String> = line_of_samples
+            .iter()
+            .map(|(_, label)| {
+                format!(
+                    "{:width$}",
+                    format!("Label: {label}"),
+                    width = IMAGE_COLUMNS * 2
+                )
+            })
+            .collect();
+        writeln!(writer, "{}", labels.join(GRID_GAP).trim_end())?;
+    }
+    Ok(())
+}
+
+/// Print an image next to a bar chart of a model's class probabilities, marking the true and
+/// predicted classes and flagging a wrong prediction.
+///
+/// # Panics
+///
+/// Panics if writing to stdout fails.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::{print_prediction, Mnist};
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+///
+/// // Probabilities as produced by a model's softmax output.
+/// let probs = [0.01, 0.02, 0.05, 0.6, 0.02, 0.2, 0.02, 0.03, 0.03, 0.02];
+/// print_prediction(&mnist.test_data[0], mnist.test_labels[0], 3, &probs);
+/// ```
+pub fn print_prediction(
+    image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS],
+    true_label: u8,
+    predicted_label: u8,
+    probs: &[f64],
+) {
+    write_prediction(
+        &mut io::stdout().lock(),
+        image,
+        true_label,
+        predicted_label,
+        probs,
+    )
+    .unwrap_or_else(|err| panic!("Failed to print prediction: {}", err));
+}
+
+/// Write an image and a bar chart of class probabilities, as printed by [`print_prediction`].
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+///
+/// # Examples
+/// ```
+/// use rust_mnist::write_prediction;
+///
+/// let mut probs = [0.0; 10];
+/// probs[5] = 1.0;
+/// let mut log = Vec::new();
+/// write_prediction(&mut log, &[0; 784], 3, 5, &probs)?;
+/// let text = String::from_utf8(log).unwrap();
+/// assert!(text.starts_with("Label: 3  Predicted: 5  WRONG"));
+/// assert!(text.contains("5 |#################### 1.00 <- predicted"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_prediction(
+    writer: &mut impl Write,
+    image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS],
+    true_label: u8,
+    predicted_label: u8,
+    probs: &[f64],
+) -> io::Result<()> {
+    let verdict = if true_label == predicted_label {
+        "correct"
+    } else {
+        "WRONG"
+    };
+    writeln!(
+        writer,
+        "Label: {true_label}  Predicted: {predicted_label}  {verdict}"
+    )?;
+
+    let chart: Vec<String> = probs
+        .iter()
+        .enumerate()
+        .map(|(class, &prob)| {
+            let filled = (0..PREDICTION_BAR_WIDTH)
+                .filter(|&step| f64::from(step) + 0.5 < prob * f64::from(PREDICTION_BAR_WIDTH))
+                .count();
+            let bar = "#".repeat(filled) + &".".repeat(usize::from(PREDICTION_BAR_WIDTH) - filled);
+            let marker = match (
+                class == usize::from(predicted_label),
+                class == usize::from(true_label),
+            ) {
+                (true, true) => " <- predicted, true",
+                (true, false) => " <- predicted",
+                (false, true) => " <- true",
+                (false, false) => "",
+            };
+            format!("{class} |{bar} {prob:.2}{marker}")
+        })
+        .collect();
+
+    // Centre the chart vertically beside the image.
+    let image_text = image_to_string(image);
+    let image_lines: Vec<&str> = image_text.lines().collect();
+    let first = IMAGE_ROWS.saturating_sub(chart.len()) / 2;
+    for line in 0..IMAGE_ROWS.max(first + chart.len()) {
+        let left = image_lines.get(line).copied().unwrap_or("");
+        match line.checked_sub(first).and_then(|bar| chart.get(bar)) {
+            Some(bar) => writeln!(
+                writer,
+                "{left:width$}{GRID_GAP}{bar}",
+                width = IMAGE_COLUMNS * 2
+            )?,
+            None => writeln!(writer, "{left}")?,
+        }
+    }
+    Ok(())
+}
+
+/// Render an image as text, one line per row, with `##` for inked pixels and `__` for blank ones.
+/// Use an [`AsciiRamp`] to show shades of gray.
+///
+/// # Examples
+/// ```
+/// use rust_mnist::image_to_string;
+///
+/// let mut image = [0; 784];
+/// image[0] = 255;
+/// let text = image_to_string(&image);
+/// assert_eq!(text.lines().count(), 28);
+/// assert!(text.starts_with("##____"));
+/// ```
+#[must_use]
+pub fn image_to_string(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]) -> String {
+    let mut text = String::with_capacity(IMAGE_ROWS * (IMAGE_COLUMNS * 2 + 1));
+
+    // Render each row.
+    for row in image.chunks(IMAGE_COLUMNS) {
+        for &pixel in row {
+            text.push_str(if pixel == 0 { "__" } else { "##" });
+        }
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,163 @@
+use super::AsciiRamp;
+use crate::formats::jsonl::base64;
+use crate::{IMAGE_COLUMNS, IMAGE_ROWS};
+use std::env;
+use std::fmt::Write;
+use std::io::{self, IsTerminal};
+
+/// Number of gray levels in the Sixel palette.
+const SIXEL_GRAYS: u16 = 16;
+
+/// Largest payload of a single Kitty graphics escape sequence, in base64 bytes.
+const KITTY_CHUNK: usize = 4096;
+
+/// Terminal graphics protocols able to show an image pixel for pixel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GraphicsProtocol {
+    /// DEC Sixel graphics, supported by xterm (with `-ti vt340`), mlterm, foot and others.
+    Sixel,
+    /// The Kitty terminal graphics protocol, supported by kitty, Ghostty and others.
+    Kitty,
+}
+
+impl GraphicsProtocol {
+    /// Guess which protocol stdout supports from whether it is a terminal and the `TERM`,
+    /// `TERM_PROGRAM` and `KITTY_WINDOW_ID` environment variables, or `None` if it supports
+    /// neither.
+    #[must_use]
+    pub fn detect() -> Option<GraphicsProtocol> {
+        if !io::stdout().is_terminal() {
+            return None;
+        }
+
+        let term = env::var("TERM").unwrap_or_default();
+        let program = env::var("TERM_PROGRAM").unwrap_or_default();
+        if env::var_os("KITTY_WINDOW_ID").is_some()
+            || term == "xterm-kitty"
+            || term == "xterm-ghostty"
+            || program == "WezTerm"
+        {
+            Some(GraphicsProtocol::Kitty)
+        } else if term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel") {
+            Some(GraphicsProtocol::Sixel)
+        } else {
+            None
+        }
+    }
+}
+
+/// Render an image as inline terminal graphics, with each pixel drawn as a `scale` by `scale`
+/// square of screen pixels.
+///
+/// With no protocol, falls back to the plain text of [`AsciiRamp::standard`], so the output of
+/// [`GraphicsProtocol::detect`] can be passed straight in.
+///
+/// # Panics
+///
+/// Panics if `scale` is zero.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::{image_to_graphics, GraphicsProtocol, Mnist};
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+/// print!("{}", image_to_graphics(&mnist.train_data[5], GraphicsProtocol::detect(), 4));
+/// ```
+#[must_use]
+pub fn image_to_graphics(
+    image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS],
+    protocol: Option<GraphicsProtocol>,
+    scale: usize,
+) -> String {
+    assert!(scale > 0, "Scale must be at least one.");
+    let mut text = match protocol {
+        Some(GraphicsProtocol::Sixel) => sixel(image, scale),
+        Some(GraphicsProtocol::Kitty) => kitty(image, scale),
+        None => return AsciiRamp::standard().render(image),
+    };
+    text.push('\n');
+    text
+}
+
+/// Pixel of `image` covering the screen pixel at `(x, y)` when drawn at `scale`.
+fn scaled_pixel(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], scale: usize, x: usize, y: usize) -> u8 {
+    image[(y / scale) * IMAGE_COLUMNS + x / scale]
+}
+
+/// Encode an image as a Sixel escape sequence using a palette of [`SIXEL_GRAYS`] grays.
+fn sixel(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], scale: usize) -> String {
+    let (width, height) = (IMAGE_COLUMNS * scale, IMAGE_ROWS * scale);
+    let level =
+        |x, y| (u16::from(scaled_pixel(image, scale, x, y)) * (SIXEL_GRAYS - 1) + 127) / 255;
+
+    // Writing to a `String` cannot fail.
+    let mut text = String::new();
+    let _ = write!(text, "\x1bPq\"1;1;{width};{height}");
+    for gray in 0..SIXEL_GRAYS {
+        let percent = gray * 100 / (SIXEL_GRAYS - 1);
+        let _ = write!(text, "#{gray};2;{percent};{percent};{percent}");
+    }
+
+    // Each sixel covers a column of six pixels; every band is drawn once per gray it contains.
+    for top in (0..height).step_by(6) {
+        let rows = top..height.min(top + 6);
+        for gray in 0..SIXEL_GRAYS {
+            let sixels: Vec<u8> = (0..width)
+                .map(|x| {
+                    rows.clone()
+                        .filter(|&y| level(x, y) == gray)
+                        .fold(0, |bits, y| bits | 1 << (y - top))
+                })
+                .collect();
+            if sixels.iter().all(|&bits| bits == 0) {
+                continue;
+            }
+
+            let _ = write!(text, "#{gray}");
+            for run in sixels.chunk_by(|a, b| a == b) {
+                let symbol = char::from(63 + run[0]);
+                if run.len() > 3 {
+                    let _ = write!(text, "!{}{symbol}", run.len());
+                } else {
+                    text.extend(std::iter::repeat_n(symbol, run.len()));
+                }
+            }
+            text.push('$');
+        }
+        text.push('-');
+    }
+    text.push_str("\x1b\\");
+    text
+}
+
+/// Encode an image as Kitty graphics escape sequences carrying raw RGB pixels.
+fn kitty(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], scale: usize) -> String {
+    let (width, height) = (IMAGE_COLUMNS * scale, IMAGE_ROWS * scale);
+    let mut rgb = Vec::with_capacity(width * height * 3);
+    for y in 0..height {
+        for x in 0..width {
+            rgb.extend_from_slice(&[scaled_pixel(image, scale, x, y); 3]);
+        }
+    }
+    let mut payload = String::new();
+    base64(&rgb, &mut payload);
+
+    // Large images must be sent in chunks, each flagged with whether more follow.
+    let mut text = String::new();
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(KITTY_CHUNK).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        // Writing to a `String` cannot fail.
+        let _ = if i == 0 {
+            write!(text, "\x1b_Ga=T,f=24,s={width},v={height},m={more};")
+        } else {
+            write!(text, "\x1b_Gm={more};")
+        };
+        // The payload is base64, so every chunk is ASCII.
+        text.push_str(&String::from_utf8_lossy(chunk));
+        text.push_str("\x1b\\");
+    }
+    text
+}
@@ -0,0 +1,77 @@
+//! Pixel-by-pixel ("sequential") views of the dataset.
+
+use crate::{Mnist, IMAGE_COLUMNS, IMAGE_ROWS};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// The order in which an image's pixels are read out into a sequence.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScanOrder {
+    /// Row by row, left to right (sequential MNIST, "sMNIST").
+    RowMajor,
+    /// Column by column, top to bottom.
+    ColumnMajor,
+    /// A fixed random permutation of the pixels, generated from `seed` (permuted sequential
+    /// MNIST, "psMNIST"). The generator is the portable `ChaCha8Rng`, so the same seed produces
+    /// the same permutation on every platform and after dependency updates.
+    Permuted { seed: u64 },
+}
+
+impl ScanOrder {
+    /// The row-major pixel index read at each step of the sequence.
+    #[must_use]
+    pub fn indices(&self) -> Vec<usize> {
+        match *self {
+            ScanOrder::RowMajor => (0..IMAGE_ROWS * IMAGE_COLUMNS).collect(),
+            ScanOrder::ColumnMajor => (0..IMAGE_COLUMNS)
+                .flat_map(|col| (0..IMAGE_ROWS).map(move |row| row * IMAGE_COLUMNS + col))
+                .collect(),
+            ScanOrder::Permuted { seed } => {
+                let mut indices: Vec<usize> = (0..IMAGE_ROWS * IMAGE_COLUMNS).collect();
+                indices.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+                indices
+            }
+        }
+    }
+}
+
+impl Mnist {
+    /// Turn every image into a length-784 pixel sequence read out in the given order, for
+    /// recurrent and state-space model benchmarks.
+    ///
+    /// Labels are unchanged; only the order of the pixels within each image is.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{Mnist, ScanOrder};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let psmnist = mnist.as_sequences(ScanOrder::Permuted { seed: 42 });
+    /// ```
+    #[must_use]
+    pub fn as_sequences(&self, order: ScanOrder) -> Mnist {
+        let indices = order.indices();
+        let reorder = |images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]]| {
+            images
+                .iter()
+                .map(|image| {
+                    let mut sequence = [0; IMAGE_ROWS * IMAGE_COLUMNS];
+                    for (step, &pixel) in indices.iter().enumerate() {
+                        sequence[step] = image[pixel];
+                    }
+                    sequence
+                })
+                .collect()
+        };
+
+        Mnist {
+            train_data: reorder(&self.train_data),
+            test_data: reorder(&self.test_data),
+            train_labels: self.train_labels.clone(),
+            test_labels: self.test_labels.clone(),
+        }
+    }
+}
@@ -0,0 +1,476 @@
+//! Where the dataset's files are read from: a local directory, memory, or a web server.
+
+use crate::open_idx;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// A place holding named files, such as the four IDX files of the dataset, that
+/// [`Mnist::load`](crate::Mnist::load) and the other loaders read from.
+///
+/// Names are paths relative to the source, with `/` between directories, such as
+/// `train/7/0.png`.
+///
+/// Implement it to load from anywhere else, such as an archive or a private bucket; only
+/// [`DataSource::open`] and [`DataSource::exists`] are required.
+pub trait DataSource {
+    /// Open the file called `name` for reading.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`io::ErrorKind::NotFound`] if there is no such file, or any
+    /// other error if it cannot be opened.
+    fn open(&self, name: &str) -> io::Result<Box<dyn Read + '_>>;
+
+    /// Whether there is a file called `name`.
+    fn exists(&self, name: &str) -> bool;
+
+    /// Read all of the file called `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or read.
+    fn read(&self, name: &str) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.open(name)?.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Size of the file called `name` in bytes, if it can be found out without reading it. Used
+    /// to report download progress; `None` unless implemented.
+    fn size(&self, name: &str) -> Option<u64> {
+        let _ = name;
+        None
+    }
+
+    /// Names of the entries directly inside the directory `dir`, `""` being the top, sorted and
+    /// with a trailing `/` on directories. Needed to load folder-per-class image trees.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be listed, of kind
+    /// [`io::ErrorKind::Unsupported`] unless implemented.
+    fn list(&self, dir: &str) -> io::Result<Vec<String>> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("Cannot list \"{dir}\": the source does not support listing."),
+        ))
+    }
+}
+
+/// `name` inside the directory `dir` of a [`DataSource`], `""` being the top.
+pub(crate) fn join(dir: &str, name: &str) -> String {
+    let dir = dir.trim_end_matches('/');
+    if dir.is_empty() {
+        name.to_string()
+    } else {
+        format!("{dir}/{name}")
+    }
+}
+
+/// Files in a local directory, as read by [`Mnist::new`](crate::Mnist::new).
+///
+/// With the `zstd` feature, a file missing from the directory is read from a zstd-compressed
+/// copy next to it, at `<name>.zst`, instead.
+#[derive(Clone, Debug)]
+pub struct LocalDir {
+    dir: PathBuf,
+}
+
+impl LocalDir {
+    /// Source reading the files in `dir`.
+    #[must_use]
+    pub fn new(dir: &Path) -> LocalDir {
+        LocalDir {
+            dir: dir.to_path_buf(),
+        }
+    }
+}
+
+impl DataSource for LocalDir {
+    fn open(&self, name: &str) -> io::Result<Box<dyn Read + '_>> {
+        open_idx(&self.dir.join(name))
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        self.dir.join(name).exists()
+            || (cfg!(feature = "zstd") && self.dir.join(format!("{name}.zst")).exists())
+    }
+
+    fn size(&self, name: &str) -> Option<u64> {
+        fs::metadata(self.dir.join(name))
+            .ok()
+            .map(|metadata| metadata.len())
+    }
+
+    fn list(&self, dir: &str) -> io::Result<Vec<String>> {
+        let mut names = Vec::new();
+        for entry in fs::read_dir(self.dir.join(dir))? {
+            let entry = entry?;
+            let mut name = entry.file_name().to_string_lossy().into_owned();
+            if entry.file_type()?.is_dir() {
+                name.push('/');
+            }
+            names.push(name);
+        }
+        names.sort();
+        Ok(names)
+    }
+}
+
+/// Files held in memory, such as ones fetched or generated by the caller.
+///
+/// # Examples
+/// ```no_run
+/// use std::fs;
+/// use rust_mnist::{LoadOptions, MemorySource, Mnist};
+///
+/// let mut source = MemorySource::new();
+/// for name in [
+///     "train-images-idx3-ubyte",
+///     "train-labels-idx1-ubyte",
+///     "t10k-images-idx3-ubyte",
+///     "t10k-labels-idx1-ubyte",
+/// ] {
+///     source.insert(name, fs::read(name)?);
+/// }
+/// let mnist = Mnist::load(&LoadOptions::new().source(source))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemorySource {
+    files: HashMap<String, Vec<u8>>,
+}
+
+impl MemorySource {
+    /// Source holding no files.
+    #[must_use]
+    pub fn new() -> MemorySource {
+        MemorySource::default()
+    }
+
+    /// Add a file called `name`, replacing any file of that name.
+    pub fn insert(&mut self, name: impl Into<String>, bytes: impl Into<Vec<u8>>) {
+        self.files.insert(name.into(), bytes.into());
+    }
+}
+
+impl DataSource for MemorySource {
+    fn open(&self, name: &str) -> io::Result<Box<dyn Read + '_>> {
+        match self.files.get(name) {
+            Some(bytes) => Ok(Box::new(bytes.as_slice())),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No file called {name} in memory."),
+            )),
+        }
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        self.files.contains_key(name)
+    }
+
+    fn size(&self, name: &str) -> Option<u64> {
+        self.files.get(name).map(|bytes| bytes.len() as u64)
+    }
+
+    fn list(&self, dir: &str) -> io::Result<Vec<String>> {
+        let prefix = join(dir, "");
+        let mut names: Vec<String> = self
+            .files
+            .keys()
+            .filter_map(|name| name.strip_prefix(&prefix))
+            .map(|rest| match rest.find('/') {
+                Some(slash) => rest[..=slash].to_string(),
+                None => rest.to_string(),
+            })
+            .collect();
+        if names.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No directory called \"{dir}\" in memory."),
+            ));
+        }
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+}
+
+/// Files served over HTTP or HTTPS from under a base URL, such as a dataset's public mirror.
+///
+/// # Examples
+/// ```no_run
+/// use rust_mnist::{Dataset, HttpSource, LoadOptions, Mnist};
+///
+/// // Load straight from the mirror, without saving the files.
+/// let source = HttpSource::new(Dataset::Fashion.base_url()).gzipped(true);
+/// let fashion = Mnist::load(&LoadOptions::new().source(source))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "download")]
+#[derive(Clone, Debug)]
+pub struct HttpSource {
+    base_url: String,
+    gzipped: bool,
+}
+
+#[cfg(feature = "download")]
+impl HttpSource {
+    /// Source fetching the file called `name` from `<base_url>/<name>`.
+    #[must_use]
+    pub fn new(base_url: &str) -> HttpSource {
+        HttpSource {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            gzipped: false,
+        }
+    }
+
+    /// Whether the server holds gzipped files, at `<base_url>/<name>.gz`, to be decompressed
+    /// while reading, as the dataset mirrors do. Off by default.
+    #[must_use]
+    pub fn gzipped(mut self, gzipped: bool) -> HttpSource {
+        self.gzipped = gzipped;
+        self
+    }
+
+    fn url(&self, name: &str) -> String {
+        let extension = if self.gzipped { ".gz" } else { "" };
+        format!("{}/{name}{extension}", self.base_url)
+    }
+}
+
+#[cfg(feature = "download")]
+impl DataSource for HttpSource {
+    fn open(&self, name: &str) -> io::Result<Box<dyn Read + '_>> {
+        let reader = match ::ureq::get(&self.url(name)).call() {
+            Ok(response) => response.into_reader(),
+            Err(::ureq::Error::Status(404, _)) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} was not found.", self.url(name)),
+                ))
+            }
+            Err(err) => return Err(io::Error::other(err)),
+        };
+        if self.gzipped {
+            Ok(Box::new(::flate2::read::GzDecoder::new(reader)))
+        } else {
+            Ok(reader)
+        }
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        ::ureq::head(&self.url(name)).call().is_ok()
+    }
+
+    fn size(&self, name: &str) -> Option<u64> {
+        let response = ::ureq::head(&self.url(name)).call().ok()?;
+        response.header("Content-Length")?.parse().ok()
+    }
+}
+
+/// Files in a publicly readable cloud object store bucket, fetched over HTTPS.
+///
+/// Requests are not signed, so only objects readable by anyone can be fetched; implement
+/// [`DataSource`] with the store's own client for private buckets.
+///
+/// # Examples
+/// ```no_run
+/// use rust_mnist::{LoadOptions, Mnist, ObjectStore};
+///
+/// // The bucket behind the original MNIST mirror.
+/// let source = ObjectStore::s3("ossci-datasets", "us-east-1", "mnist").gzipped(true);
+/// let mnist = Mnist::load(&LoadOptions::new().source(source))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "download")]
+#[derive(Clone, Debug)]
+pub struct ObjectStore {
+    http: HttpSource,
+}
+
+#[cfg(feature = "download")]
+impl ObjectStore {
+    /// Objects under `prefix` in the Amazon S3 bucket `bucket`, in `region`.
+    #[must_use]
+    pub fn s3(bucket: &str, region: &str, prefix: &str) -> ObjectStore {
+        ObjectStore::at(
+            &format!("https://{bucket}.s3.{region}.amazonaws.com"),
+            prefix,
+        )
+    }
+
+    /// Objects under `prefix` in the Google Cloud Storage bucket `bucket`.
+    #[must_use]
+    pub fn gcs(bucket: &str, prefix: &str) -> ObjectStore {
+        ObjectStore::at(&format!("https://storage.googleapis.com/{bucket}"), prefix)
+    }
+
+    /// Whether the objects are gzipped, with a `.gz` suffix, as for [`HttpSource::gzipped`].
+    #[must_use]
+    pub fn gzipped(mut self, gzipped: bool) -> ObjectStore {
+        self.http = self.http.gzipped(gzipped);
+        self
+    }
+
+    fn at(endpoint: &str, prefix: &str) -> ObjectStore {
+        let prefix = prefix.trim_matches('/');
+        let base_url = if prefix.is_empty() {
+            endpoint.to_string()
+        } else {
+            format!("{endpoint}/{prefix}")
+        };
+        ObjectStore {
+            http: HttpSource::new(&base_url),
+        }
+    }
+}
+
+#[cfg(feature = "download")]
+impl DataSource for ObjectStore {
+    fn open(&self, name: &str) -> io::Result<Box<dyn Read + '_>> {
+        self.http.open(name)
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        self.http.exists(name)
+    }
+
+    fn size(&self, name: &str) -> Option<u64> {
+        self.http.size(name)
+    }
+}
+
+/// Files in a local directory, memory-mapped rather than read through a buffer, so their bytes
+/// are paged in straight from the OS file cache.
+///
+/// Each file is mapped when opened and unmapped once read, so nothing stays mapped after
+/// loading. Only worth it for large files already in the file cache; `mnist bench` compares it
+/// with [`LocalDir`].
+///
+/// # Examples
+/// ```no_run
+/// use std::path::Path;
+/// use rust_mnist::{LoadOptions, MmapDir, Mnist};
+///
+/// let mnist = Mnist::load(&LoadOptions::new().source(MmapDir::new(Path::new("data"))))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[cfg(feature = "mmap")]
+#[derive(Clone, Debug)]
+pub struct MmapDir {
+    dir: PathBuf,
+}
+
+#[cfg(feature = "mmap")]
+impl MmapDir {
+    /// Source mapping the files in `dir`.
+    #[must_use]
+    pub fn new(dir: &Path) -> MmapDir {
+        MmapDir {
+            dir: dir.to_path_buf(),
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl DataSource for MmapDir {
+    fn open(&self, name: &str) -> io::Result<Box<dyn Read + '_>> {
+        let file = fs::File::open(self.dir.join(name))?;
+        // SAFETY: the mapping is only read while the file is loaded, and the dataset's files are
+        // not expected to be modified by anything else meanwhile.
+        let map = unsafe { ::memmap2::Mmap::map(&file)? };
+        Ok(Box::new(io::Cursor::new(map)))
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        self.dir.join(name).is_file()
+    }
+
+    fn size(&self, name: &str) -> Option<u64> {
+        fs::metadata(self.dir.join(name))
+            .ok()
+            .map(|metadata| metadata.len())
+    }
+
+    fn list(&self, dir: &str) -> io::Result<Vec<String>> {
+        LocalDir::new(&self.dir).list(dir)
+    }
+}
+
+/// Another source whose files are kept in memory once first read, so loading them again, as
+/// when reloading the dataset in a long-running process, reads nothing from the source.
+///
+/// The cache holds each file's bytes as read, so costs as much memory as the files take on the
+/// source; drop the source to free it.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::Path;
+/// use rust_mnist::{CachedSource, LoadOptions, LocalDir, Mnist};
+///
+/// let options = LoadOptions::new().source(CachedSource::new(LocalDir::new(Path::new("data"))));
+/// let mnist = Mnist::load(&options)?;
+/// // Parsed again, but from memory.
+/// let again = Mnist::load(&options)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct CachedSource<S> {
+    inner: S,
+    files: Mutex<HashMap<String, Arc<[u8]>>>,
+}
+
+impl<S: DataSource> CachedSource<S> {
+    /// Source caching the files read from `inner`.
+    #[must_use]
+    pub fn new(inner: S) -> CachedSource<S> {
+        CachedSource {
+            inner,
+            files: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Bytes of the file called `name`, read from the inner source unless already cached.
+    fn cached(&self, name: &str) -> io::Result<Arc<[u8]>> {
+        // A panic elsewhere cannot leave the map half-updated, so a poisoned lock is still sound.
+        let mut files = self.files.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(bytes) = files.get(name) {
+            return Ok(Arc::clone(bytes));
+        }
+        let bytes: Arc<[u8]> = self.inner.read(name)?.into();
+        files.insert(name.to_string(), Arc::clone(&bytes));
+        Ok(bytes)
+    }
+}
+
+impl<S: DataSource> DataSource for CachedSource<S> {
+    fn open(&self, name: &str) -> io::Result<Box<dyn Read + '_>> {
+        Ok(Box::new(io::Cursor::new(self.cached(name)?)))
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        let files = self.files.lock().unwrap_or_else(PoisonError::into_inner);
+        files.contains_key(name) || self.inner.exists(name)
+    }
+
+    fn read(&self, name: &str) -> io::Result<Vec<u8>> {
+        Ok(self.cached(name)?.to_vec())
+    }
+
+    fn size(&self, name: &str) -> Option<u64> {
+        let files = self.files.lock().unwrap_or_else(PoisonError::into_inner);
+        match files.get(name) {
+            Some(bytes) => Some(bytes.len() as u64),
+            None => self.inner.size(name),
+        }
+    }
+
+    fn list(&self, dir: &str) -> io::Result<Vec<String>> {
+        self.inner.list(dir)
+    }
+}
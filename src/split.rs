@@ -0,0 +1,135 @@
+//! Ways of carving the dataset into smaller datasets.
+
+use crate::subset::gather;
+use crate::{ImageSet, Mnist, IMAGE_COLUMNS, IMAGE_ROWS};
+use std::convert::TryFrom;
+
+/// How labels are assigned within each task produced by [`Mnist::split_tasks`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TaskLabels {
+    /// Keep the original digit labels (the class-incremental setting).
+    Original,
+    /// Remap each task's classes to `0..n`, in the order they are listed for that task (the
+    /// task-incremental or "multi-head" setting).
+    Local,
+}
+
+impl Mnist {
+    /// Split the dataset into a sequence of continual-learning tasks, one per class group.
+    ///
+    /// Each task keeps only the training and testing samples whose label appears in its group.
+    /// Passing `&[&[0, 1], &[2, 3], &[4, 5], &[6, 7], &[8, 9]]` produces the standard Split-MNIST
+    /// task sequence.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{Mnist, TaskLabels};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let tasks = mnist.split_tasks(&[&[0, 1], &[2, 3], &[4, 5], &[6, 7], &[8, 9]], TaskLabels::Local);
+    ///
+    /// // Every task is now a binary classification problem.
+    /// assert!(tasks[4].train_labels.iter().all(|&label| label < 2));
+    /// ```
+    #[must_use]
+    pub fn split_tasks(&self, tasks: &[&[u8]], labels: TaskLabels) -> Vec<Mnist> {
+        tasks
+            .iter()
+            .map(|classes| {
+                let (train_data, train_labels) =
+                    select_classes(&self.train_data, &self.train_labels, classes, labels);
+                let (test_data, test_labels) =
+                    select_classes(&self.test_data, &self.test_labels, classes, labels);
+                Mnist {
+                    train_data,
+                    test_data,
+                    train_labels,
+                    test_labels,
+                }
+            })
+            .collect()
+    }
+
+    /// Split the training data into training and validation sets, deciding each sample's side
+    /// from a hash of its contents.
+    ///
+    /// Roughly `ratio` of the samples end up in the validation set. Because the assignment only
+    /// depends on the image, its label and `salt`, it stays the same no matter how the data is
+    /// ordered, and survives re-downloading the dataset. Change `salt` to draw a different split.
+    ///
+    /// Returns `(train, validation)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is not between 0 and 1.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let (train, validation) = mnist.split_by_hash(0.1, 0);
+    /// ```
+    #[must_use]
+    pub fn split_by_hash(&self, ratio: f64, salt: u64) -> (ImageSet, ImageSet) {
+        assert!(
+            (0.0..=1.0).contains(&ratio),
+            "Validation ratio must be between 0 and 1."
+        );
+
+        let (validation, train): (Vec<usize>, Vec<usize>) =
+            (0..self.train_data.len()).partition(|&index| {
+                let hash = content_hash(&self.train_data[index], self.train_labels[index], salt);
+
+                // Use the top 32 bits of the hash as a uniformly distributed fraction in [0, 1).
+                let fraction = f64::from(u32::try_from(hash >> 32).unwrap()) / 4_294_967_296.0;
+                fraction < ratio
+            });
+        (
+            gather(&self.train_data, &self.train_labels, &train),
+            gather(&self.train_data, &self.train_labels, &validation),
+        )
+    }
+}
+
+/// Copy out the images and labels belonging to `classes`, relabelling them as requested.
+fn select_classes(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+    classes: &[u8],
+    task_labels: TaskLabels,
+) -> (Vec<[u8; IMAGE_ROWS * IMAGE_COLUMNS]>, Vec<u8>) {
+    images
+        .iter()
+        .zip(labels)
+        .filter_map(|(image, &label)| {
+            let position = classes.iter().position(|&class| class == label)?;
+            let label = match task_labels {
+                TaskLabels::Original => label,
+                TaskLabels::Local => u8::try_from(position).unwrap(),
+            };
+            Some((*image, label))
+        })
+        .unzip()
+}
+
+/// 64-bit FNV-1a hash of a salted sample. Unlike `std`'s hashers, its output is fixed forever.
+fn content_hash(image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS], label: u8, salt: u64) -> u64 {
+    let hash = fnv1a(FNV_OFFSET_BASIS, &salt.to_le_bytes());
+    fnv1a(fnv1a(hash, image), &[label])
+}
+
+/// Starting state of a 64-bit FNV-1a hash.
+pub(crate) const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Feed `bytes` into a 64-bit FNV-1a hash whose current state is `hash`.
+pub(crate) fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
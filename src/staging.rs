@@ -0,0 +1,98 @@
+//! Batch buffers laid out for direct upload to a GPU.
+
+use crate::{IMAGE_COLUMNS, IMAGE_ROWS};
+use std::mem::size_of;
+
+/// Alignment and row pitch of a [`StagingBuffer`].
+///
+/// GPU APIs commonly require copies to start at an aligned address and each row of an image to
+/// start at a multiple of some pitch (256 bytes for `wgpu` texture copies, for example).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StagingLayout {
+    /// Alignment of the start of the buffer, in bytes. Must be a power of two.
+    pub alignment: usize,
+    /// Distance from the start of one image row to the start of the next, in bytes. Must be at
+    /// least the size of a row of `f32` pixels.
+    pub row_pitch: usize,
+}
+
+impl StagingLayout {
+    /// Check that the layout is usable.
+    pub(crate) fn validate(&self) {
+        assert!(
+            self.alignment.is_power_of_two(),
+            "Staging buffer alignment must be a power of two."
+        );
+        assert!(
+            self.row_pitch >= IMAGE_COLUMNS * size_of::<f32>(),
+            "Staging buffer row pitch is too small to hold a row of pixels."
+        );
+        assert!(
+            self.row_pitch.is_multiple_of(size_of::<f32>()),
+            "Staging buffer row pitch must be a multiple of the pixel size."
+        );
+    }
+}
+
+/// `f32` pixels of a batch, packed into an aligned buffer with padded rows.
+pub struct StagingBuffer {
+    storage: Vec<u8>,
+    offset: usize,
+    len: usize,
+    layout: StagingLayout,
+}
+
+impl StagingBuffer {
+    /// A buffer with the given layout and room for `images` images, all zero until written.
+    pub(crate) fn zeroed(images: usize, layout: StagingLayout) -> StagingBuffer {
+        layout.validate();
+        let len = images * IMAGE_ROWS * layout.row_pitch;
+
+        // Over-allocate so that an aligned start is guaranteed to exist within the storage.
+        let storage = vec![0; len + layout.alignment - 1];
+        let offset = storage.as_ptr().align_offset(layout.alignment);
+        StagingBuffer {
+            storage,
+            offset,
+            len,
+            layout,
+        }
+    }
+
+    /// Pack the 784 `pixels` of the image at `index` into its padded rows.
+    pub(crate) fn write_image(&mut self, index: usize, pixels: &[f32]) {
+        let first_row = index * IMAGE_ROWS;
+        for (row, pixels) in pixels.chunks(IMAGE_COLUMNS).enumerate() {
+            let start = self.offset + (first_row + row) * self.layout.row_pitch;
+            for (column, pixel) in pixels.iter().enumerate() {
+                let at = start + column * size_of::<f32>();
+                self.storage[at..at + size_of::<f32>()].copy_from_slice(&pixel.to_ne_bytes());
+            }
+        }
+    }
+
+    /// The packed bytes, starting at an address aligned to the layout's alignment.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.storage[self.offset..self.offset + self.len]
+    }
+
+    /// Layout the buffer was packed with.
+    #[must_use]
+    pub fn layout(&self) -> StagingLayout {
+        self.layout
+    }
+
+    /// Number of image rows in the buffer, 28 per image.
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.len / self.layout.row_pitch
+    }
+
+    /// Number of images in the buffer.
+    #[must_use]
+    pub fn images(&self) -> usize {
+        self.rows() / IMAGE_ROWS
+    }
+}
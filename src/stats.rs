@@ -0,0 +1,381 @@
+//! Summary statistics of the dataset.
+
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES};
+use std::convert::TryFrom;
+
+/// Per-pixel statistics of the images of one digit, returned by [`Mnist::class_stats`].
+///
+/// Pixel values are scaled to `[0, 1]`, and `mean` and `variance` hold one value per pixel
+/// position in row-major order. Both are NaN for a digit with no samples.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassStats {
+    /// Number of samples of the digit.
+    pub count: usize,
+    /// Mean of each pixel.
+    pub mean: Vec<f32>,
+    /// Population variance of each pixel.
+    pub variance: Vec<f32>,
+}
+
+impl Mnist {
+    /// Number of samples of each digit in the split, indexed by label.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let counts = mnist.label_counts(Split::Train);
+    /// assert_eq!(counts.iter().sum::<usize>(), 60_000);
+    /// ```
+    #[must_use]
+    pub fn label_counts(&self, split: Split) -> [usize; NUM_CLASSES] {
+        label_counts(self.labels(split))
+    }
+
+    /// Indices of the split's samples of each digit, indexed by label and in ascending order.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let sevens = &mnist.class_indices(Split::Test)[7];
+    /// assert!(sevens.iter().all(|&i| mnist.test_labels[i] == 7));
+    /// ```
+    #[must_use]
+    pub fn class_indices(&self, split: Split) -> [Vec<usize>; NUM_CLASSES] {
+        class_indices(self.labels(split))
+    }
+
+    /// Number of the split's pixels falling into each of `bins` equal-width intensity buckets
+    /// spanning 0 to 255, darkest first. With 256 bins, bucket `i` counts the pixels of value
+    /// `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero or more than 256.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    ///
+    /// // Share of pixels in the brightest quarter of the range.
+    /// let histogram = mnist.intensity_histogram(Split::Train, 4);
+    /// let share = histogram[3] as f64 / histogram.iter().sum::<usize>() as f64;
+    /// ```
+    #[must_use]
+    pub fn intensity_histogram(&self, split: Split, bins: usize) -> Vec<usize> {
+        intensity_histogram(self.images(split), bins)
+    }
+
+    /// Mean and standard deviation of every pixel of every image in the split, scaled to
+    /// `[0, 1]` as [`Normalization::Standardize`](crate::Normalization::Standardize) expects, computed in a single pass.
+    ///
+    /// Both are NaN if the split is empty.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{Mnist, Normalization, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let (mean, std) = mnist.pixel_stats(Split::Train);
+    /// let normalization = Normalization::Standardize { mean, std };
+    /// ```
+    #[must_use]
+    pub fn pixel_stats(&self, split: Split) -> (f32, f32) {
+        pixel_stats(self.images(split))
+    }
+
+    /// Mean and standard deviation of each of the 784 pixel positions across the images of the
+    /// split, in row-major order and scaled to `[0, 1]`, computed in a single pass.
+    ///
+    /// Every value is NaN if the split is empty.
+    #[must_use]
+    pub fn per_pixel_stats(&self, split: Split) -> (Vec<f32>, Vec<f32>) {
+        per_pixel_stats(self.images(split))
+    }
+
+    /// Mean and variance images of each digit in the split, indexed by label, as used by
+    /// Gaussian naive Bayes classifiers.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let stats = mnist.class_stats(Split::Train);
+    ///
+    /// // How much the ones differ from each other overall.
+    /// let spread: f32 = stats[1].variance.iter().sum();
+    /// ```
+    #[must_use]
+    pub fn class_stats(&self, split: Split) -> [ClassStats; NUM_CLASSES] {
+        class_stats(self.images(split), self.labels(split))
+    }
+
+    /// The average image of each digit in the split, indexed by label, with every pixel rounded to
+    /// the nearest integer. A class with no samples averages to a blank image.
+    ///
+    /// Blurry but recognisable digits are a quick sign that images and labels line up; a
+    /// shuffling bug that separates them turns every average into the same smudge.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::{print_image, Mnist, Split};
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// let means = mnist.class_mean_images(Split::Train);
+    /// print_image(&means[4], 4);
+    /// ```
+    #[must_use]
+    pub fn class_mean_images(
+        &self,
+        split: Split,
+    ) -> [[u8; IMAGE_ROWS * IMAGE_COLUMNS]; NUM_CLASSES] {
+        class_means(self.images(split), self.labels(split))
+    }
+}
+
+impl ImageSet {
+    /// Number of samples of each digit in the set, indexed by label.
+    #[must_use]
+    pub fn label_counts(&self) -> [usize; NUM_CLASSES] {
+        label_counts(&self.labels)
+    }
+
+    /// Indices of the set's samples of each digit, indexed by label and in ascending order.
+    #[must_use]
+    pub fn class_indices(&self) -> [Vec<usize>; NUM_CLASSES] {
+        class_indices(&self.labels)
+    }
+
+    /// Number of the set's pixels falling into each of `bins` equal-width intensity buckets, as
+    /// counted by [`Mnist::intensity_histogram`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero or more than 256.
+    #[must_use]
+    pub fn intensity_histogram(&self, bins: usize) -> Vec<usize> {
+        intensity_histogram(&self.images, bins)
+    }
+
+    /// Mean and standard deviation of every pixel of every image in the set, as computed by
+    /// [`Mnist::pixel_stats`].
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_mnist::ImageSet;
+    ///
+    /// // Half the pixels are black and half white.
+    /// let set = ImageSet { images: vec![[0; 784], [255; 784]], labels: vec![0, 1] };
+    /// assert_eq!(set.pixel_stats(), (0.5, 0.5));
+    ///
+    /// let empty = ImageSet { images: Vec::new(), labels: Vec::new() };
+    /// assert!(empty.pixel_stats().0.is_nan());
+    /// ```
+    #[must_use]
+    pub fn pixel_stats(&self) -> (f32, f32) {
+        pixel_stats(&self.images)
+    }
+
+    /// Mean and standard deviation of each pixel position across the images of the set, as
+    /// computed by [`Mnist::per_pixel_stats`].
+    #[must_use]
+    pub fn per_pixel_stats(&self) -> (Vec<f32>, Vec<f32>) {
+        per_pixel_stats(&self.images)
+    }
+
+    /// Mean and variance images of each digit in the set, as computed by
+    /// [`Mnist::class_stats`].
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_mnist::ImageSet;
+    ///
+    /// let mut dot = [0; 784];
+    /// dot[0] = 255;
+    /// let set = ImageSet { images: vec![[0; 784], dot, dot], labels: vec![3, 3, 5] };
+    /// let stats = set.class_stats();
+    ///
+    /// // Class 3 has one black image and one with a dot in the corner.
+    /// assert_eq!(stats[3].count, 2);
+    /// assert_eq!((stats[3].mean[0], stats[3].variance[0]), (0.5, 0.25));
+    /// assert_eq!((stats[3].mean[1], stats[3].variance[1]), (0.0, 0.0));
+    ///
+    /// // Class 5 only has the dot, and class 0 has nothing.
+    /// assert_eq!((stats[5].mean[0], stats[5].variance[0]), (1.0, 0.0));
+    /// assert_eq!(stats[0].count, 0);
+    /// assert!(stats[0].mean[0].is_nan());
+    /// ```
+    #[must_use]
+    pub fn class_stats(&self) -> [ClassStats; NUM_CLASSES] {
+        class_stats(&self.images, &self.labels)
+    }
+
+    /// The average image of each digit in the set, as computed by
+    /// [`Mnist::class_mean_images`].
+    #[must_use]
+    pub fn class_mean_images(&self) -> [[u8; IMAGE_ROWS * IMAGE_COLUMNS]; NUM_CLASSES] {
+        class_means(&self.images, &self.labels)
+    }
+}
+
+pub(crate) fn label_counts(labels: &[u8]) -> [usize; NUM_CLASSES] {
+    let mut counts = [0; NUM_CLASSES];
+    for &label in labels {
+        counts[usize::from(label)] += 1;
+    }
+    counts
+}
+
+fn class_indices(labels: &[u8]) -> [Vec<usize>; NUM_CLASSES] {
+    let mut indices: [Vec<usize>; NUM_CLASSES] = Default::default();
+    for (index, &label) in labels.iter().enumerate() {
+        indices[usize::from(label)].push(index);
+    }
+    indices
+}
+
+pub(crate) fn intensity_histogram(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    bins: usize,
+) -> Vec<usize> {
+    assert!(
+        (1..=256).contains(&bins),
+        "Number of bins must be between 1 and 256."
+    );
+    let mut values = [0; 256];
+    for &pixel in images.iter().flatten() {
+        values[usize::from(pixel)] += 1;
+    }
+
+    let mut counts = vec![0; bins];
+    for (value, count) in values.iter().enumerate() {
+        counts[value * bins / 256] += count;
+    }
+    counts
+}
+
+/// Mean and standard deviation of all pixels, scaled to `[0, 1]`.
+fn pixel_stats(images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]]) -> (f32, f32) {
+    // Sums of bytes and their squares stay exact in an `f64` for any realistic dataset size.
+    let (mut count, mut sum, mut sum_squares) = (0.0, 0.0, 0.0);
+    for &pixel in images.iter().flatten() {
+        let pixel = f64::from(pixel);
+        count += 1.0;
+        sum += pixel;
+        sum_squares += pixel * pixel;
+    }
+    let (mean, variance) = mean_and_variance(count, sum, sum_squares);
+    (mean, variance.sqrt())
+}
+
+/// Mean and standard deviation of each pixel position, scaled to `[0, 1]`.
+fn per_pixel_stats(images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]]) -> (Vec<f32>, Vec<f32>) {
+    let mut moments = PixelMoments::new();
+    for image in images {
+        moments.add(image);
+    }
+    moments
+        .mean_and_variance()
+        .map(|(mean, variance)| (mean, variance.sqrt()))
+        .unzip()
+}
+
+pub(crate) fn class_stats(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+) -> [ClassStats; NUM_CLASSES] {
+    let mut moments: [PixelMoments; NUM_CLASSES] = std::array::from_fn(|_| PixelMoments::new());
+    for (image, &label) in images.iter().zip(labels) {
+        moments[usize::from(label)].add(image);
+    }
+    moments.map(|moments| {
+        let (mean, variance) = moments.mean_and_variance().unzip();
+        ClassStats {
+            count: moments.count,
+            mean,
+            variance,
+        }
+    })
+}
+
+/// Running sums of each pixel position's values and squared values over a set of images.
+struct PixelMoments {
+    count: usize,
+    sums: Vec<f64>,
+    sums_squares: Vec<f64>,
+}
+
+impl PixelMoments {
+    fn new() -> PixelMoments {
+        PixelMoments {
+            count: 0,
+            sums: vec![0.0; IMAGE_ROWS * IMAGE_COLUMNS],
+            sums_squares: vec![0.0; IMAGE_ROWS * IMAGE_COLUMNS],
+        }
+    }
+
+    fn add(&mut self, image: &[u8; IMAGE_ROWS * IMAGE_COLUMNS]) {
+        self.count += 1;
+        let sums = self.sums.iter_mut().zip(&mut self.sums_squares);
+        for ((sum, sum_squares), &pixel) in sums.zip(image.iter()) {
+            let pixel = f64::from(pixel);
+            *sum += pixel;
+            *sum_squares += pixel * pixel;
+        }
+    }
+
+    /// Mean and variance of each pixel position, scaled to `[0, 1]`.
+    fn mean_and_variance(&self) -> impl Iterator<Item = (f32, f32)> + '_ {
+        let count = f64::from(u32::try_from(self.count).unwrap());
+        self.sums
+            .iter()
+            .zip(&self.sums_squares)
+            .map(move |(&sum, &sum_squares)| mean_and_variance(count, sum, sum_squares))
+    }
+}
+
+/// Population mean and variance of `count` raw pixel values, scaled to `[0, 1]`.
+#[allow(clippy::cast_possible_truncation)]
+fn mean_and_variance(count: f64, sum: f64, sum_squares: f64) -> (f32, f32) {
+    let mean = sum / count;
+    // Rounding can push the variance of a constant pixel a hair below zero; `abs` undoes that
+    // while keeping the NaN of an empty dataset.
+    let variance = (sum_squares / count - mean * mean).abs();
+    ((mean / 255.0) as f32, (variance / (255.0 * 255.0)) as f32)
+}
+
+fn class_means(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+) -> [[u8; IMAGE_ROWS * IMAGE_COLUMNS]; NUM_CLASSES] {
+    let mut sums = vec![[0_u64; IMAGE_ROWS * IMAGE_COLUMNS]; NUM_CLASSES];
+    let mut counts = [0_u64; NUM_CLASSES];
+    for (image, &label) in images.iter().zip(labels) {
+        counts[usize::from(label)] += 1;
+        for (sum, &pixel) in sums[usize::from(label)].iter_mut().zip(image.iter()) {
+            *sum += u64::from(pixel);
+        }
+    }
+
+    let mut means = [[0; IMAGE_ROWS * IMAGE_COLUMNS]; NUM_CLASSES];
+    let classes = means.iter_mut().zip(&sums).zip(counts);
+    for ((mean, sum), count) in classes.filter(|&(_, count)| count > 0) {
+        for (pixel, &total) in mean.iter_mut().zip(sum.iter()) {
+            *pixel = u8::try_from((total + count / 2) / count).unwrap();
+        }
+    }
+    means
+}
@@ -0,0 +1,281 @@
+//! Reading samples one at a time in fixed memory, for targets too small to hold the dataset.
+//!
+//! Only needs `core`, so it is what the crate offers without the `std` feature.
+
+use crate::{IMAGES_MAGIC_NUMBER, IMAGE_COLUMNS, IMAGE_ROWS, LABELS_MAGIC_NUMBER, NUM_CLASSES};
+use core::convert::TryFrom;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+/// Where an [`IdxStream`] reads its bytes from.
+///
+/// With the `std` feature, every [`std::io::Read`] is one, failing with [`std::io::Error`].
+/// Anywhere, [`SliceSource`] reads a byte slice and [`FnSource`] calls a closure, such as a
+/// driver reading from an SPI flash chip, both failing with [`StreamError`].
+pub trait ByteSource {
+    /// What reading can fail with, also used to report files that are not what they should be.
+    type Error;
+
+    /// Fill `buffer` with the next bytes, failing if the source ends first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source fails or ends before `buffer` is full.
+    fn fill(&mut self, buffer: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// The error reporting that the files are malformed, as `problem` describes.
+    fn invalid(problem: &'static str) -> Self::Error;
+}
+
+#[cfg(feature = "std")]
+impl<R: io::Read> ByteSource for R {
+    type Error = io::Error;
+
+    fn fill(&mut self, buffer: &mut [u8]) -> io::Result<()> {
+        self.read_exact(buffer)
+    }
+
+    fn invalid(problem: &'static str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, problem)
+    }
+}
+
+/// A [`ByteSource`] reading a byte slice from the start, such as a file in memory-mapped flash,
+/// failing with [`StreamError::Ended`] if it ends first.
+///
+/// Its error type is the same with or without the `std` feature, unlike reading the slice
+/// through [`std::io::Read`].
+///
+/// # Examples
+/// ```
+/// use rust_mnist::{IdxStream, SliceSource, StreamError};
+///
+/// // A single sample, labelled 7, whose image is cut short.
+/// let mut images = vec![0, 0, 8, 3, 0, 0, 0, 1, 0, 0, 0, 28, 0, 0, 0, 28];
+/// images.extend([0; 700]);
+/// let labels = [0, 0, 8, 1, 0, 0, 0, 1, 7];
+///
+/// let mut stream = IdxStream::new(SliceSource(&images), SliceSource(&labels))?;
+/// assert_eq!(stream.next_into(&mut [0; 784]), Err(StreamError::Ended));
+/// # Ok::<(), StreamError>(())
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SliceSource<'a>(pub &'a [u8]);
+
+impl ByteSource for SliceSource<'_> {
+    type Error = StreamError;
+
+    fn fill(&mut self, buffer: &mut [u8]) -> Result<(), StreamError> {
+        if self.0.len() < buffer.len() {
+            return Err(StreamError::Ended);
+        }
+        let (read, rest) = self.0.split_at(buffer.len());
+        buffer.copy_from_slice(read);
+        self.0 = rest;
+        Ok(())
+    }
+
+    fn invalid(problem: &'static str) -> StreamError {
+        StreamError::Invalid(problem)
+    }
+}
+
+/// A [`ByteSource`] calling a closure to fill each buffer with the next bytes, failing with
+/// [`StreamError::Source`] if the closure does.
+///
+/// # Examples
+/// ```
+/// use rust_mnist::{FnSource, IdxStream};
+///
+/// // Reads `bytes` in order, as a driver might read an external flash chip.
+/// fn flash(bytes: &[u8]) -> impl FnMut(&mut [u8]) -> Result<(), &'static str> + '_ {
+///     let mut offset = 0;
+///     move |buffer: &mut [u8]| {
+///         let read = bytes.get(offset..offset + buffer.len()).ok_or("End of flash.")?;
+///         buffer.copy_from_slice(read);
+///         offset += buffer.len();
+///         Ok(())
+///     }
+/// }
+///
+/// // A single sample, labelled 7.
+/// let mut images = vec![0, 0, 8, 3, 0, 0, 0, 1, 0, 0, 0, 28, 0, 0, 0, 28];
+/// images.extend([0; 784]);
+/// let labels = [0, 0, 8, 1, 0, 0, 0, 1, 7];
+///
+/// let mut stream = IdxStream::new(FnSource(flash(&images)), FnSource(flash(&labels)))?;
+/// let mut image = [0; 784];
+/// assert_eq!(stream.next_into(&mut image)?, Some(7));
+/// assert_eq!(stream.next_into(&mut image)?, None);
+/// # Ok::<(), rust_mnist::StreamError<&str>>(())
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FnSource<F>(pub F);
+
+impl<F, E> ByteSource for FnSource<F>
+where
+    F: FnMut(&mut [u8]) -> Result<(), E>,
+{
+    type Error = StreamError<E>;
+
+    fn fill(&mut self, buffer: &mut [u8]) -> Result<(), StreamError<E>> {
+        (self.0)(buffer).map_err(StreamError::Source)
+    }
+
+    fn invalid(problem: &'static str) -> StreamError<E> {
+        StreamError::Invalid(problem)
+    }
+}
+
+/// Why an [`IdxStream`] over a [`SliceSource`] or a [`FnSource`] failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamError<E = core::convert::Infallible> {
+    /// The closure of a [`FnSource`] failed.
+    Source(E),
+    /// A [`SliceSource`] ended before the last sample its header declared.
+    Ended,
+    /// The files are not IDX files of 28x28 images and their labels, as described.
+    Invalid(&'static str),
+}
+
+impl<E: fmt::Display> fmt::Display for StreamError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Source(err) => err.fmt(f),
+            StreamError::Ended => f.write_str("The data ends before its last sample."),
+            StreamError::Invalid(problem) => f.write_str(problem),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug + fmt::Display> std::error::Error for StreamError<E> {}
+
+/// Pull parser over a pair of IDX image and label files, reading one sample at a time into a
+/// buffer owned by the caller.
+///
+/// The stream never allocates: each call to [`IdxStream::next_into`] reads exactly one image and
+/// one label from the sources, so memory use is the caller's 784-byte buffer plus whatever the
+/// sources themselves hold. It works without the `std` feature, on a microcontroller with the
+/// test set in memory-mapped external flash read through a [`SliceSource`], or through a
+/// [`FnSource`].
+///
+/// # Examples
+/// ```no_run
+/// use std::fs::File;
+/// use std::io::BufReader;
+/// use rust_mnist::IdxStream;
+///
+/// let mut stream = IdxStream::new(
+///     BufReader::new(File::open("t10k-images-idx3-ubyte")?),
+///     BufReader::new(File::open("t10k-labels-idx1-ubyte")?),
+/// )?;
+/// let mut image = [0; 784];
+/// let mut sevens = 0;
+/// while let Some(label) = stream.next_into(&mut image)? {
+///     if label == 7 {
+///         sevens += 1;
+///     }
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct IdxStream<I, L> {
+    images: I,
+    labels: L,
+    len: usize,
+    remaining: usize,
+}
+
+impl<I: ByteSource, L: ByteSource<Error = I::Error>> IdxStream<I, L> {
+    /// Read and check the headers of an image file and its label file, leaving both sources at
+    /// their first sample.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a header cannot be read, is not that of an IDX file of 28x28 images or
+    /// of labels, or if the two files hold different numbers of samples.
+    pub fn new(mut images: I, mut labels: L) -> Result<IdxStream<I, L>, I::Error> {
+        if read_u32(&mut images)? != IMAGES_MAGIC_NUMBER {
+            return Err(I::invalid(
+                "Magic number for image data does not match expected value.",
+            ));
+        }
+        let len = read_u32(&mut images)?;
+        if (read_u32(&mut images)?, read_u32(&mut images)?) != (IMAGE_ROWS, IMAGE_COLUMNS) {
+            return Err(I::invalid("Images are not 28x28."));
+        }
+        if read_u32(&mut labels)? != LABELS_MAGIC_NUMBER {
+            return Err(I::invalid(
+                "Magic number for labels does not match expected value.",
+            ));
+        }
+        if read_u32(&mut labels)? != len {
+            return Err(I::invalid(
+                "Number of labels does not match number of images.",
+            ));
+        }
+        Ok(IdxStream {
+            images,
+            labels,
+            len,
+            remaining: len,
+        })
+    }
+
+    /// Read the next image into `image` and return its label, or `None` once every sample has
+    /// been read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a source fails or ends before the last sample its header declared, or
+    /// if the label is not below [`NUM_CLASSES`](crate::NUM_CLASSES). `image` may then hold part
+    /// of a sample.
+    pub fn next_into(
+        &mut self,
+        image: &mut [u8; IMAGE_ROWS * IMAGE_COLUMNS],
+    ) -> Result<Option<u8>, I::Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.images.fill(image)?;
+        let mut label = [0];
+        self.labels.fill(&mut label)?;
+        self.remaining -= 1;
+        if usize::from(label[0]) >= NUM_CLASSES {
+            return Err(I::invalid("Label is not below the number of classes."));
+        }
+        Ok(Some(label[0]))
+    }
+
+    /// Number of samples the files declare.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the files declare no samples.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of samples not read yet.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Give back the image and label sources, positioned after the last sample read.
+    #[must_use]
+    pub fn into_inner(self) -> (I, L) {
+        (self.images, self.labels)
+    }
+}
+
+/// Read a big-endian `u32` header field.
+fn read_u32<S: ByteSource>(source: &mut S) -> Result<usize, S::Error> {
+    let mut bytes = [0; 4];
+    source.fill(&mut bytes)?;
+    usize::try_from(u32::from_be_bytes(bytes)).map_err(|_| S::invalid("Header field too large."))
+}
@@ -0,0 +1,219 @@
+//! Gathering samples picked by index, the building block of samplers and splitters.
+
+use crate::summary::LabelHistogram;
+use crate::{ImageSet, Mnist, MnistImage, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use std::fmt;
+use std::iter::FusedIterator;
+
+/// A borrowed selection of samples picked by index, created by [`ImageSet::subset_view`] or
+/// [`Mnist::subset_view`].
+///
+/// Samples are visited in the order of the indices, which may repeat. Nothing is copied until
+/// [`SubsetView::to_image_set`] is called.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::{Mnist, Split};
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+/// let sevens: Vec<usize> = (0..mnist.test_labels.len())
+///     .filter(|&i| mnist.test_labels[i] == 7)
+///     .collect();
+/// let view = mnist.subset_view(Split::Test, &sevens);
+/// assert!(view.iter().all(|(_, label)| label == 7));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubsetView<'a> {
+    images: &'a [[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &'a [u8],
+    indices: &'a [usize],
+}
+
+impl<'a> SubsetView<'a> {
+    /// Number of samples in the view, which is the number of indices.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Whether the view holds no samples.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// The indices of the samples in the underlying set.
+    #[must_use]
+    pub fn indices(&self) -> &'a [usize] {
+        self.indices
+    }
+
+    /// Sample `position` of the view, as its image and label.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is out of bounds.
+    #[must_use]
+    pub fn get(&self, position: usize) -> (&'a [u8; IMAGE_ROWS * IMAGE_COLUMNS], u8) {
+        let index = self.indices[position];
+        (&self.images[index], self.labels[index])
+    }
+
+    /// Image `position` of the view as an [`MnistImage`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is out of bounds.
+    #[must_use]
+    pub fn image(&self, position: usize) -> MnistImage {
+        MnistImage(*self.get(position).0)
+    }
+
+    /// Iterator over the images and labels of the view, in the order of the indices.
+    pub fn iter(&self) -> SubsetIter<'a> {
+        SubsetIter {
+            view: *self,
+            position: 0,
+        }
+    }
+
+    /// An owned copy of the samples in the view.
+    #[must_use]
+    pub fn to_image_set(&self) -> ImageSet {
+        gather(self.images, self.labels, self.indices)
+    }
+}
+
+impl<'a> IntoIterator for SubsetView<'a> {
+    type Item = (&'a [u8; IMAGE_ROWS * IMAGE_COLUMNS], u8);
+    type IntoIter = SubsetIter<'a>;
+
+    fn into_iter(self) -> SubsetIter<'a> {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &SubsetView<'a> {
+    type Item = (&'a [u8; IMAGE_ROWS * IMAGE_COLUMNS], u8);
+    type IntoIter = SubsetIter<'a>;
+
+    fn into_iter(self) -> SubsetIter<'a> {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for SubsetView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels: Vec<u8> = self.iter().map(|(_, label)| label).collect();
+        f.debug_struct("SubsetView")
+            .field("image_shape", &(IMAGE_ROWS, IMAGE_COLUMNS))
+            .field("len", &self.len())
+            .field("label_counts", &LabelHistogram(&labels))
+            .finish()
+    }
+}
+
+/// Iterator over the samples of a [`SubsetView`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SubsetIter<'a> {
+    view: SubsetView<'a>,
+    position: usize,
+}
+
+impl<'a> Iterator for SubsetIter<'a> {
+    type Item = (&'a [u8; IMAGE_ROWS * IMAGE_COLUMNS], u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position == self.view.len() {
+            return None;
+        }
+        self.position += 1;
+        Some(self.view.get(self.position - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.view.len() - self.position;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for SubsetIter<'_> {}
+
+impl FusedIterator for SubsetIter<'_> {}
+
+impl Mnist {
+    /// A copy of the samples of the split at `indices`, in that order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of bounds.
+    #[must_use]
+    pub fn subset(&self, split: Split, indices: &[usize]) -> ImageSet {
+        gather(self.images(split), self.labels(split), indices)
+    }
+
+    /// A borrowed view of the samples of the split at `indices`, in that order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of bounds.
+    #[must_use]
+    pub fn subset_view<'a>(&'a self, split: Split, indices: &'a [usize]) -> SubsetView<'a> {
+        subset_view(self.images(split), self.labels(split), indices)
+    }
+}
+
+impl ImageSet {
+    /// A copy of the samples at `indices`, in that order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of bounds.
+    #[must_use]
+    pub fn subset(&self, indices: &[usize]) -> ImageSet {
+        gather(&self.images, &self.labels, indices)
+    }
+
+    /// A borrowed view of the samples at `indices`, in that order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of bounds.
+    #[must_use]
+    pub fn subset_view<'a>(&'a self, indices: &'a [usize]) -> SubsetView<'a> {
+        subset_view(&self.images, &self.labels, indices)
+    }
+}
+
+fn subset_view<'a>(
+    images: &'a [[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &'a [u8],
+    indices: &'a [usize],
+) -> SubsetView<'a> {
+    // Check up front, so that reading the view never panics.
+    let len = images.len().min(labels.len());
+    assert!(
+        indices.iter().all(|&index| index < len),
+        "Subset index out of bounds for {} samples.",
+        len
+    );
+    SubsetView {
+        images,
+        labels,
+        indices,
+    }
+}
+
+/// Copy the samples at `indices`, in that order.
+pub(crate) fn gather(
+    images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &[u8],
+    indices: &[usize],
+) -> ImageSet {
+    ImageSet {
+        images: indices.iter().map(|&index| images[index]).collect(),
+        labels: indices.iter().map(|&index| labels[index]).collect(),
+    }
+}
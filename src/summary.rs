@@ -0,0 +1,193 @@
+//! A printable overview of the dataset.
+
+use crate::split::{fnv1a, FNV_OFFSET_BASIS};
+use crate::stats::label_counts;
+use crate::{ImageSet, Mnist, Split, IMAGE_COLUMNS, IMAGE_ROWS, NUM_CLASSES};
+use std::fmt;
+use std::mem::size_of;
+
+/// Overview of a loaded dataset, returned by [`Mnist::summary`]. Its [`Display`](fmt::Display)
+/// implementation prints everything in one block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    /// Summary of the training split.
+    pub train: SplitSummary,
+    /// Summary of the testing split.
+    pub test: SplitSummary,
+    /// Rows and columns of every image.
+    pub image_shape: (usize, usize),
+    /// Bytes of pixel and label data held in memory by both splits.
+    pub memory_bytes: usize,
+}
+
+/// Overview of one split of the dataset, part of a [`Summary`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SplitSummary {
+    /// Number of samples.
+    pub len: usize,
+    /// Number of samples of each digit, indexed by label.
+    pub label_counts: [usize; NUM_CLASSES],
+    /// Darkest and brightest pixel values, or `None` if the split is empty.
+    pub pixel_range: Option<(u8, u8)>,
+    /// 64-bit FNV-1a hash of every image followed by every label. Equal checksums on two
+    /// machines mean they loaded identical data.
+    pub checksum: u64,
+}
+
+impl Mnist {
+    /// Gather counts, class balance, image shape, pixel range, memory footprint and checksums
+    /// of both splits into a [`Summary`] that prints as a report.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use rust_mnist::Mnist;
+    ///
+    /// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+    /// println!("{}", mnist.summary());
+    /// ```
+    #[must_use]
+    pub fn summary(&self) -> Summary {
+        let train = self.split_summary(Split::Train);
+        let test = self.split_summary(Split::Test);
+        let memory_bytes = (train.len + test.len) * (IMAGE_ROWS * IMAGE_COLUMNS + size_of::<u8>());
+        Summary {
+            train,
+            test,
+            image_shape: (IMAGE_ROWS, IMAGE_COLUMNS),
+            memory_bytes,
+        }
+    }
+
+    fn split_summary(&self, split: Split) -> SplitSummary {
+        let images = self.images(split);
+        let labels = self.labels(split);
+
+        let pixels = images.iter().flatten().copied();
+        let pixel_range = pixels.clone().min().zip(pixels.max());
+        let checksum = checksum(images, labels);
+
+        SplitSummary {
+            len: labels.len(),
+            label_counts: label_counts(labels),
+            pixel_range,
+            checksum,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (rows, columns) = self.image_shape;
+        writeln!(f, "MNIST dataset")?;
+        writeln!(f, "  Image shape:  {rows} x {columns}")?;
+        writeln!(
+            f,
+            "  Memory:       {} bytes ({} MiB)",
+            self.memory_bytes,
+            tenths(self.memory_bytes, 1 << 20)
+        )?;
+        writeln!(f)?;
+
+        writeln!(f, "  Split  Samples  Pixel range  Checksum")?;
+        for (name, split) in [("train", &self.train), ("test", &self.test)] {
+            let range = match split.pixel_range {
+                Some((min, max)) => format!("{min}..={max}"),
+                None => String::from("-"),
+            };
+            writeln!(
+                f,
+                "  {name:<5}  {:>7}  {range:<11}  {:016x}",
+                split.len, split.checksum
+            )?;
+        }
+        writeln!(f)?;
+
+        // Line the headings up with the counts, which are followed by their percentages.
+        writeln!(f, "  Label  {:>6}{:10}{:>6}", "Train", "", "Test")?;
+        for label in 0..NUM_CLASSES {
+            let share = |split: &SplitSummary| {
+                let count = split.label_counts[label];
+                format!("{count:>6} ({:>4}%)", tenths(count * 100, split.len))
+            };
+            writeln!(
+                f,
+                "  {label:>5}  {}  {}",
+                share(&self.train),
+                share(&self.test)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// A derived `Debug` would print every pixel, so these print the shape of the data instead.
+impl fmt::Debug for Mnist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mnist")
+            .field("image_shape", &(IMAGE_ROWS, IMAGE_COLUMNS))
+            .field("train", &SplitDebug(&self.train_data, &self.train_labels))
+            .field("test", &SplitDebug(&self.test_data, &self.test_labels))
+            .finish()
+    }
+}
+
+impl fmt::Debug for ImageSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageSet")
+            .field("image_shape", &(IMAGE_ROWS, IMAGE_COLUMNS))
+            .field("images", &self.images.len())
+            .field("labels", &self.labels.len())
+            .field("label_counts", &LabelHistogram(&self.labels))
+            .finish()
+    }
+}
+
+/// Sample counts and label histogram of one split, for [`Mnist`]'s `Debug` implementation.
+struct SplitDebug<'a>(&'a [[u8; IMAGE_ROWS * IMAGE_COLUMNS]], &'a [u8]);
+
+impl fmt::Debug for SplitDebug<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Split")
+            .field("images", &self.0.len())
+            .field("labels", &self.1.len())
+            .field("label_counts", &LabelHistogram(self.1))
+            .finish()
+    }
+}
+
+/// Number of samples of each label present, printed as a map from label to count.
+///
+/// Unlike [`label_counts`], it copes with labels of 10 and above, so that printing a damaged set
+/// never panics.
+pub(crate) struct LabelHistogram<'a>(pub(crate) &'a [u8]);
+
+impl fmt::Debug for LabelHistogram<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut counts = [0_usize; 256];
+        for &label in self.0 {
+            counts[usize::from(label)] += 1;
+        }
+        f.debug_map()
+            .entries(counts.iter().enumerate().filter(|(_, &count)| count > 0))
+            .finish()
+    }
+}
+
+/// 64-bit FNV-1a hash of every image followed by every label, as in [`SplitSummary::checksum`].
+pub(crate) fn checksum(images: &[[u8; IMAGE_ROWS * IMAGE_COLUMNS]], labels: &[u8]) -> u64 {
+    fnv1a(
+        images
+            .iter()
+            .fold(FNV_OFFSET_BASIS, |hash, image| fnv1a(hash, image)),
+        labels,
+    )
+}
+
+/// `numerator / denominator` with one decimal place, or zero if `denominator` is zero.
+pub(crate) fn tenths(numerator: usize, denominator: usize) -> String {
+    let tenths = (numerator * 10).checked_div(denominator).unwrap_or(0);
+    format!("{}.{}", tenths / 10, tenths % 10)
+}
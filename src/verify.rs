@@ -0,0 +1,156 @@
+//! Checking a dataset's IDX files without loading them.
+
+use crate::Mnist;
+use crate::{
+    open_idx, read_u32, IDX_FILENAMES, IMAGES_MAGIC_NUMBER, IMAGE_COLUMNS, IMAGE_ROWS,
+    LABELS_MAGIC_NUMBER, NUM_CLASSES, NUM_TEST_IMAGES, NUM_TRAIN_IMAGES, TEST_DATA_FILENAME,
+    TEST_LABEL_FILENAME, TRAIN_DATA_FILENAME, TRAIN_LABEL_FILENAME,
+};
+use std::io;
+use std::io::Read;
+use std::path::Path;
+
+impl Mnist {
+    /// Check the four IDX files in `dir` that [`Mnist::new`] reads, without loading or panicking.
+    ///
+    /// Every file must exist, carry the magic number and image dimensions of MNIST, and hold
+    /// exactly as many samples as its header says; every label must be a digit, and each split's
+    /// images and labels must agree on their count. If `strict`, the counts must also be the
+    /// 60,000 training and 10,000 testing samples of MNIST, as
+    /// [`LoadOptions::strict`](crate::LoadOptions::strict) requires; otherwise any count is
+    /// accepted, such as that of a subset written by `mnist subset`.
+    ///
+    /// Returns a description of each problem found, naming the file, or nothing if the dataset
+    /// is sound.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use std::path::Path;
+    /// use rust_mnist::Mnist;
+    ///
+    /// let problems = Mnist::verify(Path::new("data"), true);
+    /// if problems.is_empty() {
+    ///     let mnist = Mnist::new(Path::new("data"));
+    /// } else {
+    ///     problems.iter().for_each(|problem| eprintln!("{problem}"));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn verify(dir: &Path, strict: bool) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut counts = Vec::new();
+        for filename in IDX_FILENAMES {
+            let result = match open_idx(&dir.join(filename)) {
+                Ok(mut reader) => check_idx(&mut reader, filename, strict),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    Err(format!("{filename} is missing from {}.", dir.display()))
+                }
+                Err(err) => Err(format!("{filename} cannot be opened: {err}.")),
+            };
+            match result {
+                Ok(count) => counts.push((filename, count)),
+                Err(problem) => problems.push(problem),
+            }
+        }
+
+        for (images, labels) in [
+            (TRAIN_DATA_FILENAME, TRAIN_LABEL_FILENAME),
+            (TEST_DATA_FILENAME, TEST_LABEL_FILENAME),
+        ] {
+            let count = |name| {
+                counts
+                    .iter()
+                    .find(|&&(filename, _)| filename == name)
+                    .map(|&(_, count)| count)
+            };
+            if let (Some(image_count), Some(label_count)) = (count(images), count(labels)) {
+                if image_count != label_count {
+                    problems.push(format!(
+                        "{images} holds {image_count} images but {labels} holds {label_count} \
+                         labels."
+                    ));
+                }
+            }
+        }
+        problems
+    }
+}
+
+/// Check that `reader` holds the IDX file named `filename`, one of [`IDX_FILENAMES`], returning
+/// its sample count or describing the first problem found.
+///
+/// If `strict`, the count must be that of MNIST; otherwise it is taken from the header.
+pub(crate) fn check_idx(
+    reader: &mut impl Read,
+    filename: &str,
+    strict: bool,
+) -> Result<usize, String> {
+    let (magic_number, expected_count) = match filename {
+        TRAIN_DATA_FILENAME => (IMAGES_MAGIC_NUMBER, NUM_TRAIN_IMAGES),
+        TEST_DATA_FILENAME => (IMAGES_MAGIC_NUMBER, NUM_TEST_IMAGES),
+        TRAIN_LABEL_FILENAME => (LABELS_MAGIC_NUMBER, NUM_TRAIN_IMAGES),
+        _ => (LABELS_MAGIC_NUMBER, NUM_TEST_IMAGES),
+    };
+    let images = magic_number == IMAGES_MAGIC_NUMBER;
+    let mut header = vec![
+        ("magic number", Some(magic_number)),
+        ("sample count", strict.then_some(expected_count)),
+    ];
+    if images {
+        header.extend([
+            ("row count", Some(IMAGE_ROWS)),
+            ("column count", Some(IMAGE_COLUMNS)),
+        ]);
+    }
+
+    let mut values = Vec::with_capacity(header.len());
+    for (field, expected) in header {
+        let value = read_u32(reader).map_err(|_| format!("{filename} ends within its header."))?;
+        if let Some(expected) = expected.filter(|&expected| expected != value) {
+            return Err(format!(
+                "{filename} has {field} {value} where {expected} was expected."
+            ));
+        }
+        values.push(value);
+    }
+    let count = values[1];
+
+    // Stream the samples rather than holding them, only looking into labels.
+    let mut buffer = vec![0; 64 * 1024];
+    let mut len = 0;
+    let mut invalid_label = None;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(format!("{filename} cannot be read: {err}.")),
+        };
+        if !images && invalid_label.is_none() {
+            invalid_label = buffer[..read]
+                .iter()
+                .copied()
+                .find(|&label| usize::from(label) >= NUM_CLASSES);
+        }
+        len += read;
+    }
+
+    let expected_len = count
+        * if images {
+            IMAGE_ROWS * IMAGE_COLUMNS
+        } else {
+            1
+        };
+    if len != expected_len {
+        return Err(format!(
+            "{filename} holds {len} bytes of samples where {expected_len} were expected; it may \
+             be truncated or damaged."
+        ));
+    }
+    match invalid_label {
+        Some(label) => Err(format!(
+            "{filename} holds label {label}, which is not a digit."
+        )),
+        None => Ok(count),
+    }
+}
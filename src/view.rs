@@ -0,0 +1,154 @@
+//! Borrowed windows over a set of samples.
+
+use crate::summary::LabelHistogram;
+use crate::{ImageSet, Mnist, MnistImage, Split, IMAGE_COLUMNS, IMAGE_ROWS};
+use std::fmt;
+use std::iter::{Copied, Zip};
+use std::ops::{Bound, RangeBounds};
+use std::slice::Iter;
+
+/// A borrowed run of consecutive samples, created by [`ImageSet::slice`] or [`Mnist::slice`].
+///
+/// It copies no pixels, so cutting a set into chunks or epochs costs nothing. Call
+/// [`ImageSetView::to_image_set`] for an owned copy.
+///
+/// # Examples
+/// ```no_run
+/// use std::path::PathBuf;
+/// use rust_mnist::{Mnist, Split};
+///
+/// let mnist = Mnist::new(&PathBuf::from("examples").join("MNIST_data"));
+/// let validation = mnist.slice(Split::Train, 50_000..);
+/// for (image, label) in validation.slice(..100) {
+///     // ...
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageSetView<'a> {
+    /// The images in the window.
+    pub images: &'a [[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    /// Their labels.
+    pub labels: &'a [u8],
+}
+
+type ViewIter<'a> = Zip<Iter<'a, [u8; IMAGE_ROWS * IMAGE_COLUMNS]>, Copied<Iter<'a, u8>>>;
+
+impl<'a> ImageSetView<'a> {
+    /// Number of samples in the view.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Whether the view holds no samples.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// A narrower window of `range`, counted from the start of this view.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` reaches past the end of the view.
+    #[must_use]
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> ImageSetView<'a> {
+        slice(self.images, self.labels, range)
+    }
+
+    /// Image `index` of the view as an [`MnistImage`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[must_use]
+    pub fn image(&self, index: usize) -> MnistImage {
+        MnistImage(self.images[index])
+    }
+
+    /// Iterator over the images and labels of the view.
+    pub fn iter(&self) -> ViewIter<'a> {
+        self.images.iter().zip(self.labels.iter().copied())
+    }
+
+    /// An owned copy of the samples in the view.
+    #[must_use]
+    pub fn to_image_set(&self) -> ImageSet {
+        ImageSet {
+            images: self.images.to_vec(),
+            labels: self.labels.to_vec(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for ImageSetView<'a> {
+    type Item = (&'a [u8; IMAGE_ROWS * IMAGE_COLUMNS], u8);
+    type IntoIter = ViewIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &ImageSetView<'a> {
+    type Item = (&'a [u8; IMAGE_ROWS * IMAGE_COLUMNS], u8);
+    type IntoIter = ViewIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for ImageSetView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageSetView")
+            .field("image_shape", &(IMAGE_ROWS, IMAGE_COLUMNS))
+            .field("images", &self.images.len())
+            .field("labels", &self.labels.len())
+            .field("label_counts", &LabelHistogram(self.labels))
+            .finish()
+    }
+}
+
+impl Mnist {
+    /// A borrowed window of `range` over the samples of the split.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` reaches past the end of the split.
+    #[must_use]
+    pub fn slice(&self, split: Split, range: impl RangeBounds<usize>) -> ImageSetView<'_> {
+        slice(self.images(split), self.labels(split), range)
+    }
+}
+
+impl ImageSet {
+    /// A borrowed window of `range` over the samples of the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` reaches past the end of the set.
+    #[must_use]
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> ImageSetView<'_> {
+        slice(&self.images, &self.labels, range)
+    }
+
+    /// A borrowed view of the whole set.
+    #[must_use]
+    pub fn view(&self) -> ImageSetView<'_> {
+        self.slice(..)
+    }
+}
+
+fn slice<'a>(
+    images: &'a [[u8; IMAGE_ROWS * IMAGE_COLUMNS]],
+    labels: &'a [u8],
+    range: impl RangeBounds<usize>,
+) -> ImageSetView<'a> {
+    let bounds: (Bound<usize>, Bound<usize>) =
+        (range.start_bound().cloned(), range.end_bound().cloned());
+    ImageSetView {
+        images: &images[bounds],
+        labels: &labels[bounds],
+    }
+}
@@ -0,0 +1,93 @@
+//! JavaScript bindings, built with `wasm-pack build --features wasm`.
+//!
+//! ```js
+//! import init, { Mnist, Split } from "./pkg/rust_mnist.js";
+//!
+//! await init();
+//! const files = ["train-images-idx3-ubyte", "train-labels-idx1-ubyte",
+//!                "t10k-images-idx3-ubyte", "t10k-labels-idx1-ubyte"];
+//! const buffers = await Promise.all(
+//!     files.map(async (file) => new Uint8Array(await (await fetch(file)).arrayBuffer())));
+//! const mnist = new Mnist(...buffers);
+//! const pixels = mnist.image(Split.Train, 0); // Uint8Array of 784 row-major pixels
+//! const label = mnist.label(Split.Train, 0);
+//! ```
+
+use crate::{Mnist, Split};
+use wasm_bindgen::prelude::*;
+
+/// One of the two halves of the dataset.
+#[wasm_bindgen(js_name = Split)]
+#[derive(Clone, Copy)]
+pub enum JsSplit {
+    Train,
+    Test,
+}
+
+impl From<JsSplit> for Split {
+    fn from(split: JsSplit) -> Split {
+        match split {
+            JsSplit::Train => Split::Train,
+            JsSplit::Test => Split::Test,
+        }
+    }
+}
+
+/// The MNIST dataset, parsed from the contents of its four IDX files.
+#[wasm_bindgen(js_name = Mnist)]
+pub struct JsMnist {
+    inner: Mnist,
+}
+
+#[wasm_bindgen(js_class = Mnist)]
+impl JsMnist {
+    /// Parse the dataset from the uncompressed IDX files, as for [`Mnist::from_array_buffers`].
+    /// Throws if a file is malformed.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        train_images: &[u8],
+        train_labels: &[u8],
+        test_images: &[u8],
+        test_labels: &[u8],
+    ) -> Result<JsMnist, JsError> {
+        let inner =
+            Mnist::from_array_buffers(train_images, train_labels, test_images, test_labels)?;
+        Ok(JsMnist { inner })
+    }
+
+    /// Number of samples in the split.
+    #[must_use]
+    pub fn length(&self, split: JsSplit) -> usize {
+        self.inner.labels(split.into()).len()
+    }
+
+    /// Image `index` of the split as a `Uint8Array` of row-major pixels.
+    /// Throws if `index` is out of bounds.
+    pub fn image(&self, split: JsSplit, index: usize) -> Result<Vec<u8>, JsError> {
+        self.inner
+            .images(split.into())
+            .get(index)
+            .map(|image| image.to_vec())
+            .ok_or_else(|| out_of_bounds(index))
+    }
+
+    /// Label of sample `index` of the split.
+    /// Throws if `index` is out of bounds.
+    pub fn label(&self, split: JsSplit, index: usize) -> Result<u8, JsError> {
+        self.inner
+            .labels(split.into())
+            .get(index)
+            .copied()
+            .ok_or_else(|| out_of_bounds(index))
+    }
+
+    /// Every label of the split as a `Uint8Array`.
+    #[must_use]
+    pub fn labels(&self, split: JsSplit) -> Vec<u8> {
+        self.inner.labels(split.into()).to_vec()
+    }
+}
+
+fn out_of_bounds(index: usize) -> JsError {
+    JsError::new(&format!("Sample index {index} is out of bounds."))
+}